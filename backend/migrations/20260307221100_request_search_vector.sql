@@ -0,0 +1,14 @@
+-- Full-text search over requests.
+-- Title terms outrank description terms; category is indexed with the
+-- simple dictionary so exact category names remain searchable.
+
+ALTER TABLE app.requests
+ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+GENERATED ALWAYS AS (
+  setweight(to_tsvector('english', COALESCE(title, '')), 'A')
+  || setweight(to_tsvector('english', COALESCE(description, '')), 'B')
+  || setweight(to_tsvector('simple', COALESCE(category, '')), 'C')
+) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_requests_search_vector
+ON app.requests USING GIN (search_vector);
@@ -0,0 +1,34 @@
+-- Named request list views. A view is private to its owner unless
+-- `workspace_domain` is set, in which case everyone whose email shares
+-- that domain can use it.
+
+CREATE TABLE IF NOT EXISTS app.saved_views (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  owner_user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  name VARCHAR(80) NOT NULL CHECK (char_length(btrim(name)) > 0),
+  filters JSONB NOT NULL DEFAULT '{}'::jsonb CHECK (jsonb_typeof(filters) = 'object'),
+  sort VARCHAR(32),
+  page_size INTEGER CHECK (page_size IS NULL OR page_size BETWEEN 1 AND 100),
+  workspace_domain TEXT CHECK (
+    workspace_domain IS NULL
+    OR workspace_domain = lower(btrim(workspace_domain))
+  ),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_saved_views_owner_name_unique
+ON app.saved_views (owner_user_id, lower(name));
+CREATE INDEX IF NOT EXISTS idx_saved_views_workspace_domain
+ON app.saved_views (workspace_domain)
+WHERE workspace_domain IS NOT NULL;
+
+DROP TRIGGER IF EXISTS saved_views_set_updated_at ON app.saved_views;
+CREATE TRIGGER saved_views_set_updated_at
+BEFORE UPDATE ON app.saved_views
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+ALTER TABLE app.user_preferences
+ADD COLUMN IF NOT EXISTS default_view_id UUID
+REFERENCES app.saved_views(id) ON DELETE SET NULL;
@@ -0,0 +1,35 @@
+-- Reusable request templates. `title_template` and `description_template`
+-- may contain `{{name}}` placeholders filled in when a request is created
+-- from the template. Sharing works like saved views: a template is private
+-- unless `workspace_domain` is set.
+
+CREATE TABLE IF NOT EXISTS app.request_templates (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  owner_user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  name VARCHAR(80) NOT NULL CHECK (char_length(btrim(name)) > 0),
+  title_template VARCHAR(255) NOT NULL CHECK (char_length(btrim(title_template)) > 0),
+  description_template TEXT CHECK (
+    description_template IS NULL OR char_length(description_template) <= 5000
+  ),
+  category VARCHAR(20) NOT NULL CHECK (category IN ('IT', 'Ops', 'Admin', 'HR')),
+  priority VARCHAR(20) NOT NULL CHECK (priority IN ('low', 'medium', 'high')),
+  assignee_email TEXT,
+  workspace_domain TEXT CHECK (
+    workspace_domain IS NULL
+    OR workspace_domain = lower(btrim(workspace_domain))
+  ),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_request_templates_owner_name_unique
+ON app.request_templates (owner_user_id, lower(name));
+CREATE INDEX IF NOT EXISTS idx_request_templates_workspace_domain
+ON app.request_templates (workspace_domain)
+WHERE workspace_domain IS NOT NULL;
+
+DROP TRIGGER IF EXISTS request_templates_set_updated_at ON app.request_templates;
+CREATE TRIGGER request_templates_set_updated_at
+BEFORE UPDATE ON app.request_templates
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
@@ -0,0 +1,40 @@
+-- Recurring requests. A schedule creates a request from a template or by
+-- copying a source request each time its recurrence rule fires.
+-- `request_schedule_runs` records every occurrence that has been claimed;
+-- its primary key is what stops two scheduler replicas from creating the
+-- same occurrence twice.
+
+CREATE TABLE IF NOT EXISTS app.request_schedules (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  owner_user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  template_id UUID REFERENCES app.request_templates(id) ON DELETE CASCADE,
+  source_request_id UUID REFERENCES app.requests(id) ON DELETE CASCADE,
+  variables JSONB NOT NULL DEFAULT '{}'::jsonb CHECK (jsonb_typeof(variables) = 'object'),
+  rule VARCHAR(200) NOT NULL CHECK (char_length(btrim(rule)) > 0),
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  next_run_at TIMESTAMPTZ NOT NULL,
+  last_run_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK ((template_id IS NULL) <> (source_request_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_request_schedules_owner_user_id
+ON app.request_schedules (owner_user_id);
+CREATE INDEX IF NOT EXISTS idx_request_schedules_due
+ON app.request_schedules (next_run_at)
+WHERE active;
+
+DROP TRIGGER IF EXISTS request_schedules_set_updated_at ON app.request_schedules;
+CREATE TRIGGER request_schedules_set_updated_at
+BEFORE UPDATE ON app.request_schedules
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+CREATE TABLE IF NOT EXISTS app.request_schedule_runs (
+  schedule_id UUID NOT NULL REFERENCES app.request_schedules(id) ON DELETE CASCADE,
+  scheduled_for TIMESTAMPTZ NOT NULL,
+  request_id UUID REFERENCES app.requests(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (schedule_id, scheduled_for)
+);
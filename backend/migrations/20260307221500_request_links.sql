@@ -0,0 +1,33 @@
+-- Relationships between requests. Links are directed: the source
+-- `duplicates`, `blocks` or is the parent of (`parent_of`) the target.
+-- `relates_to` is symmetric and stored once per pair.
+
+CREATE TABLE IF NOT EXISTS app.request_links (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  source_request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  target_request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  link_type VARCHAR(20) NOT NULL CHECK (link_type IN ('duplicates', 'blocks', 'relates_to', 'parent_of')),
+  created_by_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (source_request_id <> target_request_id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_request_links_unique
+ON app.request_links (source_request_id, target_request_id, link_type);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_request_links_relates_to_pair_unique
+ON app.request_links (
+  LEAST(source_request_id, target_request_id),
+  GREATEST(source_request_id, target_request_id)
+)
+WHERE link_type = 'relates_to';
+CREATE UNIQUE INDEX IF NOT EXISTS idx_request_links_single_parent
+ON app.request_links (target_request_id)
+WHERE link_type = 'parent_of';
+CREATE INDEX IF NOT EXISTS idx_request_links_target_request_id
+ON app.request_links (target_request_id);
+
+ALTER TABLE app.request_audit_logs
+DROP CONSTRAINT IF EXISTS request_audit_logs_action_check;
+ALTER TABLE app.request_audit_logs
+ADD CONSTRAINT request_audit_logs_action_check
+CHECK (action IN ('created', 'updated', 'deleted', 'status_changed', 'linked', 'unlinked'));
@@ -0,0 +1,45 @@
+-- Checklist items on requests. `checklist_required` on a request blocks
+-- resolving it while items remain open. Templates carry the item titles a
+-- new request starts with. Users assigned a checklist item become request
+-- participants with source 'checklist' so they can see the request.
+
+CREATE TABLE IF NOT EXISTS app.request_checklist_items (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  title VARCHAR(255) NOT NULL CHECK (char_length(btrim(title)) > 0),
+  position INTEGER NOT NULL,
+  done BOOLEAN NOT NULL DEFAULT FALSE,
+  done_at TIMESTAMPTZ,
+  done_by_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  assignee_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  due_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (done = (done_at IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_request_checklist_items_request_position
+ON app.request_checklist_items (request_id, position);
+CREATE INDEX IF NOT EXISTS idx_request_checklist_items_assignee
+ON app.request_checklist_items (assignee_user_id)
+WHERE assignee_user_id IS NOT NULL;
+
+DROP TRIGGER IF EXISTS request_checklist_items_set_updated_at ON app.request_checklist_items;
+CREATE TRIGGER request_checklist_items_set_updated_at
+BEFORE UPDATE ON app.request_checklist_items
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+ALTER TABLE app.requests
+ADD COLUMN IF NOT EXISTS checklist_required BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE app.request_templates
+ADD COLUMN IF NOT EXISTS checklist JSONB NOT NULL DEFAULT '[]'::jsonb
+  CHECK (jsonb_typeof(checklist) = 'array'),
+ADD COLUMN IF NOT EXISTS checklist_required BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE app.request_participants
+DROP CONSTRAINT IF EXISTS request_participants_source_check;
+ALTER TABLE app.request_participants
+ADD CONSTRAINT request_participants_source_check
+CHECK (source IN ('owner', 'assignee', 'actor', 'checklist'));
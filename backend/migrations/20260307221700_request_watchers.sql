@@ -0,0 +1,9 @@
+ALTER TABLE app.request_participants
+DROP CONSTRAINT IF EXISTS request_participants_source_check;
+ALTER TABLE app.request_participants
+ADD CONSTRAINT request_participants_source_check
+CHECK (source IN ('owner', 'assignee', 'actor', 'checklist', 'watcher'));
+
+CREATE INDEX IF NOT EXISTS idx_request_participants_watchers
+ON app.request_participants (request_id, first_seen_at)
+WHERE source = 'watcher';
@@ -0,0 +1,41 @@
+-- Comments on requests, and @mentions of users in descriptions and comments.
+
+CREATE TABLE IF NOT EXISTS app.request_comments (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  author_user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  body TEXT NOT NULL CHECK (char_length(btrim(body)) BETWEEN 1 AND 5000),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_request_comments_request_created
+ON app.request_comments (request_id, created_at);
+
+CREATE TABLE IF NOT EXISTS app.request_mentions (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  comment_id UUID REFERENCES app.request_comments(id) ON DELETE CASCADE,
+  author_user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  mentioned_user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (author_user_id <> mentioned_user_id)
+);
+
+-- A user is mentioned once per description and once per comment, so editing
+-- a description does not notify people who were already mentioned in it.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_request_mentions_description_unique
+ON app.request_mentions (request_id, mentioned_user_id)
+WHERE comment_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_request_mentions_comment_unique
+ON app.request_mentions (comment_id, mentioned_user_id)
+WHERE comment_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_request_mentions_mentioned_user
+ON app.request_mentions (mentioned_user_id, created_at DESC);
+
+ALTER TABLE app.request_participants
+DROP CONSTRAINT IF EXISTS request_participants_source_check;
+ALTER TABLE app.request_participants
+ADD CONSTRAINT request_participants_source_check
+CHECK (
+  source IN ('owner', 'assignee', 'actor', 'checklist', 'watcher', 'mention')
+);
@@ -0,0 +1,19 @@
+-- Persistent in-app notifications. Rows are written for every recipient;
+-- `browser_alerts` only decides whether `notification.created` is pushed.
+
+CREATE TABLE IF NOT EXISTS app.notifications (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  kind VARCHAR(20) NOT NULL CHECK (kind IN ('assigned', 'status_changed', 'comment', 'mention')),
+  request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  actor_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  data JSONB NOT NULL DEFAULT '{}'::jsonb CHECK (jsonb_typeof(data) = 'object'),
+  read_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created
+ON app.notifications (user_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_user_unread
+ON app.notifications (user_id)
+WHERE read_at IS NULL;
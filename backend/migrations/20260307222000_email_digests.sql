@@ -0,0 +1,14 @@
+-- Email digests. Each row records one digest period of one user, whether a
+-- mail was sent (`sent_at`) or skipped for lack of activity. The primary key
+-- is what stops two digest workers from mailing the same period twice.
+
+CREATE TABLE IF NOT EXISTS app.email_digest_deliveries (
+  user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  period_end TIMESTAMPTZ NOT NULL,
+  period_start TIMESTAMPTZ NOT NULL,
+  item_count INTEGER NOT NULL DEFAULT 0 CHECK (item_count >= 0),
+  sent_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (user_id, period_end),
+  CHECK (period_start < period_end)
+);
@@ -0,0 +1,50 @@
+-- Outbound webhooks. A subscription receives the request events its owner
+-- would receive over the websocket, filtered by event type. Each event is
+-- queued as one row in `webhook_deliveries`, which doubles as the delivery
+-- log; workers lease due rows by pushing `next_attempt_at` forward, so a
+-- delivery is attempted by one replica at a time.
+
+CREATE TABLE IF NOT EXISTS app.webhook_subscriptions (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  owner_user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  url VARCHAR(2000) NOT NULL CHECK (url ~ '^https?://'),
+  secret VARCHAR(200) NOT NULL CHECK (char_length(secret) >= 16),
+  events TEXT[] NOT NULL CHECK (cardinality(events) > 0),
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_subscriptions_owner_user_id
+ON app.webhook_subscriptions (owner_user_id)
+WHERE active;
+
+DROP TRIGGER IF EXISTS webhook_subscriptions_set_updated_at ON app.webhook_subscriptions;
+CREATE TRIGGER webhook_subscriptions_set_updated_at
+BEFORE UPDATE ON app.webhook_subscriptions
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+-- `request_id` has no foreign key: `request.deleted` deliveries outlive
+-- their request.
+CREATE TABLE IF NOT EXISTS app.webhook_deliveries (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  subscription_id UUID NOT NULL REFERENCES app.webhook_subscriptions(id) ON DELETE CASCADE,
+  event_type VARCHAR(50) NOT NULL,
+  request_id UUID,
+  body JSONB NOT NULL CHECK (jsonb_typeof(body) = 'object'),
+  status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'succeeded', 'failed')),
+  attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+  next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  last_attempt_at TIMESTAMPTZ,
+  response_status INTEGER,
+  last_error TEXT,
+  redelivery_of UUID REFERENCES app.webhook_deliveries(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_subscription_created
+ON app.webhook_deliveries (subscription_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due
+ON app.webhook_deliveries (next_attempt_at)
+WHERE status = 'pending';
@@ -0,0 +1,32 @@
+-- Inbound email gateway. Attachments are stored in the database alongside
+-- their request, and optionally the comment they arrived with.
+-- `inbound_emails` records every processed message: its unique
+-- `message_id` makes redelivery by the mail server a no-op, and replies are
+-- threaded by looking up the messages they reference.
+
+CREATE TABLE IF NOT EXISTS app.request_attachments (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  comment_id UUID REFERENCES app.request_comments(id) ON DELETE SET NULL,
+  uploaded_by_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  filename VARCHAR(255) NOT NULL CHECK (char_length(btrim(filename)) > 0),
+  content_type VARCHAR(255) NOT NULL,
+  size_bytes INTEGER NOT NULL CHECK (size_bytes >= 0),
+  content BYTEA NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_request_attachments_request_id
+ON app.request_attachments (request_id, created_at);
+
+CREATE TABLE IF NOT EXISTS app.inbound_emails (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  message_id VARCHAR(998) UNIQUE,
+  sender_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  request_id UUID REFERENCES app.requests(id) ON DELETE SET NULL,
+  comment_id UUID REFERENCES app.request_comments(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_inbound_emails_request_id
+ON app.inbound_emails (request_id);
@@ -0,0 +1,89 @@
+-- SLA policies. A workspace (email domain, as for shared views) sets a
+-- first-response and a resolution target per category and priority,
+-- counted in the business hours of an optional calendar (around the clock
+-- without one). `request_slas` holds the deadlines computed for each
+-- request of a workspace with a matching policy; the `*_sent_at` columns
+-- record which warning and breach events were published so every replica
+-- sends each one once.
+
+CREATE TABLE IF NOT EXISTS app.business_calendars (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  workspace_domain TEXT NOT NULL CHECK (
+    workspace_domain = lower(btrim(workspace_domain))
+  ),
+  name VARCHAR(80) NOT NULL CHECK (char_length(btrim(name)) > 0),
+  timezone VARCHAR(64) NOT NULL,
+  -- ISO weekdays, 1 = Monday through 7 = Sunday.
+  work_days SMALLINT[] NOT NULL CHECK (
+    cardinality(work_days) > 0 AND work_days <@ ARRAY[1, 2, 3, 4, 5, 6, 7]::SMALLINT[]
+  ),
+  day_start TIME NOT NULL,
+  day_end TIME NOT NULL,
+  holidays DATE[] NOT NULL DEFAULT '{}',
+  created_by_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (day_start < day_end)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_business_calendars_workspace_name_unique
+ON app.business_calendars (workspace_domain, lower(name));
+
+DROP TRIGGER IF EXISTS business_calendars_set_updated_at ON app.business_calendars;
+CREATE TRIGGER business_calendars_set_updated_at
+BEFORE UPDATE ON app.business_calendars
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+CREATE TABLE IF NOT EXISTS app.sla_policies (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  workspace_domain TEXT NOT NULL CHECK (
+    workspace_domain = lower(btrim(workspace_domain))
+  ),
+  category VARCHAR(20) NOT NULL CHECK (category IN ('IT', 'Ops', 'Admin', 'HR')),
+  priority VARCHAR(20) NOT NULL CHECK (priority IN ('low', 'medium', 'high')),
+  first_response_minutes INTEGER NOT NULL CHECK (first_response_minutes > 0),
+  resolution_minutes INTEGER NOT NULL CHECK (resolution_minutes > 0),
+  warning_percent SMALLINT NOT NULL DEFAULT 80 CHECK (
+    warning_percent BETWEEN 1 AND 99
+  ),
+  calendar_id UUID REFERENCES app.business_calendars(id) ON DELETE RESTRICT,
+  created_by_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE (workspace_domain, category, priority)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sla_policies_calendar_id
+ON app.sla_policies (calendar_id)
+WHERE calendar_id IS NOT NULL;
+
+DROP TRIGGER IF EXISTS sla_policies_set_updated_at ON app.sla_policies;
+CREATE TRIGGER sla_policies_set_updated_at
+BEFORE UPDATE ON app.sla_policies
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+CREATE TABLE IF NOT EXISTS app.request_slas (
+  request_id UUID PRIMARY KEY REFERENCES app.requests(id) ON DELETE CASCADE,
+  policy_id UUID NOT NULL REFERENCES app.sla_policies(id) ON DELETE CASCADE,
+  first_response_due_at TIMESTAMPTZ NOT NULL,
+  first_response_warn_at TIMESTAMPTZ NOT NULL,
+  first_responded_at TIMESTAMPTZ,
+  resolution_due_at TIMESTAMPTZ NOT NULL,
+  resolution_warn_at TIMESTAMPTZ NOT NULL,
+  first_response_warning_sent_at TIMESTAMPTZ,
+  first_response_breach_sent_at TIMESTAMPTZ,
+  resolution_warning_sent_at TIMESTAMPTZ,
+  resolution_breach_sent_at TIMESTAMPTZ,
+  computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_request_slas_policy_id
+ON app.request_slas (policy_id);
+CREATE INDEX IF NOT EXISTS idx_request_slas_first_response_pending
+ON app.request_slas (first_response_warn_at)
+WHERE first_responded_at IS NULL AND first_response_breach_sent_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_request_slas_resolution_pending
+ON app.request_slas (resolution_warn_at)
+WHERE resolution_breach_sent_at IS NULL;
@@ -0,0 +1,109 @@
+-- Escalation rules. A workspace (email domain, as for SLA policies) matches
+-- its unresolved requests on priority, category, status, time since the
+-- last update and SLA state, and a worker applies the rule's action to each
+-- match. Actions are audited as `escalated` by the system user.
+-- `escalation_firings` records the request's `updated_at` after a rule
+-- fired, so a rule fires once until the request changes again.
+
+-- Actor of automated changes. Inactive and without credentials, so it can
+-- never sign in, and never recorded as a request participant.
+CREATE OR REPLACE FUNCTION app.system_user_id()
+RETURNS UUID AS $$
+  SELECT '00000000-0000-0000-0000-000000000001'::UUID;
+$$ LANGUAGE sql IMMUTABLE;
+
+INSERT INTO app.app_users (id, email, display_name, is_active)
+VALUES (app.system_user_id(), 'system@reqstly.invalid', 'Reqstly', FALSE)
+ON CONFLICT (id) DO NOTHING;
+
+CREATE OR REPLACE FUNCTION app.upsert_request_participant(
+  participant_request_id UUID,
+  participant_user_id UUID,
+  participant_source TEXT,
+  participant_seen_at TIMESTAMPTZ
+)
+RETURNS VOID AS $$
+BEGIN
+  IF participant_request_id IS NULL
+    OR participant_user_id IS NULL
+    OR participant_user_id = app.system_user_id() THEN
+    RETURN;
+  END IF;
+
+  INSERT INTO app.request_participants (
+    request_id,
+    user_id,
+    source,
+    first_seen_at,
+    last_seen_at
+  )
+  VALUES (
+    participant_request_id,
+    participant_user_id,
+    participant_source,
+    participant_seen_at,
+    participant_seen_at
+  )
+  ON CONFLICT (request_id, user_id)
+  DO UPDATE SET
+    last_seen_at = GREATEST(
+      app.request_participants.last_seen_at,
+      EXCLUDED.last_seen_at
+    );
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE app.request_audit_logs
+DROP CONSTRAINT IF EXISTS request_audit_logs_action_check;
+ALTER TABLE app.request_audit_logs
+ADD CONSTRAINT request_audit_logs_action_check
+CHECK (action IN ('created', 'updated', 'deleted', 'status_changed', 'linked', 'unlinked', 'escalated'));
+
+ALTER TABLE app.notifications
+DROP CONSTRAINT IF EXISTS notifications_kind_check;
+ALTER TABLE app.notifications
+ADD CONSTRAINT notifications_kind_check
+CHECK (kind IN ('assigned', 'status_changed', 'comment', 'mention', 'escalation'));
+
+CREATE TABLE IF NOT EXISTS app.escalation_rules (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  workspace_domain TEXT NOT NULL CHECK (
+    workspace_domain = lower(btrim(workspace_domain))
+  ),
+  name VARCHAR(120) NOT NULL CHECK (char_length(btrim(name)) > 0),
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  priority VARCHAR(20) CHECK (priority IN ('low', 'medium', 'high')),
+  category VARCHAR(20) CHECK (category IN ('IT', 'Ops', 'Admin', 'HR')),
+  status VARCHAR(20) CHECK (status IN ('open', 'in_progress')),
+  idle_minutes INTEGER CHECK (idle_minutes > 0),
+  sla_state VARCHAR(20) CHECK (sla_state IN ('breached', 'at_risk', 'on_track')),
+  action VARCHAR(20) NOT NULL CHECK (
+    action IN ('reassign', 'raise_priority', 'notify', 'add_watcher')
+  ),
+  target_user_id UUID REFERENCES app.app_users(id) ON DELETE CASCADE,
+  created_by_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (idle_minutes IS NOT NULL OR sla_state IS NOT NULL),
+  CHECK ((action = 'raise_priority') = (target_user_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_escalation_rules_workspace_created
+ON app.escalation_rules (workspace_domain, created_at);
+
+DROP TRIGGER IF EXISTS escalation_rules_set_updated_at ON app.escalation_rules;
+CREATE TRIGGER escalation_rules_set_updated_at
+BEFORE UPDATE ON app.escalation_rules
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+CREATE TABLE IF NOT EXISTS app.escalation_firings (
+  rule_id UUID NOT NULL REFERENCES app.escalation_rules(id) ON DELETE CASCADE,
+  request_id UUID NOT NULL REFERENCES app.requests(id) ON DELETE CASCADE,
+  request_updated_at TIMESTAMPTZ NOT NULL,
+  fired_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (rule_id, request_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_escalation_firings_request_id
+ON app.escalation_firings (request_id);
@@ -0,0 +1,47 @@
+-- Auto-assignment routing. A workspace (email domain, as for SLA policies)
+-- routes new requests without an assignee to one of a rule's members. A
+-- rule applies to one category, or to every category without a rule of its
+-- own when `category` is NULL. Members who are unavailable or out of
+-- office are skipped; the creator keeps the request when nobody is left.
+
+ALTER TABLE app.app_users
+ADD COLUMN IF NOT EXISTS available BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE app.app_users
+ADD COLUMN IF NOT EXISTS out_of_office_until TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS app.routing_rules (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  workspace_domain TEXT NOT NULL CHECK (
+    workspace_domain = lower(btrim(workspace_domain))
+  ),
+  category VARCHAR(20) CHECK (category IN ('IT', 'Ops', 'Admin', 'HR')),
+  strategy VARCHAR(20) NOT NULL CHECK (
+    strategy IN ('round_robin', 'least_loaded', 'fixed')
+  ),
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  created_by_user_id UUID REFERENCES app.app_users(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_routing_rules_workspace_category_unique
+ON app.routing_rules (workspace_domain, COALESCE(category, ''));
+
+DROP TRIGGER IF EXISTS routing_rules_set_updated_at ON app.routing_rules;
+CREATE TRIGGER routing_rules_set_updated_at
+BEFORE UPDATE ON app.routing_rules
+FOR EACH ROW
+EXECUTE FUNCTION app.set_updated_at();
+
+CREATE TABLE IF NOT EXISTS app.routing_rule_members (
+  rule_id UUID NOT NULL REFERENCES app.routing_rules(id) ON DELETE CASCADE,
+  user_id UUID NOT NULL REFERENCES app.app_users(id) ON DELETE CASCADE,
+  position INTEGER NOT NULL CHECK (position >= 0),
+  -- When the member was last routed a request; round-robin picks the
+  -- available member who has waited longest.
+  last_routed_at TIMESTAMPTZ,
+  PRIMARY KEY (rule_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_routing_rule_members_user_id
+ON app.routing_rule_members (user_id);
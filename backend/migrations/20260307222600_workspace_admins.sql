@@ -0,0 +1,6 @@
+-- Workspace admins manage the routing and escalation rules of their
+-- workspace (email domain). Everyone else in the workspace can read them.
+-- Admins are granted with `reqstly_backend workspace-admin`.
+
+ALTER TABLE app.app_users
+ADD COLUMN IF NOT EXISTS workspace_admin BOOLEAN NOT NULL DEFAULT FALSE;
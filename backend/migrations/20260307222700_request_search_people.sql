@@ -0,0 +1,30 @@
+-- Full-text search also matches status, priority and the assignee's email
+-- and display name, all weighted below category. Assignee terms live on
+-- app_users and are joined in at query time, so renaming a user does not
+-- rewrite (or touch updated_at on) every request assigned to them.
+
+DROP INDEX IF EXISTS app.idx_requests_search_vector;
+
+ALTER TABLE app.requests
+DROP COLUMN IF EXISTS search_vector;
+
+ALTER TABLE app.requests
+ADD COLUMN search_vector TSVECTOR
+GENERATED ALWAYS AS (
+  setweight(to_tsvector('english', COALESCE(title, '')), 'A')
+  || setweight(to_tsvector('english', COALESCE(description, '')), 'B')
+  || setweight(to_tsvector('simple', COALESCE(category, '')), 'C')
+  || setweight(to_tsvector('simple', status || ' ' || priority), 'D')
+) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_requests_search_vector
+ON app.requests USING GIN (search_vector);
+
+ALTER TABLE app.app_users
+ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+GENERATED ALWAYS AS (
+  setweight(
+    to_tsvector('simple', COALESCE(email, '') || ' ' || display_name),
+    'D'
+  )
+) STORED;
@@ -0,0 +1,6 @@
+-- Imported requests keep their original (backdated) created_at. SLA clocks
+-- start when the row was imported instead, so a migration does not mark
+-- every open legacy ticket breached and fire its events and escalations.
+
+ALTER TABLE app.requests
+ADD COLUMN IF NOT EXISTS imported_at TIMESTAMPTZ;
@@ -0,0 +1,174 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS},
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgConnection};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::{fetch_visible_request, require_authenticated_user};
+use crate::{AppState, error::AppError, response};
+
+pub(super) const MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub(super) struct AttachmentRow {
+    id: Uuid,
+    request_id: Uuid,
+    comment_id: Option<Uuid>,
+    uploaded_by_user_id: Option<Uuid>,
+    filename: String,
+    content_type: String,
+    size_bytes: i32,
+    created_at: DateTime<Utc>,
+}
+
+pub(super) struct NewAttachment {
+    pub(super) filename: String,
+    pub(super) content_type: String,
+    pub(super) content: Vec<u8>,
+}
+
+const ATTACHMENT_COLUMNS: &str = "id,
+     request_id,
+     comment_id,
+     uploaded_by_user_id,
+     filename,
+     content_type,
+     size_bytes,
+     created_at";
+
+pub(super) async fn list_attachments(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    fetch_visible_request(&state.db, id, user.id).await?;
+
+    let attachments = sqlx::query_as::<_, AttachmentRow>(&format!(
+        "SELECT {ATTACHMENT_COLUMNS}
+         FROM app.request_attachments
+         WHERE request_id = $1
+         ORDER BY created_at ASC, id ASC"
+    ))
+    .bind(id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, attachments))
+}
+
+// Always a download, so uploaded HTML is never rendered in the app's
+// origin.
+pub(super) async fn download_attachment(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path((id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    fetch_visible_request(&state.db, id, user.id).await?;
+
+    let (filename, content_type, content): (String, String, Vec<u8>) =
+        sqlx::query_as(
+            "SELECT filename, content_type, content
+             FROM app.request_attachments
+             WHERE id = $1 AND request_id = $2",
+        )
+        .bind(attachment_id)
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound("attachment not found".to_string())
+        })?;
+
+    let content_type = HeaderValue::from_str(&content_type)
+        .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+    let disposition = HeaderValue::from_str(&format!(
+        "attachment; filename=\"{}\"",
+        ascii_filename(&filename)
+    ))
+    .expect("sanitized filenames are valid header values");
+
+    Ok((
+        [
+            (CONTENT_TYPE, content_type),
+            (CONTENT_DISPOSITION, disposition),
+            (X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+        ],
+        Body::from(content),
+    )
+        .into_response())
+}
+
+pub(super) async fn insert_attachments(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    comment_id: Option<Uuid>,
+    uploaded_by_user_id: Uuid,
+    attachments: Vec<NewAttachment>,
+) -> Result<Vec<AttachmentRow>, AppError> {
+    let mut rows = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        let row = sqlx::query_as::<_, AttachmentRow>(&format!(
+            "INSERT INTO app.request_attachments (
+               request_id,
+               comment_id,
+               uploaded_by_user_id,
+               filename,
+               content_type,
+               size_bytes,
+               content
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING {ATTACHMENT_COLUMNS}"
+        ))
+        .bind(request_id)
+        .bind(comment_id)
+        .bind(uploaded_by_user_id)
+        .bind(&attachment.filename)
+        .bind(&attachment.content_type)
+        .bind(attachment.content.len() as i32)
+        .bind(&attachment.content)
+        .fetch_one(&mut *conn)
+        .await?;
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+fn ascii_filename(filename: &str) -> String {
+    filename
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_graphic() && ch != '"' && ch != '\\' || ch == ' ' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_filename_replaces_unsafe_characters() {
+        assert_eq!(ascii_filename("report 2026.pdf"), "report 2026.pdf");
+        assert_eq!(ascii_filename("a\"b\\c\r\nd.txt"), "a_b_c__d.txt");
+        assert_eq!(ascii_filename("résumé.pdf"), "r_sum_.pdf");
+    }
+}
@@ -0,0 +1,502 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::events::{
+    AuditAppendEventPayload, RequestCreatedEventPayload,
+    RequestDeletedEventPayload, RequestPatchEventPayload, ServerEvent,
+};
+use super::filters::RequestListFilter;
+use super::views::ViewFilters;
+use super::{
+    AuditLogRow, RequestRow, checklists, collect_changed_fields,
+    fetch_editable_request, fetch_owned_request, fetch_request_recipient_ids,
+    fetch_visible_request, insert_audit_log, normalize_assignee_email,
+    notifications, publish_event_batches, request_audit_snapshot,
+    require_authenticated_user, resolve_assignee_user_id, sla,
+    split_recipients_by_visibility, validate_category, validate_priority,
+    validate_status,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const BULK_MAX_ITEMS: usize = 500;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct BulkRequestInput {
+    ids: Option<Vec<Uuid>>,
+    filter: Option<ViewFilters>,
+    operation: BulkOperation,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum BulkOperation {
+    SetStatus { value: String },
+    SetPriority { value: String },
+    SetCategory { value: String },
+    SetAssignee { value: Option<String> },
+    Delete,
+}
+
+impl BulkOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::SetStatus { .. } => "set_status",
+            Self::SetPriority { .. } => "set_priority",
+            Self::SetCategory { .. } => "set_category",
+            Self::SetAssignee { .. } => "set_assignee",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+enum ResolvedOperation {
+    SetStatus(String),
+    SetPriority(String),
+    SetCategory(String),
+    SetAssignee(Option<Uuid>),
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BulkItemOutcome {
+    Updated,
+    Unchanged,
+    Deleted,
+    NotFound,
+    Blocked,
+}
+
+#[derive(Debug, Serialize)]
+struct BulkItemResult {
+    id: Uuid,
+    outcome: BulkItemOutcome,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct BulkCounts {
+    updated: usize,
+    unchanged: usize,
+    deleted: usize,
+    not_found: usize,
+    blocked: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct BulkResponse {
+    operation: &'static str,
+    counts: BulkCounts,
+    results: Vec<BulkItemResult>,
+}
+
+enum PendingEvents {
+    Updated {
+        existing: RequestRow,
+        updated: Box<RequestRow>,
+        audit: AuditLogRow,
+        recipients_before: Vec<Uuid>,
+        recipients_after: Vec<Uuid>,
+    },
+    Deleted {
+        existing: RequestRow,
+        audit: AuditLogRow,
+        recipients: Vec<Uuid>,
+    },
+}
+
+pub(super) async fn bulk_requests(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<BulkRequestInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let operation_name = input.operation.name();
+    let operation = resolve_operation(&state, input.operation).await?;
+    let ids = match (input.ids, input.filter) {
+        (Some(ids), None) => dedupe_ids(ids)?,
+        (None, Some(filter)) => {
+            matching_request_ids(&state, user.id, &filter).await?
+        }
+        _ => {
+            return Err(AppError::Validation(vec![ErrorDetail {
+                field: "ids".to_string(),
+                message: "provide exactly one of ids or filter".to_string(),
+            }]));
+        }
+    };
+
+    let mut counts = BulkCounts::default();
+    let mut results = Vec::with_capacity(ids.len());
+    let mut pending = Vec::new();
+
+    let mut tx = state.db.begin().await?;
+    for id in ids {
+        let outcome =
+            apply_to_request(&mut tx, user.id, id, &operation, &mut pending)
+                .await?;
+        match outcome {
+            BulkItemOutcome::Updated => counts.updated += 1,
+            BulkItemOutcome::Unchanged => counts.unchanged += 1,
+            BulkItemOutcome::Deleted => counts.deleted += 1,
+            BulkItemOutcome::NotFound => counts.not_found += 1,
+            BulkItemOutcome::Blocked => counts.blocked += 1,
+        }
+        results.push(BulkItemResult { id, outcome });
+    }
+    tx.commit().await?;
+
+    let changes: Vec<_> = pending
+        .iter()
+        .filter_map(|item| match item {
+            PendingEvents::Updated {
+                existing,
+                updated,
+                recipients_after,
+                ..
+            } => Some((
+                existing.clone(),
+                updated.clone(),
+                recipients_after.clone(),
+            )),
+            PendingEvents::Deleted { .. } => None,
+        })
+        .collect();
+    publish_batches(&state, pending).await;
+    for (existing, updated, recipients) in changes {
+        notifications::notify_request_change(
+            &state,
+            user.id,
+            Some(&existing),
+            &updated,
+            &recipients,
+        )
+        .await?;
+    }
+
+    Ok(response::ok(
+        StatusCode::OK,
+        BulkResponse {
+            operation: operation_name,
+            counts,
+            results,
+        },
+    ))
+}
+
+async fn resolve_operation(
+    state: &AppState,
+    operation: BulkOperation,
+) -> Result<ResolvedOperation, AppError> {
+    Ok(match operation {
+        BulkOperation::SetStatus { value } => {
+            validate_status(&value)?;
+            ResolvedOperation::SetStatus(value)
+        }
+        BulkOperation::SetPriority { value } => {
+            validate_priority(&value)?;
+            ResolvedOperation::SetPriority(value)
+        }
+        BulkOperation::SetCategory { value } => {
+            validate_category(&value)?;
+            ResolvedOperation::SetCategory(value)
+        }
+        BulkOperation::SetAssignee { value } => {
+            let normalized = normalize_assignee_email(value.as_deref())?;
+            ResolvedOperation::SetAssignee(
+                resolve_assignee_user_id(&state.db, normalized.as_deref())
+                    .await?,
+            )
+        }
+        BulkOperation::Delete => ResolvedOperation::Delete,
+    })
+}
+
+fn dedupe_ids(ids: Vec<Uuid>) -> Result<Vec<Uuid>, AppError> {
+    let mut seen = HashSet::with_capacity(ids.len());
+    let ids: Vec<Uuid> =
+        ids.into_iter().filter(|id| seen.insert(*id)).collect();
+
+    if ids.is_empty() {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "ids".to_string(),
+            message: "ids must not be empty".to_string(),
+        }]));
+    }
+    if ids.len() > BULK_MAX_ITEMS {
+        return Err(too_many_items("ids"));
+    }
+
+    Ok(ids)
+}
+
+async fn matching_request_ids(
+    state: &AppState,
+    user_id: Uuid,
+    filter: &ViewFilters,
+) -> Result<Vec<Uuid>, AppError> {
+    let filter = RequestListFilter::from_query(&filter.to_list_query())?;
+
+    let mut query = QueryBuilder::<Postgres>::new("SELECT req.id");
+    filter.push_from_where(&mut query, user_id);
+    query
+        .push(" ORDER BY req.created_at DESC, req.id DESC LIMIT ")
+        .push_bind(BULK_MAX_ITEMS as i64 + 1);
+
+    let ids: Vec<Uuid> =
+        query.build_query_scalar().fetch_all(&state.db).await?;
+    if ids.len() > BULK_MAX_ITEMS {
+        return Err(too_many_items("filter"));
+    }
+
+    Ok(ids)
+}
+
+async fn apply_to_request(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    user_id: Uuid,
+    id: Uuid,
+    operation: &ResolvedOperation,
+    pending: &mut Vec<PendingEvents>,
+) -> Result<BulkItemOutcome, AppError> {
+    // Deleting needs ownership; every other operation accepts the assignee
+    // too, exactly like DELETE and PATCH /requests/:id.
+    let existing = match operation {
+        ResolvedOperation::Delete => {
+            fetch_owned_request(&mut **tx, id, user_id).await
+        }
+        _ => fetch_editable_request(&mut **tx, id, user_id).await,
+    };
+    let existing = match existing {
+        Ok(existing) => existing,
+        Err(AppError::NotFound(_)) => return Ok(BulkItemOutcome::NotFound),
+        Err(err) => return Err(err),
+    };
+    let recipients_before = fetch_request_recipient_ids(&mut **tx, id).await?;
+
+    if let ResolvedOperation::Delete = operation {
+        let audit = insert_audit_log(
+            &mut **tx,
+            existing.id,
+            user_id,
+            "deleted",
+            request_audit_snapshot(&existing),
+            serde_json::json!({}),
+        )
+        .await?;
+        sqlx::query("DELETE FROM app.requests WHERE id = $1")
+            .bind(existing.id)
+            .execute(&mut **tx)
+            .await?;
+
+        pending.push(PendingEvents::Deleted {
+            existing,
+            audit,
+            recipients: recipients_before,
+        });
+        return Ok(BulkItemOutcome::Deleted);
+    }
+
+    if let ResolvedOperation::SetStatus(value) = operation
+        && checklists::ensure_resolvable(
+            &existing,
+            value,
+            existing.checklist_required,
+        )
+        .is_err()
+    {
+        return Ok(BulkItemOutcome::Blocked);
+    }
+
+    let mut status = existing.status.clone();
+    let mut priority = existing.priority.clone();
+    let mut category = existing.category.clone();
+    let mut assignee_user_id = existing.assignee_user_id;
+    match operation {
+        ResolvedOperation::SetStatus(value) => status = value.clone(),
+        ResolvedOperation::SetPriority(value) => priority = value.clone(),
+        ResolvedOperation::SetCategory(value) => category = value.clone(),
+        ResolvedOperation::SetAssignee(value) => assignee_user_id = *value,
+        ResolvedOperation::Delete => unreachable!("handled above"),
+    }
+
+    if status == existing.status
+        && priority == existing.priority
+        && category == existing.category
+        && assignee_user_id == existing.assignee_user_id
+    {
+        return Ok(BulkItemOutcome::Unchanged);
+    }
+
+    sqlx::query(
+        "UPDATE app.requests
+         SET status = $2,
+             priority = $3,
+             category = $4,
+             assignee_user_id = $5,
+             updated_at = NOW()
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(&status)
+    .bind(&priority)
+    .bind(&category)
+    .bind(assignee_user_id)
+    .execute(&mut **tx)
+    .await?;
+    sla::refresh_request_sla(tx, id).await?;
+    let updated = fetch_visible_request(&mut **tx, id, user_id).await?;
+
+    let audit = insert_audit_log(
+        &mut **tx,
+        updated.id,
+        user_id,
+        "updated",
+        request_audit_snapshot(&existing),
+        request_audit_snapshot(&updated),
+    )
+    .await?;
+    let recipients_after = fetch_request_recipient_ids(&mut **tx, id).await?;
+
+    pending.push(PendingEvents::Updated {
+        existing,
+        updated: Box::new(updated),
+        audit,
+        recipients_before,
+        recipients_after,
+    });
+    Ok(BulkItemOutcome::Updated)
+}
+
+async fn publish_batches(state: &AppState, pending: Vec<PendingEvents>) {
+    let mut by_user: HashMap<Uuid, Vec<ServerEvent>> = HashMap::new();
+
+    for item in pending {
+        match item {
+            PendingEvents::Updated {
+                existing,
+                updated,
+                audit,
+                recipients_before,
+                recipients_after,
+            } => {
+                let updated = *updated;
+                let changed_fields =
+                    collect_changed_fields(&existing, &updated);
+                let (existing_recipients, newly_visible_recipients) =
+                    split_recipients_by_visibility(
+                        &recipients_before,
+                        &recipients_after,
+                    );
+
+                for user_id in existing_recipients {
+                    by_user.entry(user_id).or_default().push(
+                        ServerEvent::RequestPatch(RequestPatchEventPayload {
+                            request: updated.clone(),
+                            changed_fields: changed_fields.clone(),
+                            previous_status: existing.status.clone(),
+                        }),
+                    );
+                }
+                for user_id in newly_visible_recipients {
+                    by_user.entry(user_id).or_default().push(
+                        ServerEvent::RequestCreated(
+                            RequestCreatedEventPayload {
+                                request: updated.clone(),
+                            },
+                        ),
+                    );
+                }
+                for user_id in recipients_after {
+                    by_user.entry(user_id).or_default().push(
+                        ServerEvent::AuditAppend(AuditAppendEventPayload {
+                            audit: audit.clone(),
+                        }),
+                    );
+                }
+            }
+            PendingEvents::Deleted {
+                existing,
+                audit,
+                recipients,
+            } => {
+                for user_id in recipients {
+                    let events = by_user.entry(user_id).or_default();
+                    events.push(ServerEvent::AuditAppend(
+                        AuditAppendEventPayload {
+                            audit: audit.clone(),
+                        },
+                    ));
+                    events.push(ServerEvent::RequestDeleted(
+                        RequestDeletedEventPayload {
+                            id: existing.id,
+                            status: existing.status.clone(),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    publish_event_batches(state, by_user).await;
+}
+
+fn too_many_items(field: &str) -> AppError {
+    AppError::Validation(vec![ErrorDetail {
+        field: field.to_string(),
+        message: format!(
+            "bulk operations are limited to {BULK_MAX_ITEMS} requests"
+        ),
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedupe_ids_keeps_order_and_enforces_bounds() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        assert_eq!(
+            dedupe_ids(vec![a, b, a]).expect("ids should dedupe"),
+            vec![a, b]
+        );
+
+        assert!(dedupe_ids(Vec::new()).is_err());
+        let too_many = (0..=BULK_MAX_ITEMS).map(|_| Uuid::new_v4()).collect();
+        assert!(dedupe_ids(too_many).is_err());
+    }
+
+    #[test]
+    fn bulk_operation_parses_tagged_shape() {
+        let operation: BulkOperation = serde_json::from_value(
+            serde_json::json!({ "type": "set_status", "value": "resolved" }),
+        )
+        .expect("operation should parse");
+        assert_eq!(operation.name(), "set_status");
+
+        let operation: BulkOperation =
+            serde_json::from_value(serde_json::json!({ "type": "delete" }))
+                .expect("delete should parse");
+        assert_eq!(operation.name(), "delete");
+    }
+}
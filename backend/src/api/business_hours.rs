@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+
+use chrono::{
+    DateTime, Datelike, LocalResult, NaiveDate, NaiveTime, TimeDelta, TimeZone,
+    Utc,
+};
+use chrono_tz::Tz;
+
+// Holidays can never cover this many days, so a deadline is always found
+// well before.
+const MAX_SEARCH_DAYS: usize = 3660;
+
+#[derive(Debug, Clone)]
+pub(super) struct BusinessCalendar {
+    timezone: Tz,
+    work_days: [bool; 7],
+    day_start: NaiveTime,
+    day_end: NaiveTime,
+    holidays: HashSet<NaiveDate>,
+}
+
+impl BusinessCalendar {
+    pub(super) fn new(
+        timezone: Tz,
+        work_days: &[i16],
+        day_start: NaiveTime,
+        day_end: NaiveTime,
+        holidays: &[NaiveDate],
+    ) -> Self {
+        let mut days = [false; 7];
+        for day in work_days {
+            if (1..=7).contains(day) {
+                days[*day as usize - 1] = true;
+            }
+        }
+
+        Self {
+            timezone,
+            work_days: days,
+            day_start,
+            day_end,
+            holidays: holidays.iter().copied().collect(),
+        }
+    }
+
+    fn window(
+        &self,
+        date: NaiveDate,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let weekday = date.weekday().num_days_from_monday() as usize;
+        if !self.work_days[weekday] || self.holidays.contains(&date) {
+            return None;
+        }
+
+        Some((self.at(date, self.day_start), self.at(date, self.day_end)))
+    }
+
+    // Ambiguous times resolve to the earlier instant; times skipped by a DST
+    // change are read with the offset from before the change.
+    fn at(&self, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+        let local = date.and_time(time);
+        let resolved = match self.timezone.from_local_datetime(&local) {
+            LocalResult::Single(instant) => Some(instant),
+            LocalResult::Ambiguous(earliest, _) => Some(earliest),
+            LocalResult::None => self
+                .timezone
+                .from_local_datetime(&(local + TimeDelta::hours(1)))
+                .earliest(),
+        };
+
+        resolved.map_or_else(|| local.and_utc(), |instant| instant.to_utc())
+    }
+
+    fn date_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        instant.with_timezone(&self.timezone).date_naive()
+    }
+}
+
+pub(super) fn add_working_time(
+    calendar: Option<&BusinessCalendar>,
+    start: DateTime<Utc>,
+    duration: TimeDelta,
+) -> DateTime<Utc> {
+    let Some(calendar) = calendar else {
+        return start + duration;
+    };
+
+    let mut remaining = duration;
+    let mut date = calendar.date_of(start);
+    for _ in 0..MAX_SEARCH_DAYS {
+        if let Some((open, close)) = calendar.window(date) {
+            let from = open.max(start);
+            if from < close {
+                let available = close - from;
+                if remaining <= available {
+                    return from + remaining;
+                }
+                remaining -= available;
+            }
+        }
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+
+    start + duration
+}
+
+pub(super) fn working_time_between(
+    calendar: Option<&BusinessCalendar>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> TimeDelta {
+    if to <= from {
+        return TimeDelta::zero();
+    }
+    let Some(calendar) = calendar else {
+        return to - from;
+    };
+
+    let mut total = TimeDelta::zero();
+    let mut date = calendar.date_of(from);
+    let last = calendar.date_of(to);
+    while date <= last {
+        if let Some((open, close)) = calendar.window(date) {
+            let start = open.max(from);
+            let end = close.min(to);
+            if start < end {
+                total += end - start;
+            }
+        }
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("timestamp should parse")
+            .to_utc()
+    }
+
+    fn office(holidays: &[NaiveDate]) -> BusinessCalendar {
+        BusinessCalendar::new(
+            chrono_tz::Europe::Berlin,
+            &[1, 2, 3, 4, 5],
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            holidays,
+        )
+    }
+
+    #[test]
+    fn without_calendar_every_moment_counts() {
+        let start = utc("2026-03-06T16:00:00Z");
+
+        assert_eq!(
+            add_working_time(None, start, TimeDelta::hours(4)),
+            utc("2026-03-06T20:00:00Z")
+        );
+        assert_eq!(
+            working_time_between(None, start, utc("2026-03-07T16:00:00Z")),
+            TimeDelta::hours(24)
+        );
+    }
+
+    #[test]
+    fn working_time_skips_nights_weekends_and_holidays() {
+        let calendar = office(&[NaiveDate::from_ymd_opt(2026, 3, 9).unwrap()]);
+
+        // Friday 16:00 in Berlin (UTC+1) plus 4 working hours: one hour on
+        // Friday, Monday is a holiday, three hours on Tuesday.
+        let due = add_working_time(
+            Some(&calendar),
+            utc("2026-03-06T15:00:00Z"),
+            TimeDelta::hours(4),
+        );
+        assert_eq!(due, utc("2026-03-10T11:00:00Z"));
+        assert_eq!(
+            working_time_between(
+                Some(&calendar),
+                utc("2026-03-06T15:00:00Z"),
+                due
+            ),
+            TimeDelta::hours(4)
+        );
+
+        // Starting on a Saturday waits for Monday's opening.
+        assert_eq!(
+            add_working_time(
+                Some(&office(&[])),
+                utc("2026-03-07T12:00:00Z"),
+                TimeDelta::minutes(30)
+            ),
+            utc("2026-03-09T08:30:00Z")
+        );
+    }
+
+    #[test]
+    fn working_windows_follow_daylight_saving_time() {
+        // Berlin switches to UTC+2 on 2026-03-29, so opening moves to 07:00Z.
+        assert_eq!(
+            add_working_time(
+                Some(&office(&[])),
+                utc("2026-03-27T16:00:00Z"),
+                TimeDelta::hours(1)
+            ),
+            utc("2026-03-30T08:00:00Z")
+        );
+    }
+}
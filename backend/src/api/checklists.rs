@@ -0,0 +1,528 @@
+use std::collections::HashSet;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgConnection, PgExecutor};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::events::{
+    ChecklistItemEventPayload, ChecklistReorderedEventPayload, ServerEvent,
+};
+use super::{
+    RequestRow, fetch_editable_request, fetch_request_recipient_ids,
+    fetch_visible_request, normalize_assignee_email, publish_event,
+    require_authenticated_user, resolve_assignee_user_id,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const MAX_CHECKLIST_ITEMS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
+pub(super) struct ChecklistItemRow {
+    id: Uuid,
+    pub(super) request_id: Uuid,
+    title: String,
+    position: i32,
+    done: bool,
+    done_at: Option<DateTime<Utc>>,
+    done_by_user_id: Option<Uuid>,
+    assignee_user_id: Option<Uuid>,
+    assignee_email: Option<String>,
+    due_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, JsonSchema)]
+pub(super) struct ChecklistProgress {
+    total: i64,
+    done: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateItemInput {
+    title: String,
+    assignee_email: Option<String>,
+    due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateItemInput {
+    title: Option<String>,
+    done: Option<bool>,
+    assignee_email: Option<String>,
+    #[serde(default, deserialize_with = "super::deserialize_present")]
+    due_at: Option<Option<DateTime<Utc>>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ReorderItemsInput {
+    item_ids: Vec<Uuid>,
+}
+
+fn item_projection_sql() -> &'static str {
+    "item.id,
+     item.request_id,
+     item.title,
+     item.position,
+     item.done,
+     item.done_at,
+     item.done_by_user_id,
+     item.assignee_user_id,
+     assignee.email AS assignee_email,
+     item.due_at,
+     item.created_at,
+     item.updated_at"
+}
+
+pub(super) async fn list_items(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    fetch_visible_request(&state.db, id, user.id).await?;
+
+    let query = format!(
+        "SELECT {}
+         FROM app.request_checklist_items item
+         LEFT JOIN app.app_users assignee ON assignee.id = item.assignee_user_id
+         WHERE item.request_id = $1
+         ORDER BY item.position ASC, item.created_at ASC",
+        item_projection_sql()
+    );
+    let items = sqlx::query_as::<_, ChecklistItemRow>(&query)
+        .bind(id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(response::ok(StatusCode::OK, items))
+}
+
+pub(super) async fn create_item(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<CreateItemInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let title = validate_title("title", &input.title)?;
+    let mut tx = state.db.begin().await?;
+    fetch_editable_request(&mut *tx, id, user.id).await?;
+    let assignee_user_id =
+        resolve_item_assignee(&mut tx, id, input.assignee_email.as_deref())
+            .await?;
+
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*)
+         FROM app.request_checklist_items
+         WHERE request_id = $1",
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if count as usize >= MAX_CHECKLIST_ITEMS {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "title".to_string(),
+            message: format!(
+                "a checklist may have at most {MAX_CHECKLIST_ITEMS} items"
+            ),
+        }]));
+    }
+
+    let item_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.request_checklist_items (
+           request_id,
+           title,
+           position,
+           assignee_user_id,
+           due_at
+         )
+         SELECT $1, $2, COALESCE(MAX(position) + 1, 0), $3, $4
+         FROM app.request_checklist_items
+         WHERE request_id = $1
+         RETURNING id",
+    )
+    .bind(id)
+    .bind(&title)
+    .bind(assignee_user_id)
+    .bind(input.due_at)
+    .fetch_one(&mut *tx)
+    .await?;
+    let item = fetch_item(&mut *tx, id, item_id).await?;
+    tx.commit().await?;
+
+    publish_item_event(&state, item.clone(), ServerEvent::ChecklistItemAdded)
+        .await?;
+    Ok(response::ok(StatusCode::CREATED, item))
+}
+
+pub(super) async fn update_item(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path((id, item_id)): Path<(Uuid, Uuid)>,
+    Json(input): Json<UpdateItemInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let mut tx = state.db.begin().await?;
+    fetch_visible_request(&mut *tx, id, user.id).await?;
+    let existing = fetch_item(&mut *tx, id, item_id).await?;
+
+    let only_done = input.title.is_none()
+        && input.assignee_email.is_none()
+        && input.due_at.is_none();
+    let may_edit = match fetch_editable_request(&mut *tx, id, user.id).await {
+        Ok(_) => true,
+        Err(AppError::NotFound(_)) => false,
+        Err(err) => return Err(err),
+    };
+    let is_item_assignee = existing.assignee_user_id == Some(user.id);
+    if !(may_edit || is_item_assignee && only_done) {
+        return Err(AppError::NotFound("request not found".to_string()));
+    }
+
+    let title = match input.title.as_deref() {
+        Some(title) => validate_title("title", title)?,
+        None => existing.title.clone(),
+    };
+    let assignee_user_id = match input.assignee_email.as_deref() {
+        Some(email) => resolve_item_assignee(&mut tx, id, Some(email)).await?,
+        None => existing.assignee_user_id,
+    };
+    let due_at = input.due_at.unwrap_or(existing.due_at);
+    let done = input.done.unwrap_or(existing.done);
+
+    sqlx::query(
+        "UPDATE app.request_checklist_items
+         SET title = $2,
+             assignee_user_id = $3,
+             due_at = $4,
+             done = $5,
+             done_at = CASE
+               WHEN NOT $5 THEN NULL
+               ELSE COALESCE(done_at, NOW())
+             END,
+             done_by_user_id = CASE
+               WHEN NOT $5 THEN NULL
+               WHEN done THEN done_by_user_id
+               ELSE $6
+             END
+         WHERE id = $1",
+    )
+    .bind(item_id)
+    .bind(&title)
+    .bind(assignee_user_id)
+    .bind(due_at)
+    .bind(done)
+    .bind(user.id)
+    .execute(&mut *tx)
+    .await?;
+    let item = fetch_item(&mut *tx, id, item_id).await?;
+    tx.commit().await?;
+
+    publish_item_event(&state, item.clone(), ServerEvent::ChecklistItemUpdated)
+        .await?;
+    Ok(response::ok(StatusCode::OK, item))
+}
+
+pub(super) async fn delete_item(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path((id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let mut tx = state.db.begin().await?;
+    fetch_editable_request(&mut *tx, id, user.id).await?;
+    let item = fetch_item(&mut *tx, id, item_id).await?;
+    sqlx::query("DELETE FROM app.request_checklist_items WHERE id = $1")
+        .bind(item.id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    publish_item_event(&state, item, ServerEvent::ChecklistItemRemoved).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn reorder_items(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<ReorderItemsInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let mut tx = state.db.begin().await?;
+    fetch_editable_request(&mut *tx, id, user.id).await?;
+    let current: HashSet<Uuid> = sqlx::query_scalar(
+        "SELECT id
+         FROM app.request_checklist_items
+         WHERE request_id = $1
+         FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .collect();
+    let requested: HashSet<Uuid> = input.item_ids.iter().copied().collect();
+    if requested.len() != input.item_ids.len() || requested != current {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "item_ids".to_string(),
+            message: "item_ids must list every checklist item exactly once"
+                .to_string(),
+        }]));
+    }
+
+    sqlx::query(
+        "UPDATE app.request_checklist_items item
+         SET position = ordered.position - 1
+         FROM UNNEST($2::uuid[]) WITH ORDINALITY AS ordered(id, position)
+         WHERE item.id = ordered.id AND item.request_id = $1",
+    )
+    .bind(id)
+    .bind(&input.item_ids)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let recipients = fetch_request_recipient_ids(&state.db, id).await?;
+    publish_event(
+        &state,
+        &recipients,
+        ServerEvent::ChecklistReordered(ChecklistReorderedEventPayload {
+            request_id: id,
+            item_ids: input.item_ids.clone(),
+        }),
+    )
+    .await;
+
+    Ok(response::ok(
+        StatusCode::OK,
+        serde_json::json!({ "item_ids": input.item_ids }),
+    ))
+}
+
+pub(super) fn ensure_resolvable(
+    existing: &RequestRow,
+    next_status: &str,
+    checklist_required: bool,
+) -> Result<(), AppError> {
+    let open = existing.checklist_total - existing.checklist_done;
+    if next_status != "resolved"
+        || existing.status == "resolved"
+        || !checklist_required
+        || open == 0
+    {
+        return Ok(());
+    }
+
+    Err(AppError::Validation(vec![ErrorDetail {
+        field: "status".to_string(),
+        message: format!(
+            "{open} checklist item(s) must be done before resolving"
+        ),
+    }]))
+}
+
+pub(super) fn validate_titles(
+    field: &str,
+    titles: &[String],
+) -> Vec<ErrorDetail> {
+    let mut details = Vec::new();
+    if titles.len() > MAX_CHECKLIST_ITEMS {
+        details.push(ErrorDetail {
+            field: field.to_string(),
+            message: format!(
+                "a checklist may have at most {MAX_CHECKLIST_ITEMS} items"
+            ),
+        });
+    }
+    for (index, title) in titles.iter().enumerate() {
+        if let Err(AppError::Validation(errors)) =
+            validate_title(&format!("{field}[{index}]"), title)
+        {
+            details.extend(errors);
+        }
+    }
+
+    details
+}
+
+pub(super) async fn insert_initial_items(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    titles: &[String],
+) -> Result<(), AppError> {
+    if titles.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO app.request_checklist_items (request_id, title, position)
+         SELECT $1, btrim(titles.title), titles.position - 1
+         FROM UNNEST($2::text[]) WITH ORDINALITY AS titles(title, position)",
+    )
+    .bind(request_id)
+    .bind(titles)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn fetch_item_titles<'e, E>(
+    executor: E,
+    request_id: Uuid,
+) -> Result<Vec<String>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_scalar(
+        "SELECT title
+         FROM app.request_checklist_items
+         WHERE request_id = $1
+         ORDER BY position ASC, created_at ASC",
+    )
+    .bind(request_id)
+    .fetch_all(executor)
+    .await
+    .map_err(AppError::from)
+}
+
+async fn fetch_item<'e, E>(
+    executor: E,
+    request_id: Uuid,
+    item_id: Uuid,
+) -> Result<ChecklistItemRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let query = format!(
+        "SELECT {}
+         FROM app.request_checklist_items item
+         LEFT JOIN app.app_users assignee ON assignee.id = item.assignee_user_id
+         WHERE item.id = $1 AND item.request_id = $2",
+        item_projection_sql()
+    );
+
+    sqlx::query_as::<_, ChecklistItemRow>(&query)
+        .bind(item_id)
+        .bind(request_id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound("checklist item not found".to_string())
+        })
+}
+
+async fn resolve_item_assignee(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    email: Option<&str>,
+) -> Result<Option<Uuid>, AppError> {
+    let normalized = normalize_assignee_email(email)?;
+    let assignee_user_id =
+        resolve_assignee_user_id(&mut *conn, normalized.as_deref()).await?;
+
+    if let Some(user_id) = assignee_user_id {
+        sqlx::query(
+            "SELECT app.upsert_request_participant($1, $2, 'checklist', NOW())",
+        )
+        .bind(request_id)
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(assignee_user_id)
+}
+
+async fn publish_item_event(
+    state: &AppState,
+    item: ChecklistItemRow,
+    event: fn(ChecklistItemEventPayload) -> ServerEvent,
+) -> Result<(), AppError> {
+    let progress = sqlx::query_as::<_, ChecklistProgress>(
+        "SELECT COUNT(*) AS total, COUNT(*) FILTER (WHERE done) AS done
+         FROM app.request_checklist_items
+         WHERE request_id = $1",
+    )
+    .bind(item.request_id)
+    .fetch_one(&state.db)
+    .await?;
+    let recipients =
+        fetch_request_recipient_ids(&state.db, item.request_id).await?;
+
+    publish_event(
+        state,
+        &recipients,
+        event(ChecklistItemEventPayload { item, progress }),
+    )
+    .await;
+
+    Ok(())
+}
+
+fn validate_title(field: &str, title: &str) -> Result<String, AppError> {
+    let title = title.trim();
+    let message = if title.is_empty() {
+        "checklist items need a title"
+    } else if title.len() > 255 {
+        "checklist item titles must be <= 255 characters"
+    } else {
+        return Ok(title.to_string());
+    };
+
+    Err(AppError::Validation(vec![ErrorDetail {
+        field: field.to_string(),
+        message: message.to_string(),
+    }]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_titles_reports_each_bad_item() {
+        let titles = vec![
+            "Create account".to_string(),
+            "  ".to_string(),
+            "x".repeat(256),
+        ];
+        let fields: Vec<_> = validate_titles("checklist", &titles)
+            .into_iter()
+            .map(|detail| detail.field)
+            .collect();
+        assert_eq!(fields, ["checklist[1]", "checklist[2]"]);
+
+        let too_many = vec!["Step".to_string(); MAX_CHECKLIST_ITEMS + 1];
+        assert_eq!(validate_titles("checklist", &too_many).len(), 1);
+    }
+}
@@ -0,0 +1,242 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, PgConnection, PgExecutor};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::events::{CommentEventPayload, ServerEvent};
+use super::mentions::MentionRow;
+use super::{
+    fetch_editable_request, fetch_request_recipient_ids, fetch_visible_request,
+    mentions, notifications, publish_event, require_authenticated_user,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
+pub(super) struct CommentRow {
+    pub(super) id: Uuid,
+    pub(super) request_id: Uuid,
+    author_user_id: Uuid,
+    author_email: String,
+    author_display_name: String,
+    body: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateCommentInput {
+    body: String,
+}
+
+fn comment_projection_sql() -> &'static str {
+    "comment.id,
+     comment.request_id,
+     comment.author_user_id,
+     author.email AS author_email,
+     author.display_name AS author_display_name,
+     comment.body,
+     comment.created_at"
+}
+
+pub(super) async fn list_comments(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    fetch_visible_request(&state.db, id, user.id).await?;
+
+    let query = format!(
+        "SELECT {}
+         FROM app.request_comments comment
+         JOIN app.app_users author ON author.id = comment.author_user_id
+         WHERE comment.request_id = $1
+         ORDER BY comment.created_at ASC, comment.id ASC",
+        comment_projection_sql()
+    );
+    let comments = sqlx::query_as::<_, CommentRow>(&query)
+        .bind(id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(response::ok(StatusCode::OK, comments))
+}
+
+pub(super) async fn create_comment(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<CreateCommentInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let body = validate_body(&input.body)?;
+    let mut tx = state.db.begin().await?;
+    fetch_visible_request(&mut *tx, id, user.id).await?;
+    let (comment, new_mentions) =
+        insert_comment(&mut tx, id, user.id, &body).await?;
+    tx.commit().await?;
+
+    publish_comment(&state, user.id, comment.clone(), new_mentions).await?;
+    Ok(response::ok(StatusCode::CREATED, comment))
+}
+
+pub(super) async fn delete_comment(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let request = fetch_visible_request(&state.db, id, user.id).await?;
+    let comment = fetch_comment(&state.db, id, comment_id).await?;
+    if comment.author_user_id != user.id && request.owner_user_id != user.id {
+        return Err(AppError::NotFound("comment not found".to_string()));
+    }
+
+    let recipients = fetch_request_recipient_ids(&state.db, id).await?;
+    sqlx::query("DELETE FROM app.request_comments WHERE id = $1")
+        .bind(comment.id)
+        .execute(&state.db)
+        .await?;
+
+    publish_event(
+        &state,
+        &recipients,
+        ServerEvent::CommentDeleted(CommentEventPayload { comment }),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn insert_comment(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    author_user_id: Uuid,
+    body: &str,
+) -> Result<(CommentRow, Vec<MentionRow>), AppError> {
+    let may_edit =
+        match fetch_editable_request(&mut *conn, request_id, author_user_id)
+            .await
+        {
+            Ok(_) => true,
+            Err(AppError::NotFound(_)) => false,
+            Err(err) => return Err(err),
+        };
+    let comment_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.request_comments (request_id, author_user_id, body)
+         VALUES ($1, $2, $3)
+         RETURNING id",
+    )
+    .bind(request_id)
+    .bind(author_user_id)
+    .bind(body)
+    .fetch_one(&mut *conn)
+    .await?;
+    let new_mentions = mentions::record_mentions(
+        &mut *conn,
+        request_id,
+        Some(comment_id),
+        author_user_id,
+        body,
+        may_edit,
+    )
+    .await?;
+    let comment = fetch_comment(&mut *conn, request_id, comment_id).await?;
+
+    Ok((comment, new_mentions))
+}
+
+pub(super) async fn publish_comment(
+    state: &AppState,
+    author_user_id: Uuid,
+    comment: CommentRow,
+    new_mentions: Vec<MentionRow>,
+) -> Result<(), AppError> {
+    let (request_id, comment_id) = (comment.request_id, comment.id);
+    let recipients = fetch_request_recipient_ids(&state.db, request_id).await?;
+    publish_event(
+        state,
+        &recipients,
+        ServerEvent::CommentCreated(CommentEventPayload { comment }),
+    )
+    .await;
+    let mentioned: Vec<Uuid> = new_mentions
+        .iter()
+        .map(|mention| mention.mentioned_user_id)
+        .collect();
+    let commenters: Vec<Uuid> = recipients
+        .iter()
+        .copied()
+        .filter(|user_id| !mentioned.contains(user_id))
+        .collect();
+    notifications::notify(
+        state,
+        "comment",
+        request_id,
+        Some(author_user_id),
+        json!({ "comment_id": comment_id }),
+        &commenters,
+    )
+    .await?;
+    mentions::publish_mentions(state, new_mentions).await
+}
+
+async fn fetch_comment<'e, E>(
+    executor: E,
+    request_id: Uuid,
+    comment_id: Uuid,
+) -> Result<CommentRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let query = format!(
+        "SELECT {}
+         FROM app.request_comments comment
+         JOIN app.app_users author ON author.id = comment.author_user_id
+         WHERE comment.id = $1 AND comment.request_id = $2",
+        comment_projection_sql()
+    );
+
+    sqlx::query_as::<_, CommentRow>(&query)
+        .bind(comment_id)
+        .bind(request_id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| AppError::NotFound("comment not found".to_string()))
+}
+
+pub(super) fn validate_body(body: &str) -> Result<String, AppError> {
+    let body = body.trim();
+    let message = if body.is_empty() {
+        "comment cannot be empty"
+    } else if body.chars().count() > 5000 {
+        "comment must be <= 5000 characters"
+    } else {
+        return Ok(body.to_string());
+    };
+
+    Err(AppError::Validation(vec![ErrorDetail {
+        field: "body".to_string(),
+        message: message.to_string(),
+    }]))
+}
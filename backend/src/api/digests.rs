@@ -0,0 +1,488 @@
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde_json::Value;
+use sqlx::FromRow;
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::workers;
+use crate::{
+    AppState,
+    error::AppError,
+    mail::{MailMessage, MailTransport},
+};
+
+const DIGEST_WORKER_INTERVAL: Duration = Duration::from_secs(60);
+const DIGEST_BATCH: i64 = 200;
+const DIGEST_SECTION_LIMIT: usize = 50;
+
+#[derive(Debug, Clone)]
+pub struct DigestConfig {
+    pub period: TimeDelta,
+    pub app_url: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct DigestRecipient {
+    email: String,
+    display_name: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct DigestActivity {
+    kind: String,
+    request_id: Uuid,
+    request_title: String,
+    actor_display_name: Option<String>,
+    data: Value,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct OverdueRequest {
+    id: Uuid,
+    title: String,
+    status: String,
+    due_at: DateTime<Utc>,
+}
+
+struct Digest<'a> {
+    display_name: &'a str,
+    since: DateTime<Utc>,
+    activity: &'a [DigestActivity],
+    overdue: &'a [OverdueRequest],
+    app_url: &'a str,
+}
+
+pub fn spawn_digest_worker<T>(
+    state: AppState,
+    mailer: T,
+    config: DigestConfig,
+) -> JoinHandle<()>
+where
+    T: MailTransport + 'static,
+{
+    let mailer = Arc::new(mailer);
+    let config = Arc::new(config);
+    workers::spawn_periodic("digests", DIGEST_WORKER_INTERVAL, move || {
+        let (state, mailer, config) =
+            (state.clone(), Arc::clone(&mailer), Arc::clone(&config));
+        async move { run_due_digests(&state, &*mailer, &config, Utc::now()).await }
+    })
+}
+
+pub async fn run_due_digests<T>(
+    state: &AppState,
+    mailer: &T,
+    config: &DigestConfig,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError>
+where
+    T: MailTransport,
+{
+    let period_end = period_end(now, config.period);
+    let due: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT users.id
+         FROM app.app_users users
+         LEFT JOIN app.user_preferences prefs ON prefs.user_id = users.id
+         WHERE users.deleted_at IS NULL
+           AND users.is_active
+           AND users.email IS NOT NULL
+           AND COALESCE(prefs.email_digest, TRUE)
+           AND NOT EXISTS (
+             SELECT 1
+             FROM app.email_digest_deliveries delivery
+             WHERE delivery.user_id = users.id
+               AND delivery.period_end = $1
+           )
+         ORDER BY users.id
+         LIMIT $2",
+    )
+    .bind(period_end)
+    .bind(DIGEST_BATCH)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut sent = 0;
+    for user_id in due {
+        match deliver_digest(state, mailer, config, user_id, period_end).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(err) => warn!(%user_id, error = %err, "digest delivery failed"),
+        }
+    }
+
+    Ok(sent)
+}
+
+async fn deliver_digest<T>(
+    state: &AppState,
+    mailer: &T,
+    config: &DigestConfig,
+    user_id: Uuid,
+    period_end: DateTime<Utc>,
+) -> Result<bool, AppError>
+where
+    T: MailTransport,
+{
+    let mut tx = state.db.begin().await?;
+
+    // A concurrent claim by another replica blocks here until it commits,
+    // then conflicts; if it rolls back, this replica takes over.
+    let Some(period_start) = sqlx::query_scalar::<_, DateTime<Utc>>(
+        "INSERT INTO app.email_digest_deliveries (
+           user_id,
+           period_start,
+           period_end
+         )
+         SELECT $1, COALESCE(MAX(delivery.period_end), $3), $2
+         FROM app.email_digest_deliveries delivery
+         WHERE delivery.user_id = $1 AND delivery.period_end < $2
+         ON CONFLICT (user_id, period_end) DO NOTHING
+         RETURNING period_start",
+    )
+    .bind(user_id)
+    .bind(period_end)
+    .bind(period_end - config.period)
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    let recipient = sqlx::query_as::<_, DigestRecipient>(
+        "SELECT email, display_name FROM app.app_users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    let activity = sqlx::query_as::<_, DigestActivity>(
+        "SELECT
+           notification.kind,
+           notification.request_id,
+           req.title AS request_title,
+           actor.display_name AS actor_display_name,
+           notification.data
+         FROM app.notifications notification
+         JOIN app.requests req ON req.id = notification.request_id
+         LEFT JOIN app.app_users actor ON actor.id = notification.actor_user_id
+         WHERE notification.user_id = $1
+           AND notification.kind IN ('assigned', 'status_changed')
+           AND notification.created_at > $2
+           AND notification.created_at <= $3
+         ORDER BY notification.created_at ASC, notification.id ASC",
+    )
+    .bind(user_id)
+    .bind(period_start)
+    .bind(period_end)
+    .fetch_all(&mut *tx)
+    .await?;
+    let overdue = sqlx::query_as::<_, OverdueRequest>(
+        "SELECT id, title, status, due_at
+         FROM app.requests
+         WHERE $1 IN (owner_user_id, assignee_user_id)
+           AND status <> 'resolved'
+           AND due_at <= $2
+         ORDER BY due_at ASC, id ASC",
+    )
+    .bind(user_id)
+    .bind(period_end)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let item_count = activity.len() + overdue.len();
+    if item_count == 0 {
+        tx.commit().await?;
+        return Ok(false);
+    }
+
+    let (subject, text, html) = render_digest(&Digest {
+        display_name: &recipient.display_name,
+        since: period_start,
+        activity: &activity,
+        overdue: &overdue,
+        app_url: &config.app_url,
+    });
+    mailer
+        .send(MailMessage {
+            to: recipient.email,
+            subject,
+            text,
+            html,
+        })
+        .await?;
+
+    sqlx::query(
+        "UPDATE app.email_digest_deliveries
+         SET item_count = $3, sent_at = NOW()
+         WHERE user_id = $1 AND period_end = $2",
+    )
+    .bind(user_id)
+    .bind(period_end)
+    .bind(item_count as i32)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+// Periods are aligned to the Unix epoch so every replica computes the
+// same period.
+fn period_end(now: DateTime<Utc>, period: TimeDelta) -> DateTime<Utc> {
+    let period = period.num_seconds().max(60);
+    let seconds = now.timestamp();
+    DateTime::from_timestamp(seconds - seconds.rem_euclid(period), 0)
+        .unwrap_or(now)
+}
+
+fn render_digest(digest: &Digest<'_>) -> (String, String, String) {
+    let (assignments, status_changes): (Vec<_>, Vec<_>) = digest
+        .activity
+        .iter()
+        .partition(|activity| activity.kind == "assigned");
+    let app_url = digest.app_url.trim_end_matches('/');
+    let link = |id: Uuid| format!("{app_url}/requests/{id}");
+
+    let sections = [
+        (
+            "New assignments",
+            assignments
+                .iter()
+                .map(|activity| {
+                    let line = match &activity.actor_display_name {
+                        Some(actor) => {
+                            format!(
+                                "{} (assigned by {actor})",
+                                activity.request_title
+                            )
+                        }
+                        None => activity.request_title.clone(),
+                    };
+                    (line, link(activity.request_id))
+                })
+                .collect::<Vec<_>>(),
+        ),
+        (
+            "Status changes",
+            status_changes
+                .iter()
+                .map(|activity| {
+                    let from = activity.data["from"].as_str().unwrap_or("");
+                    let to = activity.data["to"].as_str().unwrap_or("");
+                    let mut line = format!(
+                        "{}: {} -> {}",
+                        activity.request_title,
+                        status_label(from),
+                        status_label(to)
+                    );
+                    if let Some(actor) = &activity.actor_display_name {
+                        let _ = write!(line, " (by {actor})");
+                    }
+                    (line, link(activity.request_id))
+                })
+                .collect(),
+        ),
+        (
+            "Overdue",
+            digest
+                .overdue
+                .iter()
+                .map(|request| {
+                    let line = format!(
+                        "{}: due {}, {}",
+                        request.title,
+                        request.due_at.format("%Y-%m-%d %H:%M UTC"),
+                        status_label(&request.status).to_lowercase()
+                    );
+                    (line, link(request.id))
+                })
+                .collect(),
+        ),
+    ];
+
+    let counts = [
+        (assignments.len(), "new assignment", "new assignments"),
+        (status_changes.len(), "status change", "status changes"),
+        (digest.overdue.len(), "overdue request", "overdue requests"),
+    ];
+    let summary = counts
+        .iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| {
+            format!("{count} {}", if *count == 1 { one } else { many })
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let subject = format!("Reqstly digest: {summary}");
+
+    let intro = format!(
+        "Here is your activity since {}.",
+        digest.since.format("%Y-%m-%d %H:%M UTC")
+    );
+    let footer = "You receive this email because email digests are enabled \
+                  in your Reqstly preferences.";
+
+    let mut text = format!("Hi {},\n\n{intro}\n", digest.display_name);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html><body>\n<p>Hi {},</p>\n<p>{}</p>\n",
+        escape_html(digest.display_name),
+        escape_html(&intro)
+    );
+    for (heading, entries) in &sections {
+        if entries.is_empty() {
+            continue;
+        }
+        let _ = write!(text, "\n{heading}\n");
+        let _ = write!(html, "<h2>{heading}</h2>\n<ul>\n");
+        for (line, url) in entries.iter().take(DIGEST_SECTION_LIMIT) {
+            let _ = writeln!(text, "- {line}\n  {url}");
+            let _ = writeln!(
+                html,
+                "<li><a href=\"{}\">{}</a></li>",
+                escape_html(url),
+                escape_html(line)
+            );
+        }
+        if entries.len() > DIGEST_SECTION_LIMIT {
+            let more =
+                format!("...and {} more", entries.len() - DIGEST_SECTION_LIMIT);
+            let _ = writeln!(text, "- {more}");
+            let _ = writeln!(html, "<li>{more}</li>");
+        }
+        html.push_str("</ul>\n");
+    }
+    let _ = write!(text, "\n{footer}\n");
+    let _ = write!(html, "<p>{footer}</p>\n</body></html>\n");
+
+    (subject, text, html)
+}
+
+fn status_label(status: &str) -> &str {
+    match status {
+        "open" => "Open",
+        "in_progress" => "In progress",
+        "resolved" => "Resolved",
+        other => other,
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn period_end_aligns_to_whole_periods() {
+        let day = TimeDelta::hours(24);
+        assert_eq!(
+            period_end(at("2026-03-08T17:45:12Z"), day),
+            at("2026-03-08T00:00:00Z")
+        );
+        assert_eq!(
+            period_end(at("2026-03-08T00:00:00Z"), day),
+            at("2026-03-08T00:00:00Z")
+        );
+        assert_eq!(
+            period_end(at("2026-03-08T17:45:12Z"), TimeDelta::hours(6)),
+            at("2026-03-08T12:00:00Z")
+        );
+    }
+
+    #[test]
+    fn render_digest_lists_sections_and_escapes_html() {
+        let request_id = Uuid::nil();
+        let activity = [
+            DigestActivity {
+                kind: "assigned".to_string(),
+                request_id,
+                request_title: "Fix <VPN> & proxy".to_string(),
+                actor_display_name: Some("Ana".to_string()),
+                data: json!({}),
+            },
+            DigestActivity {
+                kind: "status_changed".to_string(),
+                request_id,
+                request_title: "Laptop".to_string(),
+                actor_display_name: None,
+                data: json!({ "from": "open", "to": "in_progress" }),
+            },
+        ];
+        let overdue = [OverdueRequest {
+            id: request_id,
+            title: "Badge".to_string(),
+            status: "open".to_string(),
+            due_at: at("2026-03-07T09:30:00Z"),
+        }];
+
+        let (subject, text, html) = render_digest(&Digest {
+            display_name: "Bo",
+            since: at("2026-03-07T00:00:00Z"),
+            activity: &activity,
+            overdue: &overdue,
+            app_url: "https://reqstly.test/",
+        });
+
+        assert_eq!(
+            subject,
+            "Reqstly digest: 1 new assignment, 1 status change, \
+             1 overdue request"
+        );
+        let link = format!("https://reqstly.test/requests/{request_id}");
+        assert!(text.contains("- Fix <VPN> & proxy (assigned by Ana)"));
+        assert!(text.contains(&format!("  {link}")));
+        assert!(text.contains("- Laptop: Open -> In progress\n"));
+        assert!(text.contains("- Badge: due 2026-03-07 09:30 UTC, open"));
+        assert!(html.contains("Fix &lt;VPN&gt; &amp; proxy (assigned by Ana)"));
+        assert!(html.contains(&format!("<a href=\"{link}\">")));
+        assert!(!html.contains("<VPN>"));
+    }
+
+    #[test]
+    fn render_digest_truncates_long_sections() {
+        let overdue: Vec<_> = (0..DIGEST_SECTION_LIMIT + 2)
+            .map(|index| OverdueRequest {
+                id: Uuid::new_v4(),
+                title: format!("Request {index}"),
+                status: "open".to_string(),
+                due_at: at("2026-03-07T09:30:00Z"),
+            })
+            .collect();
+
+        let (subject, text, _) = render_digest(&Digest {
+            display_name: "Bo",
+            since: at("2026-03-07T00:00:00Z"),
+            activity: &[],
+            overdue: &overdue,
+            app_url: "https://reqstly.test",
+        });
+
+        assert_eq!(subject, "Reqstly digest: 52 overdue requests");
+        assert!(text.contains("- ...and 2 more"));
+        assert!(!text.contains("New assignments"));
+    }
+}
@@ -0,0 +1,960 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::{FromRow, PgConnection, PgExecutor};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use tower_sessions::Session;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::events::{
+    AuditAppendEventPayload, RequestCreatedEventPayload,
+    RequestPatchEventPayload, ServerEvent,
+};
+use super::filters::SlaFilter;
+use super::watchers::{self, WatcherRow};
+use super::{
+    AuditLogRow, RequestRow, collect_changed_fields, deserialize_present,
+    fetch_owned_request, fetch_request_recipient_ids, insert_audit_log,
+    is_valid_email, notifications, publish_event, request_audit_snapshot,
+    require_authenticated_user, sla, split_recipients_by_visibility,
+    validate_category, validate_priority, workers,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const SYSTEM_USER_ID: Uuid = Uuid::from_u128(1);
+const ESCALATION_WORKER_INTERVAL: Duration = Duration::from_secs(60);
+const ESCALATION_BATCH: i64 = 100;
+const MAX_IDLE_MINUTES: i32 = 525_600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EscalationAction {
+    Reassign,
+    RaisePriority,
+    Notify,
+    AddWatcher,
+}
+
+impl EscalationAction {
+    const VALUES: &str = "reassign, raise_priority, notify, add_watcher";
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "reassign" => Some(Self::Reassign),
+            "raise_priority" => Some(Self::RaisePriority),
+            "notify" => Some(Self::Notify),
+            "add_watcher" => Some(Self::AddWatcher),
+            _ => None,
+        }
+    }
+
+    fn needs_target(self) -> bool {
+        self != Self::RaisePriority
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+struct EscalationRuleRow {
+    id: Uuid,
+    workspace_domain: String,
+    name: String,
+    active: bool,
+    priority: Option<String>,
+    category: Option<String>,
+    status: Option<String>,
+    idle_minutes: Option<i32>,
+    sla_state: Option<String>,
+    action: String,
+    target_user_id: Option<Uuid>,
+    target_email: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateRuleInput {
+    name: String,
+    active: Option<bool>,
+    priority: Option<String>,
+    category: Option<String>,
+    status: Option<String>,
+    idle_minutes: Option<i32>,
+    sla_state: Option<String>,
+    action: String,
+    target_email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateRuleInput {
+    name: Option<String>,
+    active: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    priority: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    category: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    status: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    idle_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    sla_state: Option<Option<String>>,
+    action: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    target_email: Option<Option<String>>,
+}
+
+#[derive(Debug)]
+struct RuleFields {
+    name: String,
+    active: bool,
+    priority: Option<String>,
+    category: Option<String>,
+    status: Option<String>,
+    idle_minutes: Option<i32>,
+    sla_state: Option<String>,
+    action: String,
+    target_email: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct EscalationCandidate {
+    rule_id: Uuid,
+    request_id: Uuid,
+    owner_user_id: Uuid,
+    updated_at: DateTime<Utc>,
+}
+
+enum Escalated {
+    Updated {
+        existing: Box<RequestRow>,
+        updated: Box<RequestRow>,
+    },
+    Notified {
+        user_id: Uuid,
+    },
+    Watched {
+        watcher: WatcherRow,
+        newly_visible: bool,
+    },
+}
+
+const RULE_SELECT_SQL: &str = "SELECT
+       rule.id,
+       rule.workspace_domain,
+       rule.name,
+       rule.active,
+       rule.priority,
+       rule.category,
+       rule.status,
+       rule.idle_minutes,
+       rule.sla_state,
+       rule.action,
+       rule.target_user_id,
+       target.email AS target_email,
+       rule.created_at,
+       rule.updated_at
+     FROM app.escalation_rules rule
+     LEFT JOIN app.app_users target ON target.id = rule.target_user_id";
+
+pub(super) async fn list_rules(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let workspace = sla::workspace_of(&user)?;
+
+    let rules = sqlx::query_as::<_, EscalationRuleRow>(&format!(
+        "{RULE_SELECT_SQL}
+         WHERE rule.workspace_domain = $1
+         ORDER BY rule.created_at ASC, rule.id ASC"
+    ))
+    .bind(workspace)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, rules))
+}
+
+pub(super) async fn create_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreateRuleInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        sla::admin_workspace_of(&state.db, &user, "escalation rules").await?;
+
+    let fields = RuleFields {
+        name: input.name,
+        active: input.active.unwrap_or(true),
+        priority: input.priority,
+        category: input.category,
+        status: input.status,
+        idle_minutes: input.idle_minutes,
+        sla_state: input.sla_state,
+        action: input.action,
+        target_email: input.target_email,
+    };
+    let fields = validate_rule(fields)?;
+    let target_user_id =
+        resolve_target(&state.db, &workspace, fields.target_email.as_deref())
+            .await?;
+
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.escalation_rules (
+           workspace_domain,
+           name,
+           active,
+           priority,
+           category,
+           status,
+           idle_minutes,
+           sla_state,
+           action,
+           target_user_id,
+           created_by_user_id
+         )
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+         RETURNING id",
+    )
+    .bind(&workspace)
+    .bind(&fields.name)
+    .bind(fields.active)
+    .bind(&fields.priority)
+    .bind(&fields.category)
+    .bind(&fields.status)
+    .bind(fields.idle_minutes)
+    .bind(&fields.sla_state)
+    .bind(&fields.action)
+    .bind(target_user_id)
+    .bind(user.id)
+    .fetch_one(&state.db)
+    .await?;
+    let rule = fetch_rule(&state.db, &workspace, id).await?;
+
+    Ok(response::ok(StatusCode::CREATED, rule))
+}
+
+pub(super) async fn get_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let rule = fetch_rule(&state.db, &sla::workspace_of(&user)?, id).await?;
+
+    Ok(response::ok(StatusCode::OK, rule))
+}
+
+pub(super) async fn update_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateRuleInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        sla::admin_workspace_of(&state.db, &user, "escalation rules").await?;
+
+    let existing = fetch_rule(&state.db, &workspace, id).await?;
+    let fields = RuleFields {
+        name: input.name.unwrap_or(existing.name),
+        active: input.active.unwrap_or(existing.active),
+        priority: input.priority.unwrap_or(existing.priority),
+        category: input.category.unwrap_or(existing.category),
+        status: input.status.unwrap_or(existing.status),
+        idle_minutes: input.idle_minutes.unwrap_or(existing.idle_minutes),
+        sla_state: input.sla_state.unwrap_or(existing.sla_state),
+        action: input.action.unwrap_or(existing.action),
+        target_email: input.target_email.unwrap_or(existing.target_email),
+    };
+    let fields = validate_rule(fields)?;
+    let target_user_id =
+        resolve_target(&state.db, &workspace, fields.target_email.as_deref())
+            .await?;
+
+    sqlx::query(
+        "UPDATE app.escalation_rules
+         SET name = $2,
+             active = $3,
+             priority = $4,
+             category = $5,
+             status = $6,
+             idle_minutes = $7,
+             sla_state = $8,
+             action = $9,
+             target_user_id = $10
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(&fields.name)
+    .bind(fields.active)
+    .bind(&fields.priority)
+    .bind(&fields.category)
+    .bind(&fields.status)
+    .bind(fields.idle_minutes)
+    .bind(&fields.sla_state)
+    .bind(&fields.action)
+    .bind(target_user_id)
+    .execute(&state.db)
+    .await?;
+    let rule = fetch_rule(&state.db, &workspace, id).await?;
+
+    Ok(response::ok(StatusCode::OK, rule))
+}
+
+pub(super) async fn delete_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let workspace =
+        sla::admin_workspace_of(&state.db, &user, "escalation rules").await?;
+    let existing = fetch_rule(&state.db, &workspace, id).await?;
+    sqlx::query("DELETE FROM app.escalation_rules WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn spawn_escalation_worker(state: AppState) -> JoinHandle<()> {
+    workers::spawn_periodic(
+        "escalations",
+        ESCALATION_WORKER_INTERVAL,
+        move || {
+            let state = state.clone();
+            async move { run_due_escalations(&state, Utc::now()).await }
+        },
+    )
+}
+
+pub async fn run_due_escalations(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError> {
+    let candidates = sqlx::query_as::<_, EscalationCandidate>(&format!(
+        "SELECT
+           rule.id AS rule_id,
+           req.id AS request_id,
+           req.owner_user_id,
+           req.updated_at
+         FROM app.escalation_rules rule
+         JOIN app.app_users owner
+           ON lower(btrim(split_part(owner.email, '@', 2))) = rule.workspace_domain
+         JOIN app.requests req ON req.owner_user_id = owner.id
+         LEFT JOIN app.request_slas sla ON sla.request_id = req.id
+         LEFT JOIN app.escalation_firings firing
+           ON firing.rule_id = rule.id AND firing.request_id = req.id
+         WHERE rule.active
+           AND req.status <> 'resolved'
+           AND (rule.status IS NULL OR req.status = rule.status)
+           AND (rule.priority IS NULL OR req.priority = rule.priority)
+           AND (rule.category IS NULL OR req.category = rule.category)
+           AND (
+             rule.idle_minutes IS NULL
+             OR req.updated_at <= $1 - make_interval(mins => rule.idle_minutes)
+           )
+           AND (
+             rule.sla_state IS NULL
+             OR (rule.sla_state = 'breached' AND {breached})
+             OR (rule.sla_state = 'at_risk' AND {at_risk})
+             OR (rule.sla_state = 'on_track' AND {on_track})
+           )
+           AND (
+             firing.request_id IS NULL
+             OR firing.request_updated_at < req.updated_at
+           )
+           AND CASE rule.action
+             WHEN 'reassign' THEN
+               req.assignee_user_id IS DISTINCT FROM rule.target_user_id
+             WHEN 'raise_priority' THEN req.priority <> 'high'
+             WHEN 'add_watcher' THEN
+               req.owner_user_id <> rule.target_user_id
+               AND req.assignee_user_id IS DISTINCT FROM rule.target_user_id
+               AND NOT EXISTS (
+                 SELECT 1
+                 FROM app.request_participants participants
+                 WHERE participants.request_id = req.id
+                   AND participants.user_id = rule.target_user_id
+                   AND participants.source = 'watcher'
+               )
+               AND NOT EXISTS (
+                 SELECT 1
+                 FROM app.request_checklist_items item
+                 WHERE item.request_id = req.id
+                   AND item.assignee_user_id = rule.target_user_id
+               )
+             ELSE TRUE
+           END
+         ORDER BY rule.created_at ASC, req.updated_at ASC
+         LIMIT $2",
+        breached = SlaFilter::Breached.condition(),
+        at_risk = SlaFilter::AtRisk.condition(),
+        on_track = SlaFilter::OnTrack.condition(),
+    ))
+    .bind(now)
+    .bind(ESCALATION_BATCH)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut fired = 0;
+    for candidate in candidates {
+        match apply_rule(state, &candidate).await {
+            Ok(true) => fired += 1,
+            Ok(false) => {}
+            Err(err) => {
+                warn!(
+                    rule_id = %candidate.rule_id,
+                    request_id = %candidate.request_id,
+                    error = %err,
+                    "escalation failed"
+                );
+            }
+        }
+    }
+
+    Ok(fired)
+}
+
+async fn apply_rule(
+    state: &AppState,
+    candidate: &EscalationCandidate,
+) -> Result<bool, AppError> {
+    let mut tx = state.db.begin().await?;
+    let request_id = candidate.request_id;
+
+    let claimed: Option<Uuid> = sqlx::query_scalar(
+        "INSERT INTO app.escalation_firings (
+           rule_id,
+           request_id,
+           request_updated_at
+         )
+         VALUES ($1, $2, $3)
+         ON CONFLICT (rule_id, request_id) DO UPDATE
+         SET request_updated_at = EXCLUDED.request_updated_at,
+             fired_at = NOW()
+         WHERE app.escalation_firings.request_updated_at
+               < EXCLUDED.request_updated_at
+         RETURNING rule_id",
+    )
+    .bind(candidate.rule_id)
+    .bind(request_id)
+    .bind(candidate.updated_at)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if claimed.is_none() {
+        return Ok(false);
+    }
+    let current: Option<DateTime<Utc>> = sqlx::query_scalar(
+        "SELECT updated_at FROM app.requests WHERE id = $1 FOR UPDATE",
+    )
+    .bind(request_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if current != Some(candidate.updated_at) {
+        return Ok(false);
+    }
+
+    let rule = sqlx::query_as::<_, EscalationRuleRow>(&format!(
+        "{RULE_SELECT_SQL} WHERE rule.id = $1"
+    ))
+    .bind(candidate.rule_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    let Some(action) = EscalationAction::parse(&rule.action) else {
+        return Ok(false);
+    };
+    let existing =
+        fetch_owned_request(&mut *tx, request_id, candidate.owner_user_id)
+            .await?;
+    let recipients_before =
+        fetch_request_recipient_ids(&mut *tx, request_id).await?;
+
+    let (escalated, audit) = match (action, rule.target_user_id) {
+        (EscalationAction::Reassign, Some(target)) => {
+            sqlx::query(
+                "UPDATE app.requests
+                 SET assignee_user_id = $2,
+                     updated_at = NOW()
+                 WHERE id = $1",
+            )
+            .bind(request_id)
+            .bind(target)
+            .execute(&mut *tx)
+            .await?;
+            update_escalated(&mut tx, &rule, existing).await?
+        }
+        (EscalationAction::RaisePriority, _) => {
+            let Some(priority) = raised_priority(&existing.priority) else {
+                return Ok(false);
+            };
+            sqlx::query(
+                "UPDATE app.requests
+                 SET priority = $2,
+                     updated_at = NOW()
+                 WHERE id = $1",
+            )
+            .bind(request_id)
+            .bind(priority)
+            .execute(&mut *tx)
+            .await?;
+            update_escalated(&mut tx, &rule, existing).await?
+        }
+        (EscalationAction::Notify, Some(target)) => {
+            let audit =
+                audit_targeted(&mut tx, &rule, request_id, "notified_email")
+                    .await?;
+            (Escalated::Notified { user_id: target }, audit)
+        }
+        (EscalationAction::AddWatcher, Some(target)) => {
+            let newly_visible = watchers::insert_watcher(
+                &mut tx,
+                request_id,
+                target,
+                "target_email",
+            )
+            .await?;
+            let watcher =
+                watchers::fetch_watcher(&mut *tx, request_id, target).await?;
+            let audit =
+                audit_targeted(&mut tx, &rule, request_id, "watcher_email")
+                    .await?;
+            (
+                Escalated::Watched {
+                    watcher,
+                    newly_visible,
+                },
+                audit,
+            )
+        }
+        _ => return Ok(false),
+    };
+
+    // Record the request as the rule left it, so its own change does not
+    // count as a new update.
+    sqlx::query(
+        "UPDATE app.escalation_firings firing
+         SET request_updated_at = req.updated_at
+         FROM app.requests req
+         WHERE firing.rule_id = $1
+           AND firing.request_id = $2
+           AND req.id = firing.request_id",
+    )
+    .bind(rule.id)
+    .bind(request_id)
+    .execute(&mut *tx)
+    .await?;
+    let recipients_after =
+        fetch_request_recipient_ids(&mut *tx, request_id).await?;
+    tx.commit().await?;
+
+    publish_escalated(
+        state,
+        escalated,
+        audit,
+        &recipients_before,
+        &recipients_after,
+    )
+    .await?;
+    Ok(true)
+}
+
+async fn update_escalated(
+    conn: &mut PgConnection,
+    rule: &EscalationRuleRow,
+    existing: RequestRow,
+) -> Result<(Escalated, AuditLogRow), AppError> {
+    sla::refresh_request_sla(&mut *conn, existing.id).await?;
+    let updated =
+        fetch_owned_request(&mut *conn, existing.id, existing.owner_user_id)
+            .await?;
+
+    let mut new_value = request_audit_snapshot(&updated);
+    new_value["escalation"] = escalation_marker(rule);
+    let audit = insert_audit_log(
+        &mut *conn,
+        updated.id,
+        SYSTEM_USER_ID,
+        "escalated",
+        request_audit_snapshot(&existing),
+        new_value,
+    )
+    .await?;
+
+    Ok((
+        Escalated::Updated {
+            existing: Box::new(existing),
+            updated: Box::new(updated),
+        },
+        audit,
+    ))
+}
+
+async fn audit_targeted(
+    conn: &mut PgConnection,
+    rule: &EscalationRuleRow,
+    request_id: Uuid,
+    email_key: &str,
+) -> Result<AuditLogRow, AppError> {
+    let mut new_value = json!({ "escalation": escalation_marker(rule) });
+    new_value[email_key] = json!(rule.target_email);
+
+    insert_audit_log(
+        &mut *conn,
+        request_id,
+        SYSTEM_USER_ID,
+        "escalated",
+        json!({}),
+        new_value,
+    )
+    .await
+}
+
+async fn publish_escalated(
+    state: &AppState,
+    escalated: Escalated,
+    audit: AuditLogRow,
+    recipients_before: &[Uuid],
+    recipients_after: &[Uuid],
+) -> Result<(), AppError> {
+    let request_id = audit.request_id;
+    let rule = audit.new_value["escalation"].clone();
+
+    match escalated {
+        Escalated::Updated { existing, updated } => {
+            let (existing_recipients, newly_visible_recipients) =
+                split_recipients_by_visibility(
+                    recipients_before,
+                    recipients_after,
+                );
+            publish_event(
+                state,
+                &existing_recipients,
+                ServerEvent::RequestPatch(RequestPatchEventPayload {
+                    request: (*updated).clone(),
+                    changed_fields: collect_changed_fields(&existing, &updated),
+                    previous_status: existing.status.clone(),
+                }),
+            )
+            .await;
+            publish_event(
+                state,
+                &newly_visible_recipients,
+                ServerEvent::RequestCreated(RequestCreatedEventPayload {
+                    request: (*updated).clone(),
+                }),
+            )
+            .await;
+            publish_audit(state, audit, recipients_after).await;
+            notifications::notify_request_change(
+                state,
+                SYSTEM_USER_ID,
+                Some(&existing),
+                &updated,
+                recipients_after,
+            )
+            .await?;
+        }
+        Escalated::Notified { user_id } => {
+            publish_audit(state, audit, recipients_after).await;
+            notifications::notify(
+                state,
+                "escalation",
+                request_id,
+                Some(SYSTEM_USER_ID),
+                rule,
+                &[user_id],
+            )
+            .await?;
+        }
+        Escalated::Watched {
+            watcher,
+            newly_visible,
+        } => {
+            watchers::publish_watcher_added(
+                state,
+                request_id,
+                watcher,
+                newly_visible,
+            )
+            .await?;
+            publish_audit(state, audit, recipients_after).await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn publish_audit(
+    state: &AppState,
+    audit: AuditLogRow,
+    recipients: &[Uuid],
+) {
+    publish_event(
+        state,
+        recipients,
+        ServerEvent::AuditAppend(AuditAppendEventPayload { audit }),
+    )
+    .await;
+}
+
+fn escalation_marker(rule: &EscalationRuleRow) -> Value {
+    json!({
+        "rule_id": rule.id,
+        "rule_name": rule.name,
+        "action": rule.action,
+    })
+}
+
+fn raised_priority(priority: &str) -> Option<&'static str> {
+    match priority {
+        "low" => Some("medium"),
+        "medium" => Some("high"),
+        _ => None,
+    }
+}
+
+async fn fetch_rule<'e, E>(
+    executor: E,
+    workspace: &str,
+    id: Uuid,
+) -> Result<EscalationRuleRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, EscalationRuleRow>(&format!(
+        "{RULE_SELECT_SQL}
+         WHERE rule.id = $1 AND rule.workspace_domain = $2"
+    ))
+    .bind(id)
+    .bind(workspace)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("escalation rule not found".to_string()))
+}
+
+async fn resolve_target<'e, E>(
+    executor: E,
+    workspace: &str,
+    email: Option<&str>,
+) -> Result<Option<Uuid>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let Some(email) = email else {
+        return Ok(None);
+    };
+
+    sqlx::query_scalar(
+        "SELECT id
+         FROM app.app_users
+         WHERE lower(email) = lower($1)
+           AND lower(btrim(split_part(email, '@', 2))) = $2
+           AND is_active
+           AND deleted_at IS NULL
+         LIMIT 1",
+    )
+    .bind(email)
+    .bind(workspace)
+    .fetch_optional(executor)
+    .await?
+    .map(Some)
+    .ok_or_else(|| {
+        AppError::Validation(vec![ErrorDetail {
+            field: "target_email".to_string(),
+            message: "No user in this workspace has this email address"
+                .to_string(),
+        }])
+    })
+}
+
+// Rules need an idle time or an SLA state, or they would fire on every
+// request as soon as it is created.
+fn validate_rule(mut fields: RuleFields) -> Result<RuleFields, AppError> {
+    let mut details = Vec::new();
+    let mut invalid = |field: &str, message: String| {
+        details.push(ErrorDetail {
+            field: field.to_string(),
+            message,
+        });
+    };
+
+    fields.name = fields.name.trim().to_string();
+    if fields.name.is_empty() || fields.name.chars().count() > 120 {
+        invalid("name", "name must be 1-120 characters".to_string());
+    }
+    if let Some(priority) = &fields.priority
+        && validate_priority(priority).is_err()
+    {
+        invalid(
+            "priority",
+            "priority must be one of low, medium, high".to_string(),
+        );
+    }
+    if let Some(category) = &fields.category
+        && validate_category(category).is_err()
+    {
+        invalid(
+            "category",
+            "category must be one of IT, Ops, Admin, HR".to_string(),
+        );
+    }
+    if let Some(status) = &fields.status
+        && !matches!(status.as_str(), "open" | "in_progress")
+    {
+        invalid(
+            "status",
+            "status must be one of open, in_progress".to_string(),
+        );
+    }
+    if let Some(minutes) = fields.idle_minutes
+        && !(1..=MAX_IDLE_MINUTES).contains(&minutes)
+    {
+        invalid(
+            "idle_minutes",
+            format!("idle_minutes must be between 1 and {MAX_IDLE_MINUTES}"),
+        );
+    }
+    if let Some(state) = &fields.sla_state
+        && SlaFilter::parse(state).is_none()
+    {
+        invalid(
+            "sla_state",
+            format!("sla_state must be one of {}", SlaFilter::VALUES),
+        );
+    }
+    if fields.idle_minutes.is_none() && fields.sla_state.is_none() {
+        invalid(
+            "idle_minutes",
+            "a rule needs idle_minutes or sla_state".to_string(),
+        );
+    }
+
+    fields.target_email = fields
+        .target_email
+        .map(|email| email.trim().to_lowercase())
+        .filter(|email| !email.is_empty());
+    match EscalationAction::parse(&fields.action) {
+        None => invalid(
+            "action",
+            format!("action must be one of {}", EscalationAction::VALUES),
+        ),
+        Some(action) if action.needs_target() => match &fields.target_email {
+            None => invalid(
+                "target_email",
+                format!("target_email is required for {}", fields.action),
+            ),
+            Some(email) if !is_valid_email(email) => invalid(
+                "target_email",
+                "target_email must be a valid email address".to_string(),
+            ),
+            Some(_) => {}
+        },
+        Some(_) => fields.target_email = None,
+    }
+
+    if details.is_empty() {
+        Ok(fields)
+    } else {
+        Err(AppError::Validation(details))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> RuleFields {
+        RuleFields {
+            name: " Stale high priority ".to_string(),
+            active: true,
+            priority: Some("high".to_string()),
+            category: None,
+            status: None,
+            idle_minutes: Some(240),
+            sla_state: None,
+            action: "reassign".to_string(),
+            target_email: Some(" Lead@Example.com ".to_string()),
+        }
+    }
+
+    #[test]
+    fn validate_rule_normalizes_name_and_target() {
+        let fields = validate_rule(fields()).expect("rule should be valid");
+
+        assert_eq!(fields.name, "Stale high priority");
+        assert_eq!(fields.target_email.as_deref(), Some("lead@example.com"));
+
+        let raise = validate_rule(RuleFields {
+            action: "raise_priority".to_string(),
+            ..fields
+        })
+        .expect("rule should be valid");
+        assert_eq!(raise.target_email, None);
+    }
+
+    #[test]
+    fn validate_rule_collects_every_invalid_field() {
+        let err = validate_rule(RuleFields {
+            status: Some("resolved".to_string()),
+            idle_minutes: None,
+            sla_state: Some("late".to_string()),
+            action: "notify".to_string(),
+            target_email: None,
+            ..fields()
+        })
+        .expect_err("rule should fail");
+
+        let AppError::Validation(details) = err else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> =
+            details.iter().map(|detail| detail.field.as_str()).collect();
+        assert_eq!(fields, ["status", "sla_state", "target_email"]);
+    }
+
+    #[test]
+    fn rules_need_a_trigger() {
+        let err = validate_rule(RuleFields {
+            idle_minutes: None,
+            ..fields()
+        })
+        .expect_err("rule should fail");
+
+        let AppError::Validation(details) = err else {
+            panic!("expected validation error");
+        };
+        assert_eq!(details[0].field, "idle_minutes");
+    }
+
+    #[test]
+    fn priorities_are_raised_one_step() {
+        assert_eq!(raised_priority("low"), Some("medium"));
+        assert_eq!(raised_priority("medium"), Some("high"));
+        assert_eq!(raised_priority("high"), None);
+    }
+}
@@ -0,0 +1,498 @@
+use axum::{http::StatusCode, response::IntoResponse};
+use chrono::{DateTime, Utc};
+use schemars::{JsonSchema, schema_for};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use uuid::Uuid;
+
+use super::{
+    AuditLogRow, RequestRow,
+    checklists::{ChecklistItemRow, ChecklistProgress},
+    comments::CommentRow,
+    links::RequestLinkRow,
+    mentions::MentionRow,
+    notifications::NotificationRow,
+    sla::SlaTarget,
+    watchers::WatcherRow,
+};
+use crate::{
+    error::{AppError, ErrorDetail},
+    realtime::{
+        self, EventEnvelope, OutboundEvent, RevocationReason, WireCompression,
+        WireEncoding,
+    },
+    response,
+};
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(tag = "type", content = "payload")]
+pub(super) enum ServerEvent {
+    #[serde(rename = "request.created")]
+    RequestCreated(RequestCreatedEventPayload),
+    #[serde(rename = "request.patch")]
+    RequestPatch(RequestPatchEventPayload),
+    #[serde(rename = "request.deleted")]
+    RequestDeleted(RequestDeletedEventPayload),
+    #[serde(rename = "audit.append")]
+    AuditAppend(AuditAppendEventPayload),
+    #[serde(rename = "request.link_added")]
+    RequestLinkAdded(RequestLinkEventPayload),
+    #[serde(rename = "request.link_removed")]
+    RequestLinkRemoved(RequestLinkEventPayload),
+    #[serde(rename = "request.watcher_added")]
+    RequestWatcherAdded(RequestWatcherEventPayload),
+    #[serde(rename = "request.watcher_removed")]
+    RequestWatcherRemoved(RequestWatcherEventPayload),
+    #[serde(rename = "comment.created")]
+    CommentCreated(CommentEventPayload),
+    #[serde(rename = "comment.deleted")]
+    CommentDeleted(CommentEventPayload),
+    #[serde(rename = "mention.created")]
+    MentionCreated(MentionEventPayload),
+    #[serde(rename = "notification.created")]
+    NotificationCreated(NotificationEventPayload),
+    #[serde(rename = "notification.read")]
+    NotificationsRead(NotificationsReadEventPayload),
+    #[serde(rename = "checklist.item_added")]
+    ChecklistItemAdded(ChecklistItemEventPayload),
+    #[serde(rename = "checklist.item_updated")]
+    ChecklistItemUpdated(ChecklistItemEventPayload),
+    #[serde(rename = "checklist.item_removed")]
+    ChecklistItemRemoved(ChecklistItemEventPayload),
+    #[serde(rename = "checklist.reordered")]
+    ChecklistReordered(ChecklistReorderedEventPayload),
+    #[serde(rename = "sla.warning")]
+    SlaWarning(SlaEventPayload),
+    #[serde(rename = "sla.breached")]
+    SlaBreached(SlaEventPayload),
+    #[serde(rename = "profile.patch")]
+    ProfilePatch(ProfilePatchEventPayload),
+    #[serde(rename = "sync.required")]
+    SyncRequired {},
+    #[serde(rename = "session.revoked")]
+    SessionRevoked(SessionRevokedEventPayload),
+    #[serde(rename = "hello.accepted")]
+    HelloAccepted(HelloAcceptedEventPayload),
+    #[serde(rename = "ack")]
+    Ack(AckEventPayload),
+    #[serde(rename = "nack")]
+    Nack(NackEventPayload),
+    #[serde(rename = "request.batch")]
+    RequestBatch(RequestBatchEventPayload),
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct RequestCreatedEventPayload {
+    pub(super) request: RequestRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct RequestPatchEventPayload {
+    pub(super) request: RequestRow,
+    pub(super) changed_fields: Vec<String>,
+    pub(super) previous_status: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct RequestPatchDiffPayload {
+    id: Uuid,
+    changes: Map<String, Value>,
+    previous_status: String,
+}
+
+impl RequestPatchEventPayload {
+    fn diff(&self) -> Result<RequestPatchDiffPayload, serde_json::Error> {
+        let request = serde_json::to_value(&self.request)?;
+        let changes = self
+            .changed_fields
+            .iter()
+            .filter_map(|field| {
+                Some((field.clone(), request.get(field)?.clone()))
+            })
+            .collect();
+
+        Ok(RequestPatchDiffPayload {
+            id: self.request.id,
+            changes,
+            previous_status: self.previous_status.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct RequestDeletedEventPayload {
+    pub(super) id: Uuid,
+    pub(super) status: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct RequestBatchEventPayload {
+    pub(super) events: Vec<ServerEvent>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct AuditAppendEventPayload {
+    pub(super) audit: AuditLogRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct RequestLinkEventPayload {
+    pub(super) link: RequestLinkRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct RequestWatcherEventPayload {
+    pub(super) request_id: Uuid,
+    pub(super) watcher: WatcherRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct CommentEventPayload {
+    pub(super) comment: CommentRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct MentionEventPayload {
+    pub(super) mention: MentionRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct NotificationEventPayload {
+    pub(super) notification: NotificationRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct NotificationsReadEventPayload {
+    pub(super) notification_ids: Vec<Uuid>,
+    pub(super) unread_count: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct ChecklistItemEventPayload {
+    pub(super) item: ChecklistItemRow,
+    pub(super) progress: ChecklistProgress,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct ChecklistReorderedEventPayload {
+    pub(super) request_id: Uuid,
+    pub(super) item_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct SlaEventPayload {
+    pub(super) request_id: Uuid,
+    pub(super) target: SlaTarget,
+    pub(super) due_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct ProfilePatchEventPayload {
+    pub(super) user: ProfileSnapshot,
+    pub(super) changed_fields: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct ProfileSnapshot {
+    pub(super) id: Uuid,
+    pub(super) email: String,
+    pub(super) display_name: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct SessionRevokedEventPayload {
+    pub(super) reason: RevocationReason,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct HelloAcceptedEventPayload {
+    pub(super) protocol_version: u8,
+    pub(super) encoding: WireEncoding,
+    pub(super) compression: WireCompression,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct AckEventPayload {
+    pub(super) op_id: String,
+    pub(super) request: RequestRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct NackEventPayload {
+    pub(super) op_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) id: Option<Uuid>,
+    pub(super) error: NackError,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct NackError {
+    code: String,
+    message: String,
+    details: Option<Vec<ErrorDetail>>,
+}
+
+impl From<&AppError> for NackError {
+    fn from(error: &AppError) -> Self {
+        Self {
+            code: error.code().to_string(),
+            message: error.message(),
+            details: error.details(),
+        }
+    }
+}
+
+impl ServerEvent {
+    fn request_id(&self) -> Option<Uuid> {
+        match self {
+            Self::RequestCreated(payload) => Some(payload.request.id),
+            Self::RequestPatch(payload) => Some(payload.request.id),
+            Self::RequestDeleted(payload) => Some(payload.id),
+            Self::AuditAppend(payload) => Some(payload.audit.request_id),
+            Self::RequestLinkAdded(payload)
+            | Self::RequestLinkRemoved(payload) => {
+                Some(payload.link.source_request_id)
+            }
+            Self::RequestWatcherAdded(payload)
+            | Self::RequestWatcherRemoved(payload) => Some(payload.request_id),
+            Self::CommentCreated(payload) | Self::CommentDeleted(payload) => {
+                Some(payload.comment.request_id)
+            }
+            Self::MentionCreated(payload) => Some(payload.mention.request_id),
+            Self::NotificationCreated(payload) => {
+                Some(payload.notification.request_id)
+            }
+            Self::ChecklistItemAdded(payload)
+            | Self::ChecklistItemUpdated(payload)
+            | Self::ChecklistItemRemoved(payload) => {
+                Some(payload.item.request_id)
+            }
+            Self::ChecklistReordered(payload) => Some(payload.request_id),
+            Self::SlaWarning(payload) | Self::SlaBreached(payload) => {
+                Some(payload.request_id)
+            }
+            Self::Ack(payload) => Some(payload.request.id),
+            Self::Nack(payload) => payload.id,
+            Self::RequestBatch(_)
+            | Self::NotificationsRead(_)
+            | Self::ProfilePatch(_)
+            | Self::SyncRequired {}
+            | Self::SessionRevoked(_)
+            | Self::HelloAccepted(_) => None,
+        }
+    }
+
+    pub(super) fn is_webhook_event(&self) -> bool {
+        matches!(
+            self,
+            Self::RequestCreated(_)
+                | Self::RequestPatch(_)
+                | Self::RequestDeleted(_)
+                | Self::CommentCreated(_)
+                | Self::CommentDeleted(_)
+                | Self::SlaWarning(_)
+                | Self::SlaBreached(_)
+        )
+    }
+
+    pub(super) fn to_outbound(
+        &self,
+        v1: EventEnvelope,
+    ) -> Result<OutboundEvent, serde_json::Error> {
+        let mut v2 = v1.clone();
+        v2.v = 2;
+        if let Some(payload) = self.v2_payload()? {
+            v2.payload = payload;
+        }
+
+        OutboundEvent::new(v1)?.with_version(v2)
+    }
+
+    fn v2_payload(&self) -> Result<Option<Value>, serde_json::Error> {
+        match self {
+            Self::RequestPatch(payload) => {
+                serde_json::to_value(payload.diff()?).map(Some)
+            }
+            Self::RequestBatch(batch) => {
+                let events = batch
+                    .events
+                    .iter()
+                    .map(|event| match event {
+                        Self::RequestPatch(payload) => Ok(json!({
+                            "type": "request.patch",
+                            "payload": payload.diff()?,
+                        })),
+                        other => serde_json::to_value(other),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(json!({ "events": events })))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub(super) fn to_envelope(
+        &self,
+        trace_id: Option<String>,
+    ) -> Result<EventEnvelope, serde_json::Error> {
+        let request_id = self.request_id();
+        let mut tagged = serde_json::to_value(self)?;
+        let event_type = tagged
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let payload = tagged
+            .get_mut("payload")
+            .map_or_else(|| json!({}), Value::take);
+
+        Ok(EventEnvelope::new(
+            event_type, request_id, trace_id, payload,
+        ))
+    }
+}
+
+pub(super) async fn get_realtime_schema() -> impl IntoResponse {
+    response::ok(
+        StatusCode::OK,
+        json!({
+            "protocol_version": realtime::LATEST_PROTOCOL_VERSION,
+            "supported_versions": realtime::SUPPORTED_PROTOCOL_VERSIONS,
+            "envelope": schema_for!(EventEnvelope),
+            "events": schema_for!(ServerEvent),
+            "version_overrides": {
+                "2": {
+                    "request.patch": schema_for!(RequestPatchDiffPayload),
+                },
+            },
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_event_envelope_carries_type_payload_and_request_id() {
+        let id = Uuid::new_v4();
+        let envelope =
+            ServerEvent::RequestDeleted(RequestDeletedEventPayload {
+                id,
+                status: "closed".to_string(),
+            })
+            .to_envelope(Some("trace-1".to_string()))
+            .expect("event should serialize");
+
+        assert_eq!(envelope.v, realtime::PROTOCOL_VERSION_V1);
+        assert_eq!(envelope.event_type, "request.deleted");
+        assert_eq!(envelope.request_id, Some(id));
+        assert_eq!(envelope.payload["status"], "closed");
+
+        let sync = ServerEvent::SyncRequired {}
+            .to_envelope(None)
+            .expect("event should serialize");
+        assert_eq!(sync.event_type, "sync.required");
+        assert_eq!(sync.payload, json!({}));
+    }
+
+    fn request(status: &str) -> RequestRow {
+        RequestRow {
+            id: Uuid::new_v4(),
+            owner_user_id: Uuid::nil(),
+            title: "Laptop".to_string(),
+            description: None,
+            category: "IT".to_string(),
+            status: status.to_string(),
+            priority: "high".to_string(),
+            assignee_user_id: None,
+            assignee_email: None,
+            assignee_display_name: None,
+            checklist_total: 0,
+            checklist_done: 0,
+            checklist_required: false,
+            sla_first_response_due_at: None,
+            sla_resolution_due_at: None,
+            sla_remaining_seconds: None,
+            sla_first_response_breached: false,
+            sla_resolution_breached: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn versions(event: &ServerEvent) -> (Value, Value) {
+        let envelope = event.to_envelope(None).expect("event should serialize");
+        let outbound =
+            event.to_outbound(envelope).expect("event should encode");
+        let parse = |version| {
+            serde_json::from_str::<Value>(outbound.json(version))
+                .expect("event should be json")
+        };
+        (parse(1), parse(2))
+    }
+
+    #[test]
+    fn v2_request_patch_only_carries_changed_fields() {
+        let request = request("in_progress");
+        let id = request.id;
+        let event = ServerEvent::RequestPatch(RequestPatchEventPayload {
+            request,
+            changed_fields: vec!["status".to_string(), "missing".to_string()],
+            previous_status: "open".to_string(),
+        });
+
+        let (v1, v2) = versions(&event);
+        assert_eq!(v1["v"], 1);
+        assert_eq!(v1["payload"]["request"]["title"], "Laptop");
+        assert_eq!(v2["v"], 2);
+        assert_eq!(v2["ts"], v1["ts"]);
+        assert_eq!(v2["payload"]["id"], json!(id));
+        assert_eq!(
+            v2["payload"]["changes"],
+            json!({ "status": "in_progress" })
+        );
+        assert_eq!(v2["payload"]["previous_status"], "open");
+    }
+
+    #[test]
+    fn v2_request_batch_rewrites_nested_patches() {
+        let request = request("resolved");
+        let id = request.id;
+        let event = ServerEvent::RequestBatch(RequestBatchEventPayload {
+            events: vec![
+                ServerEvent::RequestPatch(RequestPatchEventPayload {
+                    request,
+                    changed_fields: vec!["status".to_string()],
+                    previous_status: "open".to_string(),
+                }),
+                ServerEvent::RequestDeleted(RequestDeletedEventPayload {
+                    id,
+                    status: "resolved".to_string(),
+                }),
+            ],
+        });
+
+        let (v1, v2) = versions(&event);
+        let events = &v2["payload"]["events"];
+        assert_eq!(
+            v1["payload"]["events"][0]["payload"]["request"]["id"],
+            json!(id)
+        );
+        assert_eq!(events[0]["type"], "request.patch");
+        assert_eq!(
+            events[0]["payload"]["changes"],
+            json!({ "status": "resolved" })
+        );
+        assert_eq!(events[1]["payload"]["status"], "resolved");
+    }
+
+    #[test]
+    fn other_events_only_bump_the_version() {
+        let event = ServerEvent::SyncRequired {};
+
+        let (v1, v2) = versions(&event);
+        assert_eq!(v2["v"], 2);
+        assert_eq!(v2["payload"], v1["payload"]);
+    }
+}
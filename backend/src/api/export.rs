@@ -0,0 +1,452 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{
+        HeaderMap, HeaderValue,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::{StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+use tokio::sync::mpsc;
+use tower_sessions::Session;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::filters::RequestListFilter;
+use super::pagination::RequestSort;
+use super::{
+    AuditLogRow, ListRequestsQuery, RequestRow, audit_log_projection_sql,
+    fetch_visible_request, request_projection_sql, require_authenticated_user,
+    views,
+};
+use crate::{
+    AppState,
+    error::{AppError, ErrorDetail},
+};
+
+const ROWS_PER_CHUNK: usize = 200;
+const CHUNK_BUFFER: usize = 4;
+
+const REQUEST_COLUMNS: &[&str] = &[
+    "id",
+    "owner_user_id",
+    "title",
+    "description",
+    "category",
+    "status",
+    "priority",
+    "assignee_user_id",
+    "assignee_email",
+    "assignee_display_name",
+    "checklist_total",
+    "checklist_done",
+    "checklist_required",
+    "created_at",
+    "updated_at",
+    "resolved_at",
+    "due_at",
+    "sla_first_response_due_at",
+    "sla_resolution_due_at",
+    "sla_remaining_seconds",
+    "sla_first_response_breached",
+    "sla_resolution_breached",
+];
+
+const AUDIT_COLUMNS: &[&str] = &[
+    "id",
+    "request_id",
+    "actor_user_id",
+    "actor_email",
+    "action",
+    "old_value",
+    "new_value",
+    "created_at",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Csv,
+    JsonLines,
+}
+
+impl ExportFormat {
+    fn parse(value: Option<&str>) -> Result<Self, AppError> {
+        match value.map(str::trim).unwrap_or("csv") {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::JsonLines),
+            _ => Err(AppError::Validation(vec![ErrorDetail {
+                field: "format".to_string(),
+                message: "format must be one of csv or jsonl".to_string(),
+            }])),
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::JsonLines => "application/x-ndjson",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::JsonLines => "jsonl",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ExportQuery {
+    format: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+struct RequestExportRow {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    request: RequestRow,
+    resolved_at: Option<DateTime<Utc>>,
+    due_at: Option<DateTime<Utc>>,
+}
+
+trait CsvRecord {
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+impl CsvRecord for RequestExportRow {
+    fn csv_fields(&self) -> Vec<String> {
+        let request = &self.request;
+        vec![
+            request.id.to_string(),
+            request.owner_user_id.to_string(),
+            request.title.clone(),
+            request.description.clone().unwrap_or_default(),
+            request.category.clone(),
+            request.status.clone(),
+            request.priority.clone(),
+            optional(request.assignee_user_id),
+            request.assignee_email.clone().unwrap_or_default(),
+            request.assignee_display_name.clone().unwrap_or_default(),
+            request.checklist_total.to_string(),
+            request.checklist_done.to_string(),
+            request.checklist_required.to_string(),
+            timestamp(request.created_at),
+            timestamp(request.updated_at),
+            self.resolved_at.map(timestamp).unwrap_or_default(),
+            self.due_at.map(timestamp).unwrap_or_default(),
+            request
+                .sla_first_response_due_at
+                .map(timestamp)
+                .unwrap_or_default(),
+            request
+                .sla_resolution_due_at
+                .map(timestamp)
+                .unwrap_or_default(),
+            request
+                .sla_remaining_seconds
+                .map(|seconds| seconds.to_string())
+                .unwrap_or_default(),
+            request.sla_first_response_breached.to_string(),
+            request.sla_resolution_breached.to_string(),
+        ]
+    }
+}
+
+impl CsvRecord for AuditLogRow {
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.request_id.to_string(),
+            self.actor_user_id.to_string(),
+            self.actor_email.clone(),
+            self.action.clone(),
+            self.old_value.to_string(),
+            self.new_value.to_string(),
+            timestamp(self.created_at),
+        ]
+    }
+}
+
+pub(super) async fn export_requests(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Query(export): Query<ExportQuery>,
+    Query(query): Query<ListRequestsQuery>,
+) -> Result<Response, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let format = ExportFormat::parse(export.format.as_deref())?;
+
+    let query = views::apply_view(&state.db, &user, query).await?;
+    let filter = RequestListFilter::from_query(&query)?;
+    let sort =
+        RequestSort::parse(query.sort.as_deref(), filter.is_searching())?;
+
+    let mut builder = QueryBuilder::<Postgres>::new("SELECT ");
+    builder
+        .push(request_projection_sql())
+        .push(", req.resolved_at, req.due_at");
+    filter.push_from_where(&mut builder, user.id);
+    sort.push_order_by(&mut builder);
+
+    let filename = format!(
+        "requests-{}.{}",
+        Utc::now().format("%Y%m%d"),
+        format.extension()
+    );
+    Ok(stream_rows::<RequestExportRow>(
+        state.db.clone(),
+        builder,
+        format,
+        REQUEST_COLUMNS,
+        &filename,
+    ))
+}
+
+pub(super) async fn export_request_audit(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(export): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let format = ExportFormat::parse(export.format.as_deref())?;
+    fetch_visible_request(&state.db, id, user.id).await?;
+
+    let mut builder = QueryBuilder::<Postgres>::new("SELECT ");
+    builder
+        .push(audit_log_projection_sql())
+        .push(
+            " FROM app.request_audit_logs logs
+             LEFT JOIN app.app_users actor ON actor.id = logs.actor_user_id
+             WHERE logs.request_id = ",
+        )
+        .push_bind(id)
+        .push(" ORDER BY logs.created_at ASC, logs.id ASC");
+
+    let filename = format!("request-{id}-audit.{}", format.extension());
+    Ok(stream_rows::<AuditLogRow>(
+        state.db.clone(),
+        builder,
+        format,
+        AUDIT_COLUMNS,
+        &filename,
+    ))
+}
+
+// A database error after the headers were sent can only abort the body,
+// so it is logged and the connection is cut.
+fn stream_rows<T>(
+    pool: PgPool,
+    mut builder: QueryBuilder<'static, Postgres>,
+    format: ExportFormat,
+    columns: &'static [&'static str],
+    filename: &str,
+) -> Response
+where
+    T: for<'r> FromRow<'r, sqlx::postgres::PgRow>
+        + CsvRecord
+        + Serialize
+        + Send
+        + Unpin
+        + 'static,
+{
+    let (sender, receiver) =
+        mpsc::channel::<Result<String, std::io::Error>>(CHUNK_BUFFER);
+
+    tokio::spawn(async move {
+        let mut chunk = String::new();
+        if format == ExportFormat::Csv {
+            write_csv_record(&mut chunk, columns.iter().copied());
+        }
+
+        let mut rows = builder.build_query_as::<T>().fetch(&pool);
+        let mut buffered = 0;
+        while let Some(row) = rows.next().await {
+            let row = match row {
+                Ok(row) => row,
+                Err(err) => {
+                    warn!(error = %err, "export query failed mid-stream");
+                    let _ = sender.send(Err(std::io::Error::other(err))).await;
+                    return;
+                }
+            };
+
+            match format {
+                ExportFormat::Csv => {
+                    let fields = row.csv_fields();
+                    write_csv_record(
+                        &mut chunk,
+                        fields.iter().map(String::as_str),
+                    );
+                }
+                ExportFormat::JsonLines => {
+                    let line = serde_json::to_string(&row)
+                        .expect("export rows always serialize");
+                    chunk.push_str(&line);
+                    chunk.push('\n');
+                }
+            }
+
+            buffered += 1;
+            if buffered == ROWS_PER_CHUNK {
+                buffered = 0;
+                if sender.send(Ok(std::mem::take(&mut chunk))).await.is_err() {
+                    return;
+                }
+            }
+        }
+
+        if !chunk.is_empty() {
+            let _ = sender.send(Ok(chunk)).await;
+        }
+    });
+
+    let body = stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    });
+
+    let disposition =
+        HeaderValue::from_str(&format!("attachment; filename=\"{filename}\""))
+            .expect("export filenames are ASCII");
+    (
+        [
+            (
+                CONTENT_TYPE,
+                HeaderValue::from_static(format.content_type()),
+            ),
+            (CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+// Fields a spreadsheet would evaluate as a formula are prefixed with `'`.
+fn write_csv_record<'a>(
+    out: &mut String,
+    fields: impl Iterator<Item = &'a str>,
+) {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+
+        let guard = field.starts_with(['=', '+', '-', '@', '\t', '\r']);
+        let needs_quotes = guard
+            || field.contains([',', '"', '\n', '\r'])
+            || field.starts_with(' ')
+            || field.ends_with(' ');
+        if !needs_quotes {
+            out.push_str(field);
+            continue;
+        }
+
+        out.push('"');
+        if guard {
+            out.push('\'');
+        }
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    }
+    out.push_str("\r\n");
+}
+
+fn optional(value: Option<Uuid>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[&str]) -> String {
+        let mut out = String::new();
+        write_csv_record(&mut out, fields.iter().copied());
+        out
+    }
+
+    #[test]
+    fn csv_record_quotes_only_when_needed() {
+        assert_eq!(record(&["plain", "", "IT"]), "plain,,IT\r\n");
+        assert_eq!(
+            record(&["a,b", "say \"hi\"", "two\nlines"]),
+            "\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+    }
+
+    #[test]
+    fn csv_record_neutralizes_formulas() {
+        assert_eq!(
+            record(&["=HYPERLINK(\"x\")", "@sum", "ok"]),
+            "\"'=HYPERLINK(\"\"x\"\")\",\"'@sum\",ok\r\n"
+        );
+    }
+
+    #[test]
+    fn export_format_defaults_to_csv_and_rejects_unknown() {
+        assert_eq!(
+            ExportFormat::parse(None).expect("default should parse"),
+            ExportFormat::Csv
+        );
+        assert_eq!(
+            ExportFormat::parse(Some("jsonl")).expect("jsonl should parse"),
+            ExportFormat::JsonLines
+        );
+        assert!(ExportFormat::parse(Some("xlsx")).is_err());
+    }
+
+    #[test]
+    fn request_columns_match_csv_fields() {
+        let row = RequestExportRow {
+            request: RequestRow {
+                id: Uuid::nil(),
+                owner_user_id: Uuid::nil(),
+                title: "Title".to_string(),
+                description: None,
+                category: "IT".to_string(),
+                status: "open".to_string(),
+                priority: "low".to_string(),
+                assignee_user_id: None,
+                assignee_email: None,
+                assignee_display_name: None,
+                checklist_total: 0,
+                checklist_done: 0,
+                checklist_required: false,
+                sla_first_response_due_at: None,
+                sla_resolution_due_at: None,
+                sla_remaining_seconds: None,
+                sla_first_response_breached: false,
+                sla_resolution_breached: false,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+            resolved_at: None,
+            due_at: None,
+        };
+
+        assert_eq!(row.csv_fields().len(), REQUEST_COLUMNS.len());
+        let json = serde_json::to_value(&row).expect("row should serialize");
+        let mut keys: Vec<_> = json
+            .as_object()
+            .expect("row should be an object")
+            .keys()
+            .cloned()
+            .collect();
+        let mut columns: Vec<_> =
+            REQUEST_COLUMNS.iter().map(ToString::to_string).collect();
+        columns.sort();
+        keys.sort();
+        assert_eq!(keys, columns);
+    }
+}
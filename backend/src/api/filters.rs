@@ -0,0 +1,443 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use super::{
+    ListRequestsQuery, validate_category, validate_priority, validate_status,
+};
+use crate::error::{AppError, ErrorDetail};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PersonFilter {
+    Me,
+    Nobody,
+    Email(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SlaFilter {
+    Breached,
+    AtRisk,
+    OnTrack,
+}
+
+const SLA_BREACHED_SQL: &str = "(
+  COALESCE(sla.first_responded_at, NOW()) > sla.first_response_due_at
+  OR COALESCE(req.resolved_at, NOW()) > sla.resolution_due_at
+)";
+
+const SLA_AT_RISK_SQL: &str = "(
+  req.status <> 'resolved'
+  AND (
+    (sla.first_responded_at IS NULL AND sla.first_response_warn_at <= NOW())
+    OR sla.resolution_warn_at <= NOW()
+  )
+)";
+
+impl SlaFilter {
+    pub(super) const VALUES: &str = "breached, at_risk, on_track";
+
+    pub(super) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "breached" => Some(Self::Breached),
+            "at_risk" => Some(Self::AtRisk),
+            "on_track" => Some(Self::OnTrack),
+            _ => None,
+        }
+    }
+
+    pub(super) fn condition(self) -> String {
+        match self {
+            Self::Breached => SLA_BREACHED_SQL.to_string(),
+            Self::AtRisk => {
+                format!("(NOT {SLA_BREACHED_SQL} AND {SLA_AT_RISK_SQL})")
+            }
+            Self::OnTrack => format!(
+                "(sla.request_id IS NOT NULL
+                  AND NOT {SLA_BREACHED_SQL}
+                  AND NOT {SLA_AT_RISK_SQL})"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct DateRange {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct RequestListFilter {
+    statuses: Vec<String>,
+    categories: Vec<String>,
+    priorities: Vec<String>,
+    assignee: Option<PersonFilter>,
+    owner: Option<PersonFilter>,
+    created: DateRange,
+    updated: DateRange,
+    resolved: DateRange,
+    sla: Option<SlaFilter>,
+    search: Option<String>,
+}
+
+impl RequestListFilter {
+    pub(super) fn from_query(
+        query: &ListRequestsQuery,
+    ) -> Result<Self, AppError> {
+        let mut errors = Vec::new();
+
+        let statuses =
+            parse_list(query.status.as_deref(), validate_status, &mut errors);
+        let categories = parse_list(
+            query.category.as_deref(),
+            validate_category,
+            &mut errors,
+        );
+        let priorities = parse_list(
+            query.priority.as_deref(),
+            validate_priority,
+            &mut errors,
+        );
+
+        let assignee =
+            parse_person("assignee", query.assignee.as_deref(), true)
+                .map_err(|detail| errors.push(detail))
+                .ok()
+                .flatten();
+        let owner = parse_person("owner", query.owner.as_deref(), false)
+            .map_err(|detail| errors.push(detail))
+            .ok()
+            .flatten();
+
+        let mut range = |field_from: &str,
+                         from: &Option<String>,
+                         field_to: &str,
+                         to: &Option<String>| {
+            let from = parse_timestamp(field_from, from.as_deref())
+                .map_err(|detail| errors.push(detail))
+                .ok()
+                .flatten();
+            let to = parse_timestamp(field_to, to.as_deref())
+                .map_err(|detail| errors.push(detail))
+                .ok()
+                .flatten();
+            if let (Some(from), Some(to)) = (from, to)
+                && from >= to
+            {
+                errors.push(ErrorDetail {
+                    field: field_to.to_string(),
+                    message: format!("{field_to} must be after {field_from}"),
+                });
+            }
+            DateRange { from, to }
+        };
+        let created = range(
+            "created_from",
+            &query.created_from,
+            "created_to",
+            &query.created_to,
+        );
+        let updated = range(
+            "updated_from",
+            &query.updated_from,
+            "updated_to",
+            &query.updated_to,
+        );
+        let resolved = range(
+            "resolved_from",
+            &query.resolved_from,
+            "resolved_to",
+            &query.resolved_to,
+        );
+
+        let sla = match query.sla.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(value) => {
+                let filter = SlaFilter::parse(value);
+                if filter.is_none() {
+                    errors.push(ErrorDetail {
+                        field: "sla".to_string(),
+                        message: format!(
+                            "sla must be one of {}",
+                            SlaFilter::VALUES
+                        ),
+                    });
+                }
+                filter
+            }
+        };
+
+        if !errors.is_empty() {
+            return Err(AppError::Validation(errors));
+        }
+
+        let search = query
+            .q
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string);
+
+        Ok(Self {
+            statuses,
+            categories,
+            priorities,
+            assignee,
+            owner,
+            created,
+            updated,
+            resolved,
+            sla,
+            search,
+        })
+    }
+
+    pub(super) fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    pub(super) fn push_from_where(
+        &self,
+        builder: &mut QueryBuilder<'_, Postgres>,
+        viewer_id: Uuid,
+    ) {
+        builder
+            .push(
+                " FROM app.request_participants participants
+                 JOIN app.requests req ON req.id = participants.request_id
+                 LEFT JOIN app.app_users assignee
+                   ON assignee.id = req.assignee_user_id
+                 LEFT JOIN app.request_slas sla ON sla.request_id = req.id
+                 CROSS JOIN LATERAL (
+                   SELECT
+                     websearch_to_tsquery('english', ",
+            )
+            .push_bind(self.search.clone())
+            .push(
+                "::text) AS query,
+                     req.search_vector
+                       || COALESCE(assignee.search_vector, ''::tsvector)
+                       AS document
+                 ) search
+                 WHERE participants.user_id = ",
+            )
+            .push_bind(viewer_id);
+
+        for (column, values) in [
+            ("req.status", &self.statuses),
+            ("req.category", &self.categories),
+            ("req.priority", &self.priorities),
+        ] {
+            if !values.is_empty() {
+                builder
+                    .push(format_args!(" AND {column} = ANY("))
+                    .push_bind(values.clone())
+                    .push(")");
+            }
+        }
+
+        if let Some(assignee) = &self.assignee {
+            push_person(builder, "req.assignee_user_id", assignee, viewer_id);
+        }
+        if let Some(owner) = &self.owner {
+            push_person(builder, "req.owner_user_id", owner, viewer_id);
+        }
+
+        for (column, range) in [
+            ("req.created_at", &self.created),
+            ("req.updated_at", &self.updated),
+            ("req.resolved_at", &self.resolved),
+        ] {
+            if let Some(from) = range.from {
+                builder
+                    .push(format_args!(" AND {column} >= "))
+                    .push_bind(from);
+            }
+            if let Some(to) = range.to {
+                builder.push(format_args!(" AND {column} < ")).push_bind(to);
+            }
+        }
+
+        if let Some(sla) = self.sla {
+            builder.push(format_args!(" AND {}", sla.condition()));
+        }
+
+        if self.search.is_some() {
+            builder.push(" AND search.document @@ search.query");
+        }
+    }
+}
+
+fn push_person(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    column: &str,
+    person: &PersonFilter,
+    viewer_id: Uuid,
+) {
+    match person {
+        PersonFilter::Me => {
+            builder
+                .push(format_args!(" AND {column} = "))
+                .push_bind(viewer_id);
+        }
+        PersonFilter::Nobody => {
+            builder.push(format_args!(" AND {column} IS NULL"));
+        }
+        PersonFilter::Email(email) => {
+            builder
+                .push(format_args!(
+                    " AND {column} IN (
+                       SELECT id FROM app.app_users
+                       WHERE deleted_at IS NULL AND lower(email) = lower("
+                ))
+                .push_bind(email.clone())
+                .push("))");
+        }
+    }
+}
+
+fn parse_list(
+    raw: Option<&str>,
+    validate: fn(&str) -> Result<(), AppError>,
+    errors: &mut Vec<ErrorDetail>,
+) -> Vec<String> {
+    let mut values: Vec<String> = Vec::new();
+    for value in raw.unwrap_or_default().split(',').map(str::trim) {
+        if value.is_empty() || values.iter().any(|seen| seen == value) {
+            continue;
+        }
+        match validate(value) {
+            Ok(()) => values.push(value.to_string()),
+            Err(AppError::Validation(details)) => {
+                errors.extend(details);
+                return Vec::new();
+            }
+            Err(_) => return Vec::new(),
+        }
+    }
+    values
+}
+
+fn parse_person(
+    field: &str,
+    raw: Option<&str>,
+    allow_nobody: bool,
+) -> Result<Option<PersonFilter>, ErrorDetail> {
+    let Some(value) = raw.map(str::trim).filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    match value {
+        "me" => Ok(Some(PersonFilter::Me)),
+        "none" if allow_nobody => Ok(Some(PersonFilter::Nobody)),
+        email if email.contains('@') => {
+            Ok(Some(PersonFilter::Email(email.to_string())))
+        }
+        _ => Err(ErrorDetail {
+            field: field.to_string(),
+            message: if allow_nobody {
+                format!("{field} must be me, none or an email address")
+            } else {
+                format!("{field} must be me or an email address")
+            },
+        }),
+    }
+}
+
+pub(super) fn parse_timestamp(
+    field: &str,
+    raw: Option<&str>,
+) -> Result<Option<DateTime<Utc>>, ErrorDetail> {
+    let Some(value) = raw.map(str::trim).filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(timestamp.with_timezone(&Utc)));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(Some(date.and_time(Default::default()).and_utc()));
+    }
+
+    Err(ErrorDetail {
+        field: field.to_string(),
+        message: format!("{field} must be an RFC 3339 timestamp or YYYY-MM-DD"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query() -> ListRequestsQuery {
+        ListRequestsQuery::default()
+    }
+
+    #[test]
+    fn filter_parses_multi_value_and_person_filters() {
+        let filter = RequestListFilter::from_query(&ListRequestsQuery {
+            status: Some("open, in_progress,open".to_string()),
+            assignee: Some("none".to_string()),
+            owner: Some("lead@example.com".to_string()),
+            created_from: Some("2026-03-01".to_string()),
+            ..query()
+        })
+        .expect("filter should parse");
+
+        assert_eq!(filter.statuses, vec!["open", "in_progress"]);
+        assert_eq!(filter.assignee, Some(PersonFilter::Nobody));
+        assert_eq!(
+            filter.owner,
+            Some(PersonFilter::Email("lead@example.com".to_string()))
+        );
+        assert_eq!(
+            filter.created.from.map(|value| value.to_rfc3339()),
+            Some("2026-03-01T00:00:00+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn filter_collects_every_invalid_field() {
+        let err = RequestListFilter::from_query(&ListRequestsQuery {
+            status: Some("open,bogus".to_string()),
+            owner: Some("none".to_string()),
+            updated_from: Some("2026-03-02".to_string()),
+            updated_to: Some("2026-03-01".to_string()),
+            resolved_to: Some("yesterday".to_string()),
+            sla: Some("late".to_string()),
+            ..query()
+        })
+        .expect_err("filter should fail");
+
+        let AppError::Validation(details) = err else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> =
+            details.iter().map(|detail| detail.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            ["status", "owner", "updated_to", "resolved_to", "sla"]
+        );
+    }
+
+    #[test]
+    fn filter_binds_values_instead_of_interpolating() {
+        let filter = RequestListFilter::from_query(&ListRequestsQuery {
+            owner: Some("x'); DROP TABLE app.requests; --@x".to_string()),
+            q: Some("printer".to_string()),
+            ..query()
+        })
+        .expect("filter should parse");
+
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT 1");
+        filter.push_from_where(&mut builder, Uuid::nil());
+        let sql = builder.sql();
+
+        assert!(!sql.contains("DROP TABLE"));
+        assert!(sql.contains("lower(email) = lower($3)"));
+        assert!(sql.contains("search.document @@ search.query"));
+    }
+}
@@ -0,0 +1,501 @@
+use std::collections::HashMap;
+
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header::CONTENT_TYPE},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::events::{
+    AuditAppendEventPayload, RequestCreatedEventPayload, ServerEvent,
+};
+use super::filters::parse_timestamp;
+use super::{
+    AuditLogRow, CreateRequestInput, RequestRow, create_request_record,
+    fetch_request_recipient_ids, normalize_assignee_email,
+    publish_event_batches, require_authenticated_user,
+    resolve_assignee_user_id, validate_create_input,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const IMPORT_MAX_ROWS: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    JsonLines,
+}
+
+impl ImportFormat {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value.trim() {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::JsonLines),
+            _ => Err(AppError::Validation(vec![ErrorDetail {
+                field: "format".to_string(),
+                message: "format must be one of csv or jsonl".to_string(),
+            }])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub field: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub valid_rows: usize,
+    pub imported: usize,
+    pub errors: Vec<ImportRowError>,
+    pub request_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ImportRow {
+    title: Option<String>,
+    description: Option<String>,
+    category: Option<String>,
+    priority: Option<String>,
+    assignee_email: Option<String>,
+    created_at: Option<String>,
+}
+
+pub async fn import_requests(
+    pool: &PgPool,
+    owner_id: Uuid,
+    format: ImportFormat,
+    data: &str,
+    dry_run: bool,
+) -> Result<ImportReport, AppError> {
+    let (report, _) = run_import(pool, owner_id, format, data, dry_run).await?;
+    Ok(report)
+}
+
+pub async fn resolve_import_owner(
+    pool: &PgPool,
+    email: &str,
+) -> Result<Uuid, AppError> {
+    sqlx::query_scalar(
+        "SELECT id
+         FROM app.app_users
+         WHERE lower(email) = lower($1) AND deleted_at IS NULL",
+    )
+    .bind(email.trim())
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!("no active user with email {email}"))
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ImportQuery {
+    format: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+pub(super) async fn import_requests_handler(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Query(query): Query<ImportQuery>,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let format = match query.format.as_deref() {
+        Some(format) => ImportFormat::parse(format)?,
+        None => format_from_content_type(&headers),
+    };
+    let data = std::str::from_utf8(&body).map_err(|_| {
+        AppError::Validation(vec![ErrorDetail {
+            field: "body".to_string(),
+            message: "import data must be UTF-8".to_string(),
+        }])
+    })?;
+
+    let (report, created) =
+        run_import(&state.db, user.id, format, data, query.dry_run).await?;
+
+    let mut by_user: HashMap<Uuid, Vec<ServerEvent>> = HashMap::new();
+    for (request, audit) in created {
+        for user_id in
+            fetch_request_recipient_ids(&state.db, request.id).await?
+        {
+            let events = by_user.entry(user_id).or_default();
+            events.push(ServerEvent::RequestCreated(
+                RequestCreatedEventPayload {
+                    request: request.clone(),
+                },
+            ));
+            events.push(ServerEvent::AuditAppend(AuditAppendEventPayload {
+                audit: audit.clone(),
+            }));
+        }
+    }
+    publish_event_batches(&state, by_user).await;
+
+    let status = if report.dry_run {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    Ok(response::ok(status, report))
+}
+
+fn format_from_content_type(headers: &HeaderMap) -> ImportFormat {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if content_type.starts_with("application/x-ndjson")
+        || content_type.starts_with("application/jsonl")
+    {
+        ImportFormat::JsonLines
+    } else {
+        ImportFormat::Csv
+    }
+}
+
+async fn run_import(
+    pool: &PgPool,
+    owner_id: Uuid,
+    format: ImportFormat,
+    data: &str,
+    dry_run: bool,
+) -> Result<(ImportReport, Vec<(RequestRow, AuditLogRow)>), AppError> {
+    let mut errors = Vec::new();
+    let rows = match format {
+        ImportFormat::Csv => parse_csv_rows(data, &mut errors),
+        ImportFormat::JsonLines => parse_jsonl_rows(data, &mut errors),
+    };
+    let total_rows = rows.len() + count_unparsed(&errors);
+
+    if total_rows == 0 {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "rows".to_string(),
+            message: "import contains no rows".to_string(),
+        }]));
+    }
+    if total_rows > IMPORT_MAX_ROWS {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "rows".to_string(),
+            message: format!("imports are limited to {IMPORT_MAX_ROWS} rows"),
+        }]));
+    }
+
+    let mut assignees: HashMap<String, Option<Uuid>> = HashMap::new();
+    let mut valid = Vec::with_capacity(rows.len());
+    for (row_number, row) in rows {
+        match validate_row(pool, row, &mut assignees).await? {
+            Ok(input) => valid.push(input),
+            Err(details) => {
+                errors.extend(details.into_iter().map(|detail| {
+                    ImportRowError {
+                        row: row_number,
+                        field: detail.field,
+                        message: detail.message,
+                    }
+                }));
+            }
+        }
+    }
+    errors.sort_by_key(|error| error.row);
+
+    let mut report = ImportReport {
+        dry_run,
+        total_rows,
+        valid_rows: valid.len(),
+        imported: 0,
+        errors,
+        request_ids: Vec::new(),
+    };
+    if dry_run {
+        return Ok((report, Vec::new()));
+    }
+    if !report.errors.is_empty() {
+        return Err(AppError::Validation(
+            report
+                .errors
+                .into_iter()
+                .map(|error| ErrorDetail {
+                    field: format!("rows[{}].{}", error.row, error.field),
+                    message: error.message,
+                })
+                .collect(),
+        ));
+    }
+
+    let mut created = Vec::with_capacity(valid.len());
+    let mut tx = pool.begin().await?;
+    for input in valid {
+        let mut marker = serde_json::Map::new();
+        marker.insert("imported".to_string(), json!(true));
+        let (record, audit) =
+            create_request_record(&mut tx, owner_id, input, Some(marker))
+                .await?;
+        report.request_ids.push(record.id);
+        created.push((record, audit));
+    }
+    tx.commit().await?;
+
+    report.imported = created.len();
+    Ok((report, created))
+}
+
+async fn validate_row(
+    pool: &PgPool,
+    row: ImportRow,
+    assignees: &mut HashMap<String, Option<Uuid>>,
+) -> Result<Result<CreateRequestInput, Vec<ErrorDetail>>, AppError> {
+    let mut details = Vec::new();
+
+    let created_at =
+        match parse_timestamp("created_at", row.created_at.as_deref()) {
+            Ok(Some(created_at)) if created_at > Utc::now() => {
+                details.push(ErrorDetail {
+                    field: "created_at".to_string(),
+                    message: "created_at must not be in the future".to_string(),
+                });
+                None
+            }
+            Ok(created_at) => created_at,
+            Err(detail) => {
+                details.push(detail);
+                None
+            }
+        };
+
+    let input = CreateRequestInput {
+        title: row.title.unwrap_or_default(),
+        description: row.description.filter(|value| !value.is_empty()),
+        category: row.category.unwrap_or_default(),
+        priority: row.priority.unwrap_or_default(),
+        assignee_email: row.assignee_email,
+        checklist: Vec::new(),
+        checklist_required: false,
+        created_at,
+    };
+    if let Err(AppError::Validation(errors)) = validate_create_input(&input) {
+        details.extend(errors);
+    }
+
+    if let Ok(Some(email)) =
+        normalize_assignee_email(input.assignee_email.as_deref())
+    {
+        let resolved = match assignees.get(&email) {
+            Some(resolved) => *resolved,
+            None => {
+                let resolved =
+                    match resolve_assignee_user_id(pool, Some(&email)).await {
+                        Ok(resolved) => resolved,
+                        Err(AppError::Validation(_)) => None,
+                        Err(err) => return Err(err),
+                    };
+                assignees.insert(email, resolved);
+                resolved
+            }
+        };
+        if resolved.is_none() {
+            details.push(ErrorDetail {
+                field: "assignee_email".to_string(),
+                message: "No user exists with this email address".to_string(),
+            });
+        }
+    }
+
+    if !details.is_empty() {
+        return Ok(Err(details));
+    }
+    Ok(Ok(input))
+}
+
+fn count_unparsed(errors: &[ImportRowError]) -> usize {
+    errors.iter().filter(|error| error.field == "row").count()
+}
+
+fn parse_jsonl_rows(
+    data: &str,
+    errors: &mut Vec<ImportRowError>,
+) -> Vec<(usize, ImportRow)> {
+    let mut rows = Vec::new();
+    let lines = data.lines().map(str::trim).filter(|line| !line.is_empty());
+    for (index, line) in lines.enumerate() {
+        match serde_json::from_str::<ImportRow>(line) {
+            Ok(row) => rows.push((index + 1, row)),
+            Err(err) => errors.push(ImportRowError {
+                row: index + 1,
+                field: "row".to_string(),
+                message: format!("row is not a valid JSON object: {err}"),
+            }),
+        }
+    }
+    rows
+}
+
+fn parse_csv_rows(
+    data: &str,
+    errors: &mut Vec<ImportRowError>,
+) -> Vec<(usize, ImportRow)> {
+    let records = match parse_csv(data) {
+        Ok(records) => records,
+        Err(message) => {
+            errors.push(ImportRowError {
+                row: 0,
+                field: "row".to_string(),
+                message,
+            });
+            return Vec::new();
+        }
+    };
+    let mut records = records.into_iter();
+    let Some(header) = records.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<String> = header
+        .iter()
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+
+    records
+        .enumerate()
+        .map(|(index, record)| {
+            let mut row = ImportRow::default();
+            for (column, value) in columns.iter().zip(record) {
+                let slot = match column.as_str() {
+                    "title" => &mut row.title,
+                    "description" => &mut row.description,
+                    "category" => &mut row.category,
+                    "priority" => &mut row.priority,
+                    "assignee_email" => &mut row.assignee_email,
+                    "created_at" => &mut row.created_at,
+                    _ => continue,
+                };
+                *slot = Some(value);
+            }
+            (index + 1, row)
+        })
+        .collect()
+}
+
+fn parse_csv(data: &str) -> Result<Vec<Vec<String>>, String> {
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(ch),
+            }
+            continue;
+        }
+
+        match ch {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            _ => field.push(ch),
+        }
+    }
+
+    if in_quotes {
+        return Err("CSV has an unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_csv_handles_quotes_and_line_breaks() {
+        let records = parse_csv(
+            "\u{feff}title,description\r\n\"A, b\",\"say \"\"hi\"\"\nthere\"\r\n\r\nplain,\n",
+        )
+        .expect("csv should parse");
+
+        assert_eq!(
+            records,
+            vec![
+                vec!["title", "description"],
+                vec!["A, b", "say \"hi\"\nthere"],
+                vec!["plain", ""],
+            ]
+        );
+        assert!(parse_csv("title\n\"open").is_err());
+    }
+
+    #[test]
+    fn csv_rows_map_known_columns_and_ignore_the_rest() {
+        let mut errors = Vec::new();
+        let rows = parse_csv_rows(
+            "id,Title,category,priority,status\n1,Printer,IT,high,open\n",
+            &mut errors,
+        );
+
+        assert!(errors.is_empty());
+        assert_eq!(rows.len(), 1);
+        let (row_number, row) = &rows[0];
+        assert_eq!(*row_number, 1);
+        assert_eq!(row.title.as_deref(), Some("Printer"));
+        assert_eq!(row.priority.as_deref(), Some("high"));
+        assert!(row.assignee_email.is_none());
+    }
+
+    #[test]
+    fn jsonl_rows_report_undecodable_lines() {
+        let mut errors = Vec::new();
+        let rows = parse_jsonl_rows(
+            "{\"title\":\"A\"}\n\nnot json\n{\"title\":\"B\"}\n",
+            &mut errors,
+        );
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].0, 3);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].row, 2);
+        assert_eq!(count_unparsed(&errors), 1);
+    }
+}
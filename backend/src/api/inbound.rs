@@ -0,0 +1,487 @@
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header::AUTHORIZATION},
+    response::IntoResponse,
+};
+use mail_parser::{Message, MessageParser, MimeHeaders};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use super::attachments::{self, MAX_ATTACHMENT_BYTES, NewAttachment};
+use super::{
+    CreateRequestInput, comments, create_request_record, fetch_visible_request,
+    publish_request_created, validate_create_input,
+};
+use crate::{
+    AppState,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+pub(super) const MAX_MESSAGE_BYTES: usize = 25 * 1024 * 1024;
+
+const MAX_TITLE_BYTES: usize = 255;
+const MAX_DESCRIPTION_BYTES: usize = 5000;
+const MAX_FILENAME_BYTES: usize = 255;
+
+#[derive(Debug, Deserialize)]
+pub(super) struct InboundEmailQuery {
+    category: Option<String>,
+    priority: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct InboundEmailResult {
+    action: &'static str,
+    request_id: Uuid,
+    comment_id: Option<Uuid>,
+    attachment_count: i64,
+}
+
+enum Outcome {
+    Created {
+        record: Box<super::RequestRow>,
+        audit_entry: Box<super::AuditLogRow>,
+    },
+    Commented {
+        comment: Box<comments::CommentRow>,
+        new_mentions: Vec<super::mentions::MentionRow>,
+    },
+}
+
+pub(super) async fn receive_email(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<InboundEmailQuery>,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    authorize(&state, &headers)?;
+
+    let message =
+        MessageParser::default().parse(&body[..]).ok_or_else(|| {
+            validation_error("message", "message is not a valid RFC 822 email")
+        })?;
+    let sender_email = message
+        .from()
+        .and_then(|from| from.first())
+        .and_then(|addr| addr.address())
+        .map(|address| address.trim().to_lowercase())
+        .ok_or_else(|| {
+            validation_error("from", "message has no sender address")
+        })?;
+    let sender_user_id: Uuid = sqlx::query_scalar(
+        "SELECT id
+         FROM app.app_users
+         WHERE lower(email) = $1
+           AND is_active = TRUE
+           AND deleted_at IS NULL",
+    )
+    .bind(&sender_email)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        validation_error("from", "sender does not match an active user")
+    })?;
+    let new_attachments = read_attachments(&message)?;
+    let message_id = message
+        .message_id()
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+
+    let mut tx = state.db.begin().await?;
+    let inbound_id: Option<Uuid> = sqlx::query_scalar(
+        "INSERT INTO app.inbound_emails (message_id, sender_user_id)
+         VALUES ($1, $2)
+         ON CONFLICT (message_id) DO NOTHING
+         RETURNING id",
+    )
+    .bind(message_id)
+    .bind(sender_user_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(inbound_id) = inbound_id else {
+        tx.rollback().await?;
+        let result = fetch_processed(&state, message_id).await?;
+        return Ok(response::ok(StatusCode::OK, result));
+    };
+
+    let thread_request_id =
+        find_thread_request(&mut tx, &message, sender_user_id).await?;
+    let attachment_count = new_attachments.len() as i64;
+    let outcome = match thread_request_id {
+        Some(request_id) => {
+            let text = reply_text(&message.body_text(0).unwrap_or_default());
+            let text = if text.is_empty() && !new_attachments.is_empty() {
+                let names: Vec<&str> = new_attachments
+                    .iter()
+                    .map(|attachment| attachment.filename.as_str())
+                    .collect();
+                format!("Attached: {}", names.join(", "))
+            } else {
+                text
+            };
+            let text = comments::validate_body(&truncate_to_bytes(
+                &text,
+                MAX_DESCRIPTION_BYTES,
+            ))?;
+            let (comment, new_mentions) = comments::insert_comment(
+                &mut tx,
+                request_id,
+                sender_user_id,
+                &text,
+            )
+            .await?;
+            attachments::insert_attachments(
+                &mut tx,
+                request_id,
+                Some(comment.id),
+                sender_user_id,
+                new_attachments,
+            )
+            .await?;
+            Outcome::Commented {
+                comment: Box::new(comment),
+                new_mentions,
+            }
+        }
+        None => {
+            let input = CreateRequestInput {
+                title: title_from_subject(message.subject()),
+                description: Some(truncate_to_bytes(
+                    message.body_text(0).unwrap_or_default().trim(),
+                    MAX_DESCRIPTION_BYTES,
+                ))
+                .filter(|description| !description.is_empty()),
+                category: query.category.unwrap_or_else(|| "IT".to_string()),
+                priority: query
+                    .priority
+                    .unwrap_or_else(|| "medium".to_string()),
+                assignee_email: None,
+                checklist: Vec::new(),
+                checklist_required: false,
+                created_at: None,
+            };
+            validate_create_input(&input)?;
+            let mut marker = serde_json::Map::new();
+            marker.insert("source".to_string(), json!("email"));
+            marker.insert("message_id".to_string(), json!(message_id));
+            let (record, audit_entry) = create_request_record(
+                &mut tx,
+                sender_user_id,
+                input,
+                Some(marker),
+            )
+            .await?;
+            attachments::insert_attachments(
+                &mut tx,
+                record.id,
+                None,
+                sender_user_id,
+                new_attachments,
+            )
+            .await?;
+            Outcome::Created {
+                record: Box::new(record),
+                audit_entry: Box::new(audit_entry),
+            }
+        }
+    };
+
+    let (request_id, comment_id) = match &outcome {
+        Outcome::Created { record, .. } => (record.id, None),
+        Outcome::Commented { comment, .. } => {
+            (comment.request_id, Some(comment.id))
+        }
+    };
+    sqlx::query(
+        "UPDATE app.inbound_emails
+         SET request_id = $2, comment_id = $3
+         WHERE id = $1",
+    )
+    .bind(inbound_id)
+    .bind(request_id)
+    .bind(comment_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    match outcome {
+        Outcome::Created {
+            record,
+            audit_entry,
+        } => publish_request_created(&state, &record, *audit_entry).await?,
+        Outcome::Commented {
+            comment,
+            new_mentions,
+        } => {
+            comments::publish_comment(
+                &state,
+                sender_user_id,
+                *comment,
+                new_mentions,
+            )
+            .await?
+        }
+    }
+
+    Ok(response::ok(
+        StatusCode::CREATED,
+        InboundEmailResult {
+            action: if comment_id.is_some() {
+                "commented"
+            } else {
+                "created"
+            },
+            request_id,
+            comment_id,
+            attachment_count,
+        },
+    ))
+}
+
+// Digests are compared so the comparison time does not depend on how much
+// of the token matched.
+fn authorize(state: &AppState, headers: &HeaderMap) -> Result<(), AppError> {
+    let Some(expected) = state.inbound_email_token.as_deref() else {
+        return Err(AppError::NotFound(
+            "inbound email is not enabled".to_string(),
+        ));
+    };
+    let presented = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    if Sha256::digest(presented.as_bytes())
+        == Sha256::digest(expected.as_bytes())
+    {
+        Ok(())
+    } else {
+        Err(AppError::Unauthorized(
+            "invalid inbound email token".to_string(),
+        ))
+    }
+}
+
+async fn fetch_processed(
+    state: &AppState,
+    message_id: Option<&str>,
+) -> Result<InboundEmailResult, AppError> {
+    let (request_id, comment_id): (Option<Uuid>, Option<Uuid>) =
+        sqlx::query_as(
+            "SELECT request_id, comment_id
+             FROM app.inbound_emails
+             WHERE message_id = $1",
+        )
+        .bind(message_id)
+        .fetch_one(&state.db)
+        .await?;
+    let request_id = request_id.ok_or_else(|| {
+        AppError::NotFound("request for this message was deleted".to_string())
+    })?;
+    let attachment_count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*)
+         FROM app.request_attachments
+         WHERE request_id = $1
+           AND comment_id IS NOT DISTINCT FROM $2",
+    )
+    .bind(request_id)
+    .bind(comment_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(InboundEmailResult {
+        action: if comment_id.is_some() {
+            "commented"
+        } else {
+            "created"
+        },
+        request_id,
+        comment_id,
+        attachment_count,
+    })
+}
+
+async fn find_thread_request(
+    conn: &mut PgConnection,
+    message: &Message<'_>,
+    sender_user_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    let referenced: Vec<String> = [message.in_reply_to(), message.references()]
+        .into_iter()
+        .filter_map(|value| value.as_text_list())
+        .flatten()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+
+    let mut candidates: Vec<Uuid> = Vec::new();
+    if !referenced.is_empty() {
+        candidates.extend(
+            sqlx::query_scalar::<_, Uuid>(
+                "SELECT request_id
+                 FROM app.inbound_emails
+                 WHERE message_id = ANY($1)
+                   AND request_id IS NOT NULL
+                 ORDER BY created_at DESC",
+            )
+            .bind(&referenced)
+            .fetch_all(&mut *conn)
+            .await?,
+        );
+    }
+    candidates.extend(message.subject().and_then(request_id_in_subject));
+
+    for request_id in candidates {
+        match fetch_visible_request(&mut *conn, request_id, sender_user_id)
+            .await
+        {
+            Ok(_) => return Ok(Some(request_id)),
+            Err(AppError::NotFound(_)) => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(None)
+}
+
+fn read_attachments(
+    message: &Message<'_>,
+) -> Result<Vec<NewAttachment>, AppError> {
+    message
+        .attachments()
+        .enumerate()
+        .map(|(index, part)| {
+            let content = part.contents();
+            if content.len() > MAX_ATTACHMENT_BYTES {
+                return Err(validation_error(
+                    "attachments",
+                    &format!(
+                        "attachments must be <= {} bytes",
+                        MAX_ATTACHMENT_BYTES
+                    ),
+                ));
+            }
+            let filename = part
+                .attachment_name()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| truncate_to_bytes(name, MAX_FILENAME_BYTES))
+                .unwrap_or_else(|| format!("attachment-{}", index + 1));
+            let content_type = part
+                .content_type()
+                .map(|ctype| match ctype.subtype() {
+                    Some(subtype) => format!("{}/{}", ctype.ctype(), subtype),
+                    None => ctype.ctype().to_string(),
+                })
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+
+            Ok(NewAttachment {
+                filename,
+                content_type: truncate_to_bytes(&content_type, 255),
+                content: content.to_vec(),
+            })
+        })
+        .collect()
+}
+
+fn request_id_in_subject(subject: &str) -> Option<Uuid> {
+    subject
+        .split(|ch: char| !(ch.is_ascii_hexdigit() || ch == '-'))
+        .filter(|token| token.len() == 36)
+        .find_map(|token| Uuid::parse_str(token).ok())
+}
+
+fn reply_text(body: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in body.lines() {
+        if line == "-- " || line == "--" {
+            break;
+        }
+        if line.trim_start().starts_with('>') {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    if let Some(last) = lines.last()
+        && last.trim_start().starts_with("On ")
+        && last.trim_end().ends_with("wrote:")
+    {
+        lines.pop();
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+fn title_from_subject(subject: Option<&str>) -> String {
+    let title = subject.map(str::trim).unwrap_or_default();
+    if title.is_empty() {
+        "(no subject)".to_string()
+    } else {
+        truncate_to_bytes(title, MAX_TITLE_BYTES)
+    }
+}
+
+fn truncate_to_bytes(value: &str, max_bytes: usize) -> String {
+    let mut end = value.len().min(max_bytes);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    value[..end].to_string()
+}
+
+fn validation_error(field: &str, message: &str) -> AppError {
+    AppError::Validation(vec![ErrorDetail {
+        field: field.to_string(),
+        message: message.to_string(),
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reply_text_drops_quotes_and_signature() {
+        let body = "Still broken after the restart.\n\n\
+                    On Mon, 2 Mar 2026, Support wrote:\n\
+                    > Please restart it.\n\
+                    > Thanks\n\
+                    -- \n\
+                    Jane";
+
+        assert_eq!(reply_text(body), "Still broken after the restart.");
+        assert_eq!(reply_text("> only a quote"), "");
+    }
+
+    #[test]
+    fn request_id_in_subject_finds_uuid() {
+        let id = Uuid::new_v4();
+
+        assert_eq!(
+            request_id_in_subject(&format!("Re: [#{id}] Laptop")),
+            Some(id)
+        );
+        assert_eq!(request_id_in_subject("Re: laptop 1234"), None);
+    }
+
+    #[test]
+    fn title_from_subject_falls_back_and_truncates() {
+        assert_eq!(title_from_subject(None), "(no subject)");
+        assert_eq!(title_from_subject(Some("   ")), "(no subject)");
+        assert_eq!(title_from_subject(Some(" VPN down ")), "VPN down");
+
+        let long = "é".repeat(200);
+        let title = title_from_subject(Some(&long));
+        assert_eq!(title.len(), 254);
+        assert!(title.chars().all(|ch| ch == 'é'));
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::HashSet;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, PgConnection};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::checklists::ensure_resolvable;
+use super::events::{
+    AuditAppendEventPayload, RequestLinkEventPayload, ServerEvent,
+};
+use super::{
+    AuditLogRow, UpdateRequestInput, fetch_editable_request,
+    fetch_request_recipient_ids, fetch_visible_request, insert_audit_log,
+    publish_event, publish_request_update, require_authenticated_user,
+    update_request_record,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
+pub(super) struct RequestLinkRow {
+    id: Uuid,
+    pub(super) source_request_id: Uuid,
+    target_request_id: Uuid,
+    link_type: String,
+    created_by_user_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct LinkedRequest {
+    id: Uuid,
+    title: String,
+    status: String,
+    priority: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RequestLink {
+    #[serde(flatten)]
+    link: RequestLinkRow,
+    relation: String,
+    request: LinkedRequest,
+}
+
+#[derive(Debug, FromRow)]
+struct LinkedRow {
+    #[sqlx(flatten)]
+    link: RequestLinkRow,
+    other_id: Uuid,
+    other_title: String,
+    other_status: String,
+    other_priority: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateLinkInput {
+    link_type: String,
+    target_request_id: Uuid,
+    #[serde(default)]
+    resolve_duplicate: bool,
+}
+
+fn link_projection_sql() -> &'static str {
+    "link.id,
+     link.source_request_id,
+     link.target_request_id,
+     link.link_type,
+     link.created_by_user_id,
+     link.created_at"
+}
+
+pub(super) async fn list_links(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    fetch_visible_request(&state.db, id, user.id).await?;
+
+    let query = format!(
+        "SELECT {},
+                other.id AS other_id,
+                other.title AS other_title,
+                other.status AS other_status,
+                other.priority AS other_priority
+         FROM app.request_links link
+         JOIN app.requests other
+           ON other.id = CASE
+             WHEN link.source_request_id = $1 THEN link.target_request_id
+             ELSE link.source_request_id
+           END
+         JOIN app.request_participants participants
+           ON participants.request_id = other.id
+          AND participants.user_id = $2
+         WHERE link.source_request_id = $1 OR link.target_request_id = $1
+         ORDER BY link.created_at ASC, link.id ASC",
+        link_projection_sql()
+    );
+    let links = sqlx::query_as::<_, LinkedRow>(&query)
+        .bind(id)
+        .bind(user.id)
+        .fetch_all(&state.db)
+        .await?
+        .into_iter()
+        .map(|row| RequestLink {
+            relation: relation_from(&row.link, id),
+            request: LinkedRequest {
+                id: row.other_id,
+                title: row.other_title,
+                status: row.other_status,
+                priority: row.other_priority,
+            },
+            link: row.link,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(response::ok(StatusCode::OK, links))
+}
+
+pub(super) async fn create_link(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<CreateLinkInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let (link_type, reversed) = parse_link_type(&input.link_type)?;
+    if input.target_request_id == id {
+        return Err(validation(
+            "target_request_id",
+            "a request cannot be linked to itself",
+        ));
+    }
+    if input.resolve_duplicate && link_type != "duplicates" {
+        return Err(validation(
+            "resolve_duplicate",
+            "resolve_duplicate only applies to duplicates links",
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+    let current = fetch_editable_request(&mut *tx, id, user.id).await?;
+    let other =
+        match fetch_visible_request(&mut *tx, input.target_request_id, user.id)
+            .await
+        {
+            Ok(other) => other,
+            Err(AppError::NotFound(_)) => {
+                return Err(validation(
+                    "target_request_id",
+                    "request not found",
+                ));
+            }
+            Err(err) => return Err(err),
+        };
+    let (source_id, target_id, source_status) = if reversed {
+        (other.id, id, &other.status)
+    } else {
+        (id, other.id, &current.status)
+    };
+    let resolve_source = input.resolve_duplicate && source_status != "resolved";
+
+    if input.resolve_duplicate {
+        let source =
+            match fetch_editable_request(&mut *tx, source_id, user.id).await {
+                Ok(source) => source,
+                Err(AppError::NotFound(_)) => {
+                    return Err(validation(
+                        "resolve_duplicate",
+                        "only requests you can edit can be resolved",
+                    ));
+                }
+                Err(err) => return Err(err),
+            };
+        // Checked before the insert so a blocked resolve leaves no link.
+        if let Err(AppError::Validation(details)) =
+            ensure_resolvable(&source, "resolved", source.checklist_required)
+        {
+            return Err(AppError::Validation(
+                details
+                    .into_iter()
+                    .map(|detail| ErrorDetail {
+                        field: "resolve_duplicate".to_string(),
+                        message: detail.message,
+                    })
+                    .collect(),
+            ));
+        }
+    }
+    if link_type != "relates_to" {
+        // Serializes directed link writes so two concurrent inserts cannot
+        // close a cycle that neither saw.
+        sqlx::query(
+            "SELECT pg_advisory_xact_lock(hashtext('app.request_links'))",
+        )
+        .execute(&mut *tx)
+        .await?;
+        if creates_cycle(&mut tx, link_type, source_id, target_id).await? {
+            return Err(validation(
+                "link_type",
+                "this link would create a cycle",
+            ));
+        }
+    }
+
+    let query = format!(
+        "INSERT INTO app.request_links AS link (
+           source_request_id,
+           target_request_id,
+           link_type,
+           created_by_user_id
+         )
+         VALUES ($1, $2, $3, $4)
+         RETURNING {}",
+        link_projection_sql()
+    );
+    let link = sqlx::query_as::<_, RequestLinkRow>(&query)
+        .bind(source_id)
+        .bind(target_id)
+        .bind(link_type)
+        .bind(user.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_link_write_error)?;
+    let audit_entries =
+        insert_link_audit(&mut tx, &link, user.id, "linked").await?;
+    let resolved = if resolve_source {
+        Some(
+            update_request_record(
+                &mut tx,
+                user.id,
+                source_id,
+                UpdateRequestInput {
+                    title: None,
+                    description: None,
+                    category: None,
+                    status: Some("resolved".to_string()),
+                    priority: None,
+                    assignee_email: None,
+                    checklist_required: None,
+                },
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+    tx.commit().await?;
+
+    publish_link_change(
+        &state,
+        ServerEvent::RequestLinkAdded(RequestLinkEventPayload {
+            link: link.clone(),
+        }),
+        audit_entries,
+    )
+    .await?;
+    let other = match resolved {
+        Some(update) => {
+            let resolved =
+                publish_request_update(&state, user.id, update).await?;
+            if resolved.id == other.id {
+                resolved
+            } else {
+                other
+            }
+        }
+        None => other,
+    };
+
+    let relation = relation_from(&link, id);
+    Ok(response::ok(
+        StatusCode::CREATED,
+        RequestLink {
+            link,
+            relation,
+            request: LinkedRequest {
+                id: other.id,
+                title: other.title,
+                status: other.status,
+                priority: other.priority,
+            },
+        },
+    ))
+}
+
+pub(super) async fn delete_link(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let mut tx = state.db.begin().await?;
+    fetch_editable_request(&mut *tx, id, user.id).await?;
+    let query = format!(
+        "DELETE FROM app.request_links AS link
+         WHERE link.id = $1
+           AND (link.source_request_id = $2 OR link.target_request_id = $2)
+         RETURNING {}",
+        link_projection_sql()
+    );
+    let link = sqlx::query_as::<_, RequestLinkRow>(&query)
+        .bind(link_id)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("link not found".to_string()))?;
+    let audit_entries =
+        insert_link_audit(&mut tx, &link, user.id, "unlinked").await?;
+    tx.commit().await?;
+
+    publish_link_change(
+        &state,
+        ServerEvent::RequestLinkRemoved(RequestLinkEventPayload { link }),
+        audit_entries,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn parse_link_type(value: &str) -> Result<(&'static str, bool), AppError> {
+    match value {
+        "duplicates" => Ok(("duplicates", false)),
+        "duplicated_by" => Ok(("duplicates", true)),
+        "blocks" => Ok(("blocks", false)),
+        "blocked_by" => Ok(("blocks", true)),
+        "parent_of" => Ok(("parent_of", false)),
+        "child_of" => Ok(("parent_of", true)),
+        "relates_to" => Ok(("relates_to", false)),
+        _ => Err(validation(
+            "link_type",
+            "link_type must be one of duplicates, duplicated_by, blocks, \
+             blocked_by, parent_of, child_of, relates_to",
+        )),
+    }
+}
+
+fn relation_from(link: &RequestLinkRow, request_id: Uuid) -> String {
+    if link.source_request_id == request_id {
+        return link.link_type.clone();
+    }
+
+    match link.link_type.as_str() {
+        "duplicates" => "duplicated_by",
+        "blocks" => "blocked_by",
+        "parent_of" => "child_of",
+        other => other,
+    }
+    .to_string()
+}
+
+async fn creates_cycle(
+    conn: &mut PgConnection,
+    link_type: &str,
+    source_id: Uuid,
+    target_id: Uuid,
+) -> Result<bool, AppError> {
+    sqlx::query_scalar(
+        "WITH RECURSIVE reachable(id) AS (
+           SELECT $2::uuid
+           UNION
+           SELECT link.target_request_id
+           FROM app.request_links link
+           JOIN reachable ON link.source_request_id = reachable.id
+           WHERE link.link_type = $3
+         )
+         SELECT EXISTS (SELECT 1 FROM reachable WHERE id = $1)",
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .bind(link_type)
+    .fetch_one(conn)
+    .await
+    .map_err(AppError::from)
+}
+
+async fn insert_link_audit(
+    conn: &mut PgConnection,
+    link: &RequestLinkRow,
+    actor_user_id: Uuid,
+    action: &str,
+) -> Result<[AuditLogRow; 2], AppError> {
+    let value = json!({
+        "link_id": link.id,
+        "link_type": link.link_type,
+        "source_request_id": link.source_request_id,
+        "target_request_id": link.target_request_id,
+    });
+    let (old_value, new_value) = if action == "unlinked" {
+        (value, json!({}))
+    } else {
+        (json!({}), value)
+    };
+
+    let mut entries = Vec::with_capacity(2);
+    for request_id in [link.source_request_id, link.target_request_id] {
+        entries.push(
+            insert_audit_log(
+                &mut *conn,
+                request_id,
+                actor_user_id,
+                action,
+                old_value.clone(),
+                new_value.clone(),
+            )
+            .await?,
+        );
+    }
+    let [source, target]: [AuditLogRow; 2] = entries
+        .try_into()
+        .map_err(|_| AppError::Internal("missing link audit".to_string()))?;
+    Ok([source, target])
+}
+
+async fn publish_link_change(
+    state: &AppState,
+    event: ServerEvent,
+    [source_audit, target_audit]: [AuditLogRow; 2],
+) -> Result<(), AppError> {
+    let source_recipients =
+        fetch_request_recipient_ids(&state.db, source_audit.request_id).await?;
+    let target_recipients =
+        fetch_request_recipient_ids(&state.db, target_audit.request_id).await?;
+    let recipients: Vec<Uuid> = source_recipients
+        .iter()
+        .chain(&target_recipients)
+        .copied()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    publish_event(state, &recipients, event).await;
+    publish_event(
+        state,
+        &source_recipients,
+        ServerEvent::AuditAppend(AuditAppendEventPayload {
+            audit: source_audit,
+        }),
+    )
+    .await;
+    publish_event(
+        state,
+        &target_recipients,
+        ServerEvent::AuditAppend(AuditAppendEventPayload {
+            audit: target_audit,
+        }),
+    )
+    .await;
+
+    Ok(())
+}
+
+fn map_link_write_error(error: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_error) = &error {
+        match db_error.constraint() {
+            Some(
+                "idx_request_links_unique"
+                | "idx_request_links_relates_to_pair_unique",
+            ) => {
+                return validation(
+                    "target_request_id",
+                    "these requests are already linked this way",
+                );
+            }
+            Some("idx_request_links_single_parent") => {
+                return validation(
+                    "link_type",
+                    "the child request already has a parent",
+                );
+            }
+            _ => {}
+        }
+    }
+
+    AppError::Database(error)
+}
+
+fn validation(field: &str, message: &str) -> AppError {
+    AppError::Validation(vec![ErrorDetail {
+        field: field.to_string(),
+        message: message.to_string(),
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(source: Uuid, target: Uuid, link_type: &str) -> RequestLinkRow {
+        RequestLinkRow {
+            id: Uuid::new_v4(),
+            source_request_id: source,
+            target_request_id: target,
+            link_type: link_type.to_string(),
+            created_by_user_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn inverse_link_types_swap_direction() {
+        assert_eq!(parse_link_type("blocks").unwrap(), ("blocks", false));
+        assert_eq!(parse_link_type("blocked_by").unwrap(), ("blocks", true));
+        assert_eq!(parse_link_type("child_of").unwrap(), ("parent_of", true));
+        assert!(parse_link_type("clones").is_err());
+    }
+
+    #[test]
+    fn relation_reads_from_the_given_request() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let blocks = link(a, b, "blocks");
+        assert_eq!(relation_from(&blocks, a), "blocks");
+        assert_eq!(relation_from(&blocks, b), "blocked_by");
+
+        let related = link(a, b, "relates_to");
+        assert_eq!(relation_from(&related, b), "relates_to");
+    }
+}
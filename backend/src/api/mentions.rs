@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{FromRow, PgConnection, PgExecutor};
+use uuid::Uuid;
+
+use super::events::{MentionEventPayload, ServerEvent};
+use super::{is_valid_email, notifications, publish_event};
+use crate::{AppState, error::AppError};
+
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
+pub(super) struct MentionRow {
+    id: Uuid,
+    pub(super) request_id: Uuid,
+    comment_id: Option<Uuid>,
+    author_user_id: Uuid,
+    author_display_name: String,
+    pub(super) mentioned_user_id: Uuid,
+    request_title: String,
+    created_at: DateTime<Utc>,
+}
+
+const MENTION_ORDER_SQL: &str =
+    "ORDER BY mention.created_at ASC, mention.id ASC";
+
+fn mention_select_sql() -> &'static str {
+    "SELECT
+       mention.id,
+       mention.request_id,
+       mention.comment_id,
+       mention.author_user_id,
+       author.display_name AS author_display_name,
+       mention.mentioned_user_id,
+       req.title AS request_title,
+       mention.created_at
+     FROM app.request_mentions mention
+     JOIN app.app_users author ON author.id = mention.author_user_id
+     JOIN app.requests req ON req.id = mention.request_id"
+}
+
+pub(super) async fn record_mentions(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    comment_id: Option<Uuid>,
+    author_user_id: Uuid,
+    text: &str,
+    grant_access: bool,
+) -> Result<Vec<MentionRow>, AppError> {
+    let emails = extract_mentions(text);
+    if emails.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if grant_access {
+        sqlx::query(
+            "SELECT app.upsert_request_participant($1, users.id, 'mention', NOW())
+             FROM app.app_users users
+             WHERE lower(users.email) = ANY($2)
+               AND users.deleted_at IS NULL
+               AND users.id <> $3",
+        )
+        .bind(request_id)
+        .bind(&emails)
+        .bind(author_user_id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    let ids: Vec<Uuid> = sqlx::query_scalar(
+        "INSERT INTO app.request_mentions (
+           request_id,
+           comment_id,
+           author_user_id,
+           mentioned_user_id
+         )
+         SELECT $1, $2, $3, users.id
+         FROM app.app_users users
+         JOIN app.request_participants participants
+           ON participants.request_id = $1
+          AND participants.user_id = users.id
+         WHERE lower(users.email) = ANY($4)
+           AND users.deleted_at IS NULL
+           AND users.id <> $3
+         ON CONFLICT DO NOTHING
+         RETURNING id",
+    )
+    .bind(request_id)
+    .bind(comment_id)
+    .bind(author_user_id)
+    .bind(&emails)
+    .fetch_all(&mut *conn)
+    .await?;
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let query = format!(
+        "{} WHERE mention.id = ANY($1) {MENTION_ORDER_SQL}",
+        mention_select_sql()
+    );
+    sqlx::query_as::<_, MentionRow>(&query)
+        .bind(&ids)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(AppError::from)
+}
+
+pub(super) async fn fetch_description_mentions<'e, E>(
+    executor: E,
+    request_id: Uuid,
+) -> Result<Vec<MentionRow>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let query = format!(
+        "{}
+         WHERE mention.request_id = $1 AND mention.comment_id IS NULL
+         {MENTION_ORDER_SQL}",
+        mention_select_sql()
+    );
+    sqlx::query_as::<_, MentionRow>(&query)
+        .bind(request_id)
+        .fetch_all(executor)
+        .await
+        .map_err(AppError::from)
+}
+
+pub(super) async fn publish_mentions(
+    state: &AppState,
+    mentions: Vec<MentionRow>,
+) -> Result<(), AppError> {
+    for mention in mentions {
+        let recipient = mention.mentioned_user_id;
+        let data = json!({
+            "mention_id": mention.id,
+            "comment_id": mention.comment_id,
+        });
+        let (request_id, author_user_id) =
+            (mention.request_id, mention.author_user_id);
+        publish_event(
+            state,
+            &[recipient],
+            ServerEvent::MentionCreated(MentionEventPayload { mention }),
+        )
+        .await;
+        notifications::notify(
+            state,
+            "mention",
+            request_id,
+            Some(author_user_id),
+            data,
+            &[recipient],
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+// An `@` only starts a mention at the start of the text or after a
+// character that cannot be part of an address, so plain addresses such as
+// `bob@corp.com` are not mentions.
+fn extract_mentions(text: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous = None;
+
+    for (index, ch) in text.char_indices() {
+        let starts_mention =
+            ch == '@' && !previous.is_some_and(is_address_char);
+        previous = Some(ch);
+        if !starts_mention {
+            continue;
+        }
+
+        let rest = &text[index + 1..];
+        let end = rest
+            .find(|c: char| !is_address_char(c) && c != '@')
+            .unwrap_or(rest.len());
+        let candidate = rest[..end].trim_end_matches(['.', '-']);
+        if !is_valid_email(candidate) {
+            continue;
+        }
+
+        let email = candidate.to_lowercase();
+        if !mentions.contains(&email) {
+            mentions.push(email);
+        }
+    }
+
+    mentions
+}
+
+fn is_address_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '%' | '+' | '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_mentions_finds_prefixed_addresses_once() {
+        let text = "Ping @Alice@corp.com and (@bob@corp.com). \
+                    Again @alice@corp.com, not carol@corp.com or @dave.";
+        assert_eq!(extract_mentions(text), ["alice@corp.com", "bob@corp.com"]);
+    }
+
+    #[test]
+    fn extract_mentions_ignores_malformed_addresses() {
+        assert!(extract_mentions("@a@b@c.com @@x.com @y@ @").is_empty());
+        assert_eq!(extract_mentions("@eve@corp.com."), ["eve@corp.com"]);
+    }
+}
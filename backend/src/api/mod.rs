@@ -1,28 +1,84 @@
-use std::collections::HashSet;
+mod attachments;
+mod bulk;
+mod business_hours;
+mod checklists;
+mod comments;
+mod digests;
+mod escalations;
+mod events;
+mod export;
+mod filters;
+mod import;
+mod inbound;
+mod links;
+mod mentions;
+mod notifications;
+mod pagination;
+mod routing;
+mod schedules;
+mod sla;
+mod templates;
+mod views;
+mod watchers;
+mod webhooks;
+mod workers;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use axum::{
     Json, Router,
-    extract::ws::{Message, WebSocket, WebSocketUpgrade},
-    extract::{Path, Query, State},
+    extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+    extract::{DefaultBodyLimit, Path, Query, State},
     http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
-    routing::get,
+    routing::{delete, get, patch, post, put},
 };
 use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use sqlx::{FromRow, PgPool};
-use tokio::time::{self, Duration, Instant};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgConnection, PgExecutor, PgPool, Postgres, QueryBuilder};
+use tokio::{
+    task::JoinSet,
+    time::{self, Duration, Instant},
+};
 use tower_sessions::Session;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
+pub use self::digests::{DigestConfig, run_due_digests, spawn_digest_worker};
+pub use self::escalations::{run_due_escalations, spawn_escalation_worker};
+use self::events::{
+    AckEventPayload, AuditAppendEventPayload, HelloAcceptedEventPayload,
+    NackError, NackEventPayload, ProfilePatchEventPayload, ProfileSnapshot,
+    RequestBatchEventPayload, RequestCreatedEventPayload,
+    RequestDeletedEventPayload, RequestPatchEventPayload, ServerEvent,
+    SessionRevokedEventPayload,
+};
+use self::filters::RequestListFilter;
+pub use self::import::{
+    ImportFormat, ImportReport, ImportRowError, import_requests,
+    resolve_import_owner,
+};
+use self::pagination::{RequestCursor, RequestSort};
+pub use self::schedules::{run_due_schedules, spawn_scheduler};
+pub use self::sla::{
+    run_due_sla_events, set_workspace_admin, spawn_sla_worker,
+};
+pub use self::webhooks::{
+    WebhookClient, run_due_webhooks, spawn_webhook_worker, webhook_client,
+};
 use crate::{
     AppState,
-    auth::{middleware, routes as auth_routes},
+    auth::{middleware, routes as auth_routes, session as auth_session},
     error::{AppError, ErrorDetail},
-    realtime::{self, ClientMessage, EventEnvelope},
+    realtime::{
+        self, ClientMessage, ConnectionAuth, ConnectionFormat, OutboundEvent,
+        WireFormat, WireFrame,
+    },
     response,
 };
 
@@ -33,7 +89,7 @@ struct AuthUserRow {
     display_name: String,
 }
 
-#[derive(Debug, Clone, Serialize, FromRow)]
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
 struct RequestRow {
     id: Uuid,
     owner_user_id: Uuid,
@@ -45,11 +101,30 @@ struct RequestRow {
     assignee_user_id: Option<Uuid>,
     assignee_email: Option<String>,
     assignee_display_name: Option<String>,
+    checklist_total: i64,
+    checklist_done: i64,
+    checklist_required: bool,
+    sla_first_response_due_at: Option<DateTime<Utc>>,
+    sla_resolution_due_at: Option<DateTime<Utc>>,
+    sla_remaining_seconds: Option<i64>,
+    sla_first_response_breached: bool,
+    sla_resolution_breached: bool,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, FromRow)]
+#[derive(Debug, Serialize, FromRow)]
+struct RequestListItem {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    request: RequestRow,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snippet: Option<String>,
+    #[serde(skip)]
+    search_rank: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
 struct AuditLogRow {
     id: Uuid,
     request_id: Uuid,
@@ -74,15 +149,27 @@ struct AssigneeSuggestionRow {
     assignment_count: i64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct ListRequestsQuery {
     status: Option<String>,
     category: Option<String>,
     priority: Option<String>,
+    assignee: Option<String>,
+    owner: Option<String>,
+    created_from: Option<String>,
+    created_to: Option<String>,
+    updated_from: Option<String>,
+    updated_to: Option<String>,
+    resolved_from: Option<String>,
+    resolved_to: Option<String>,
+    sla: Option<String>,
     q: Option<String>,
     sort: Option<String>,
     page: Option<u64>,
     limit: Option<u64>,
+    cursor: Option<String>,
+    view: Option<String>,
+    include_total: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -92,6 +179,12 @@ struct CreateRequestInput {
     category: String,
     priority: String,
     assignee_email: Option<String>,
+    #[serde(default)]
+    checklist: Vec<String>,
+    #[serde(default)]
+    checklist_required: bool,
+    #[serde(skip)]
+    created_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -102,6 +195,7 @@ struct UpdateRequestInput {
     status: Option<String>,
     priority: Option<String>,
     assignee_email: Option<String>,
+    checklist_required: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -115,6 +209,8 @@ struct UpdatePreferencesInput {
     email_digest: Option<bool>,
     browser_alerts: Option<bool>,
     default_page_size: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    default_view_id: Option<Option<Uuid>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -134,6 +230,7 @@ struct PreferencesResponse {
     email_digest: bool,
     browser_alerts: bool,
     default_page_size: i32,
+    default_view_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone)]
@@ -141,29 +238,7 @@ struct PreferencesUpdate {
     email_digest: bool,
     browser_alerts: bool,
     default_page_size: i32,
-}
-
-#[derive(Debug, Serialize)]
-struct RequestCreatedEventPayload {
-    request: RequestRow,
-}
-
-#[derive(Debug, Serialize)]
-struct RequestPatchEventPayload {
-    request: RequestRow,
-    changed_fields: Vec<String>,
-    previous_status: String,
-}
-
-#[derive(Debug, Serialize)]
-struct RequestDeletedEventPayload {
-    id: Uuid,
-    status: String,
-}
-
-#[derive(Debug, Serialize)]
-struct AuditAppendEventPayload {
-    audit: AuditLogRow,
+    default_view_id: Option<Uuid>,
 }
 
 pub fn router() -> Router<AppState> {
@@ -171,13 +246,21 @@ pub fn router() -> Router<AppState> {
         .merge(auth_routes::router())
         .route("/health", get(health))
         .route("/me", get(me).patch(update_me))
+        .route(
+            "/me/availability",
+            get(routing::get_availability).patch(routing::update_availability),
+        )
         .route(
             "/preferences",
             get(get_preferences).patch(update_preferences),
         )
         .route("/meta/enums", get(get_enums))
+        .route("/realtime/schema", get(events::get_realtime_schema))
         .route("/assignees/suggestions", get(list_assignee_suggestions))
         .route("/requests", get(list_requests).post(create_request))
+        .route("/requests/bulk", post(bulk::bulk_requests))
+        .route("/requests/export", get(export::export_requests))
+        .route("/requests/import", post(import::import_requests_handler))
         .route(
             "/requests/:id",
             get(get_request)
@@ -185,6 +268,152 @@ pub fn router() -> Router<AppState> {
                 .delete(delete_request),
         )
         .route("/requests/:id/audit", get(get_request_audit))
+        .route(
+            "/requests/:id/attachments",
+            get(attachments::list_attachments),
+        )
+        .route(
+            "/requests/:id/attachments/:attachment_id",
+            get(attachments::download_attachment),
+        )
+        .route(
+            "/requests/:id/checklist",
+            get(checklists::list_items).post(checklists::create_item),
+        )
+        .route(
+            "/requests/:id/checklist/order",
+            put(checklists::reorder_items),
+        )
+        .route(
+            "/requests/:id/checklist/:item_id",
+            patch(checklists::update_item).delete(checklists::delete_item),
+        )
+        .route(
+            "/requests/:id/links",
+            get(links::list_links).post(links::create_link),
+        )
+        .route(
+            "/requests/:id/comments",
+            get(comments::list_comments).post(comments::create_comment),
+        )
+        .route(
+            "/requests/:id/comments/:comment_id",
+            delete(comments::delete_comment),
+        )
+        .route("/requests/:id/links/:link_id", delete(links::delete_link))
+        .route(
+            "/requests/:id/watch",
+            post(watchers::watch_request).delete(watchers::unwatch_request),
+        )
+        .route(
+            "/requests/:id/watchers",
+            get(watchers::list_watchers).post(watchers::add_watcher),
+        )
+        .route(
+            "/requests/:id/watchers/:user_id",
+            delete(watchers::remove_watcher),
+        )
+        .route(
+            "/requests/:id/audit/export",
+            get(export::export_request_audit),
+        )
+        .route(
+            "/schedules",
+            get(schedules::list_schedules).post(schedules::create_schedule),
+        )
+        .route(
+            "/schedules/:id",
+            get(schedules::get_schedule)
+                .patch(schedules::update_schedule)
+                .delete(schedules::delete_schedule),
+        )
+        .route(
+            "/templates",
+            get(templates::list_templates).post(templates::create_template),
+        )
+        .route(
+            "/templates/:id",
+            get(templates::get_template)
+                .patch(templates::update_template)
+                .delete(templates::delete_template),
+        )
+        .route("/notifications", get(notifications::list_notifications))
+        .route(
+            "/notifications/unread-count",
+            get(notifications::get_unread_count),
+        )
+        .route(
+            "/notifications/read-all",
+            post(notifications::mark_all_read),
+        )
+        .route("/notifications/:id/read", post(notifications::mark_read))
+        .route(
+            "/sla/calendars",
+            get(sla::list_calendars).post(sla::create_calendar),
+        )
+        .route(
+            "/sla/calendars/:id",
+            get(sla::get_calendar)
+                .patch(sla::update_calendar)
+                .delete(sla::delete_calendar),
+        )
+        .route(
+            "/sla/policies",
+            get(sla::list_policies).post(sla::create_policy),
+        )
+        .route(
+            "/sla/policies/:id",
+            get(sla::get_policy)
+                .patch(sla::update_policy)
+                .delete(sla::delete_policy),
+        )
+        .route(
+            "/escalation-rules",
+            get(escalations::list_rules).post(escalations::create_rule),
+        )
+        .route(
+            "/escalation-rules/:id",
+            get(escalations::get_rule)
+                .patch(escalations::update_rule)
+                .delete(escalations::delete_rule),
+        )
+        .route(
+            "/routing-rules",
+            get(routing::list_rules).post(routing::create_rule),
+        )
+        .route(
+            "/routing-rules/:id",
+            get(routing::get_rule)
+                .patch(routing::update_rule)
+                .delete(routing::delete_rule),
+        )
+        .route("/views", get(views::list_views).post(views::create_view))
+        .route(
+            "/views/:id",
+            get(views::get_view)
+                .patch(views::update_view)
+                .delete(views::delete_view),
+        )
+        .route(
+            "/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route(
+            "/webhooks/:id",
+            get(webhooks::get_webhook)
+                .patch(webhooks::update_webhook)
+                .delete(webhooks::delete_webhook),
+        )
+        .route("/webhooks/:id/deliveries", get(webhooks::list_deliveries))
+        .route(
+            "/webhooks/:id/deliveries/:delivery_id/redeliver",
+            post(webhooks::redeliver),
+        )
+        .route(
+            "/inbound/email",
+            post(inbound::receive_email)
+                .layer(DefaultBodyLimit::max(inbound::MAX_MESSAGE_BYTES)),
+        )
 }
 
 pub async fn health(
@@ -226,16 +455,27 @@ pub async fn ws(
         .and_then(|value| value.to_str().ok())
         .map(ToString::to_string);
 
-    let user_id =
-        resolve_ws_user_id(&state, &session, &headers, query.token.as_deref())
-            .await?;
-    let hub = state.realtime_hub.clone();
+    let (user_id, connection_auth) = resolve_ws_connection_auth(
+        &state,
+        &session,
+        &headers,
+        query.token.as_deref(),
+    )
+    .await?;
 
     Ok(ws
+        .protocols(realtime::wire::WS_SUBPROTOCOLS)
         .max_frame_size(realtime::WS_MAX_MESSAGE_BYTES)
         .max_message_size(realtime::WS_MAX_MESSAGE_BYTES)
         .on_upgrade(move |socket| async move {
-            handle_ws_connection(socket, hub, user_id, trace_id).await;
+            handle_ws_connection(
+                socket,
+                state,
+                user_id,
+                connection_auth,
+                trace_id,
+            )
+            .await;
         }))
 }
 
@@ -260,47 +500,116 @@ fn extract_ws_token(
     Ok(token.to_string())
 }
 
-async fn resolve_ws_user_id(
+async fn resolve_ws_connection_auth(
     state: &AppState,
     session_handle: &Session,
     headers: &HeaderMap,
     query_token: Option<&str>,
-) -> Result<Uuid, AppError> {
+) -> Result<(Uuid, ConnectionAuth), AppError> {
     let ws_token = extract_ws_token(headers, query_token).ok();
 
     if let Some(token) = ws_token {
-        return middleware::verify_ws_token_with_state(state, &token).await;
+        let user_id =
+            middleware::verify_ws_token_with_state(state, &token).await?;
+        let token_fingerprint = Sha256::digest(token.as_bytes()).to_vec();
+        return Ok((user_id, ConnectionAuth::WsToken { token_fingerprint }));
     }
 
+    let missing_auth = || {
+        AppError::Unauthorized(
+            "missing websocket authentication token".to_string(),
+        )
+    };
+
     let context =
         middleware::resolve_request_auth(state, session_handle, headers).await;
-    match context {
-        Ok(auth) => Ok(auth.user.id),
-        Err(AppError::Unauthorized(_)) => Err(AppError::Unauthorized(
-            "missing websocket authentication token".to_string(),
-        )),
-        Err(other) => Err(other),
-    }
+    let user_id = match context {
+        Ok(auth) => auth.user.id,
+        Err(AppError::Unauthorized(_)) => return Err(missing_auth()),
+        Err(other) => return Err(other),
+    };
+
+    let session_user = auth_session::load_session_user(session_handle)
+        .await?
+        .ok_or_else(missing_auth)?;
+    let session_id =
+        auth_session::session_id(session_handle).ok_or_else(missing_auth)?;
+
+    Ok((
+        user_id,
+        ConnectionAuth::Session {
+            session_id,
+            session_version: session_user.session_version,
+        },
+    ))
 }
 
 async fn handle_ws_connection(
     socket: WebSocket,
-    hub: realtime::RealtimeHub,
+    state: AppState,
     user_id: Uuid,
+    connection_auth: ConnectionAuth,
     trace_id: Option<String>,
 ) {
-    let (connection_id, mut outbound) = hub.register(user_id).await;
+    let hub = state.realtime_hub.clone();
+    let mut format = ConnectionFormat {
+        wire: socket
+            .protocol()
+            .and_then(|protocol| protocol.to_str().ok())
+            .and_then(WireFormat::from_subprotocol)
+            .unwrap_or_default(),
+        protocol_version: realtime::PROTOCOL_VERSION_V1,
+    };
     let (mut sender, mut receiver) = socket.split();
+    let realtime::RealtimeConnection {
+        id: connection_id,
+        mut outbound,
+        mut disconnect,
+    } = match hub
+        .register_authenticated(user_id, connection_auth.clone())
+        .await
+    {
+        Ok(connection) => connection,
+        Err(exceeded) => {
+            // Closed after the upgrade rather than refused with a 429:
+            // browsers only expose the close code to scripts.
+            warn!(%user_id, scope = exceeded.as_str(), "websocket rejected at cap");
+            let _ = sender
+                .send(Message::Close(Some(CloseFrame {
+                    code: realtime::WS_CLOSE_TRY_AGAIN_LATER,
+                    reason: "connection limit reached".into(),
+                })))
+                .await;
+            return;
+        }
+    };
+    hub.set_format(user_id, connection_id, format).await;
 
     let mut heartbeat = time::interval(Duration::from_secs(
         realtime::WS_HEARTBEAT_INTERVAL_SECS,
     ));
     heartbeat.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
 
+    let recheck_period =
+        Duration::from_secs(realtime::WS_AUTH_RECHECK_INTERVAL_SECS);
+    let mut auth_recheck =
+        time::interval_at(Instant::now() + recheck_period, recheck_period);
+    auth_recheck.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+
     let mut idle_deadline =
         Instant::now() + Duration::from_secs(realtime::WS_IDLE_TIMEOUT_SECS);
-
-    debug!(%user_id, %connection_id, ?trace_id, "websocket connected");
+    // Mutations run off the read loop so a slow write does not hold back
+    // heartbeats and outbound events; dropping the set aborts them.
+    let mut mutations = JoinSet::new();
+
+    debug!(
+        %user_id,
+        %connection_id,
+        ?trace_id,
+        encoding = ?format.wire.encoding,
+        compression = ?format.wire.compression,
+        "websocket connected",
+    );
 
     loop {
         let sleep = time::sleep_until(idle_deadline);
@@ -316,52 +625,83 @@ async fn handle_ws_connection(
                     break;
                 }
             }
+            reason = &mut disconnect => {
+                match reason {
+                    Ok(realtime::DisconnectReason::Revoked(reason)) => {
+                        close_revoked_ws(&mut sender, format, reason, trace_id.clone())
+                            .await;
+                    }
+                    Ok(realtime::DisconnectReason::SlowConsumer) => {
+                        warn!(%user_id, %connection_id, "websocket slow consumer disconnected");
+                        close_slow_consumer_ws(&mut sender, format, trace_id.clone())
+                            .await;
+                    }
+                    Err(_) => {}
+                }
+                break;
+            }
+            _ = auth_recheck.tick() => {
+                match middleware::revalidate_ws_connection(
+                    &state.db,
+                    user_id,
+                    &connection_auth,
+                )
+                .await
+                {
+                    Ok(None) => {}
+                    Ok(Some(reason)) => {
+                        debug!(
+                            %user_id,
+                            %connection_id,
+                            reason = reason.as_str(),
+                            "websocket credential no longer valid",
+                        );
+                        close_revoked_ws(&mut sender, format, reason, trace_id.clone())
+                            .await;
+                        break;
+                    }
+                    Err(err) => {
+                        warn!(
+                            %user_id,
+                            %connection_id,
+                            error = %err,
+                            "failed to revalidate websocket credential",
+                        );
+                    }
+                }
+            }
             maybe_outbound = outbound.recv() => {
-                let Some(text) = maybe_outbound else {
+                let Some(event) = maybe_outbound else {
                     break;
                 };
 
-                if sender
-                    .send(Message::Text(text.to_string()))
-                    .await
-                    .is_err()
-                {
+                if !send_ws_event(&mut sender, format, &event).await {
                     break;
                 }
             }
             maybe_inbound = receiver.next() => {
-                match maybe_inbound {
+                let raw = match maybe_inbound {
                     Some(Ok(Message::Text(text))) => {
                         idle_deadline = Instant::now()
                             + Duration::from_secs(realtime::WS_IDLE_TIMEOUT_SECS);
-                        let sync_required =
-                            handle_client_message(&text, user_id, connection_id);
-                        if sync_required {
-                            match EventEnvelope::new(
-                                "sync.required",
-                                None,
-                                trace_id.clone(),
-                                json!({}),
-                            )
-                            .encode()
-                            {
-                                Ok(message) => {
-                                    if sender
-                                        .send(Message::Text(message.to_string()))
-                                        .await
-                                        .is_err()
-                                    {
-                                        break;
-                                    }
-                                }
-                                Err(err) => {
-                                    warn!(
-                                        %user_id,
-                                        %connection_id,
-                                        error = %err,
-                                        "failed to encode sync.required event",
-                                    );
-                                }
+                        if text.len() > realtime::WS_MAX_MESSAGE_BYTES {
+                            warn!(%user_id, %connection_id, "websocket message exceeded max size");
+                            continue;
+                        }
+                        serde_json::from_str::<serde_json::Value>(&text).ok()
+                    }
+                    Some(Ok(Message::Binary(bytes))) => {
+                        if !format.wire.is_binary() {
+                            warn!(%user_id, %connection_id, "websocket binary frame rejected");
+                            break;
+                        }
+                        idle_deadline = Instant::now()
+                            + Duration::from_secs(realtime::WS_IDLE_TIMEOUT_SECS);
+                        match format.wire.decode_binary(&bytes) {
+                            Ok(value) => Some(value),
+                            Err(err) => {
+                                debug!(%user_id, %connection_id, error = %err, "websocket binary frame ignored");
+                                None
                             }
                         }
                     }
@@ -371,14 +711,12 @@ async fn handle_ws_connection(
                         if sender.send(Message::Pong(payload)).await.is_err() {
                             break;
                         }
+                        continue;
                     }
                     Some(Ok(Message::Pong(_))) => {
                         idle_deadline = Instant::now()
                             + Duration::from_secs(realtime::WS_IDLE_TIMEOUT_SECS);
-                    }
-                    Some(Ok(Message::Binary(_))) => {
-                        warn!(%user_id, %connection_id, "websocket binary frame rejected");
-                        break;
+                        continue;
                     }
                     Some(Ok(Message::Close(_))) => {
                         break;
@@ -390,6 +728,97 @@ async fn handle_ws_connection(
                     None => {
                         break;
                     }
+                };
+
+                let Some(raw) = raw else {
+                    debug!(%user_id, %connection_id, "websocket client message ignored");
+                    continue;
+                };
+
+                let message = match parse_client_message(user_id, connection_id, raw) {
+                    Ok(message) => message,
+                    Err(outcome) => {
+                        if !send_client_outcome(&mut sender, format, outcome, trace_id.clone()).await {
+                            break;
+                        }
+                        continue;
+                    }
+                };
+
+                let ClientMessage::Hello {
+                    last_seen_ts,
+                    encoding,
+                    compression,
+                    protocol_version,
+                } = message
+                else {
+                    if mutations.len() >= realtime::WS_MAX_PENDING_MUTATIONS {
+                        let op_id = message.op_id().unwrap_or_default().to_string();
+                        let busy = nack_event(
+                            op_id,
+                            None,
+                            &AppError::RateLimited("too many pending operations".to_string()),
+                        );
+                        if !send_server_event(&mut sender, format, busy, trace_id.clone()).await {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    let state = state.clone();
+                    let connection_auth = connection_auth.clone();
+                    mutations.spawn(async move {
+                        handle_client_mutation(&state, user_id, &connection_auth, message).await
+                    });
+                    continue;
+                };
+
+                if encoding.is_some()
+                    || compression.is_some()
+                    || protocol_version.is_some()
+                {
+                    let previous = format;
+                    format = ConnectionFormat {
+                        wire: WireFormat {
+                            encoding: encoding.unwrap_or(previous.wire.encoding),
+                            compression: compression
+                                .unwrap_or(previous.wire.compression),
+                        },
+                        protocol_version: protocol_version
+                            .map_or(previous.protocol_version, realtime::negotiate_protocol_version),
+                    };
+                    hub.set_format(user_id, connection_id, format).await;
+                    // Confirmed in the previous frame encoding;
+                    // everything after it uses the new one.
+                    let accepted = ServerEvent::HelloAccepted(HelloAcceptedEventPayload {
+                        protocol_version: format.protocol_version,
+                        encoding: format.wire.encoding,
+                        compression: format.wire.compression,
+                    });
+                    let confirm_format = ConnectionFormat {
+                        wire: previous.wire,
+                        protocol_version: format.protocol_version,
+                    };
+                    if !send_server_event(&mut sender, confirm_format, accepted, trace_id.clone()).await {
+                        break;
+                    }
+                }
+                if last_seen_ts.is_some()
+                    && !send_server_event(&mut sender, format, ServerEvent::SyncRequired {}, trace_id.clone()).await
+                {
+                    break;
+                }
+            }
+            Some(joined) = mutations.join_next() => {
+                let outcome = match joined {
+                    Ok(outcome) => outcome,
+                    Err(err) => {
+                        warn!(%user_id, %connection_id, error = %err, "websocket mutation task failed");
+                        continue;
+                    }
+                };
+                if !send_client_outcome(&mut sender, format, outcome, trace_id.clone()).await {
+                    break;
                 }
             }
         }
@@ -397,37 +826,281 @@ async fn handle_ws_connection(
 
     hub.unregister(user_id, connection_id).await;
     let _ = sender.close().await;
+    // Wait for the client's close frame: dropping the socket with its
+    // frames unread resets the connection, and the client loses whatever
+    // it had not read yet, close code included.
+    let _ = time::timeout(
+        Duration::from_secs(realtime::WS_CLOSE_TIMEOUT_SECS),
+        async {
+            while let Some(Ok(message)) = receiver.next().await {
+                if matches!(message, Message::Close(_)) {
+                    break;
+                }
+            }
+        },
+    )
+    .await;
     debug!(%user_id, %connection_id, "websocket disconnected");
 }
 
-fn handle_client_message(
-    text: &str,
+async fn send_ws_event<S>(
+    sender: &mut S,
+    format: ConnectionFormat,
+    event: &OutboundEvent,
+) -> bool
+where
+    S: SinkExt<Message> + Unpin,
+{
+    let message = match event.frame(format) {
+        Ok(WireFrame::Text(text)) => Message::Text(text.to_string()),
+        Ok(WireFrame::Binary(bytes)) => Message::Binary(bytes.to_vec()),
+        Err(err) => {
+            warn!(error = %err, "failed to encode websocket frame");
+            return true;
+        }
+    };
+
+    sender.send(message).await.is_ok()
+}
+
+async fn send_server_event<S>(
+    sender: &mut S,
+    format: ConnectionFormat,
+    event: ServerEvent,
+    trace_id: Option<String>,
+) -> bool
+where
+    S: SinkExt<Message> + Unpin,
+{
+    match event
+        .to_envelope(trace_id)
+        .and_then(|envelope| event.to_outbound(envelope))
+    {
+        Ok(outbound) => send_ws_event(sender, format, &outbound).await,
+        Err(err) => {
+            warn!(error = %err, "failed to encode websocket event");
+            true
+        }
+    }
+}
+
+async fn close_revoked_ws<S>(
+    sender: &mut S,
+    format: ConnectionFormat,
+    reason: realtime::RevocationReason,
+    trace_id: Option<String>,
+) where
+    S: SinkExt<Message> + Unpin,
+{
+    let revoked =
+        ServerEvent::SessionRevoked(SessionRevokedEventPayload { reason });
+    send_server_event(sender, format, revoked, trace_id).await;
+
+    let _ = sender
+        .send(Message::Close(Some(CloseFrame {
+            code: realtime::WS_CLOSE_SESSION_REVOKED,
+            reason: reason.as_str().into(),
+        })))
+        .await;
+}
+
+async fn close_slow_consumer_ws<S>(
+    sender: &mut S,
+    format: ConnectionFormat,
+    trace_id: Option<String>,
+) where
+    S: SinkExt<Message> + Unpin,
+{
+    send_server_event(sender, format, ServerEvent::SyncRequired {}, trace_id)
+        .await;
+
+    let _ = sender
+        .send(Message::Close(Some(CloseFrame {
+            code: realtime::WS_CLOSE_SLOW_CONSUMER,
+            reason: "slow consumer".into(),
+        })))
+        .await;
+}
+
+enum ClientMessageOutcome {
+    Ignored,
+    Reply(Box<ServerEvent>),
+    Revoked(realtime::RevocationReason),
+}
+
+async fn send_client_outcome<S>(
+    sender: &mut S,
+    format: ConnectionFormat,
+    outcome: ClientMessageOutcome,
+    trace_id: Option<String>,
+) -> bool
+where
+    S: SinkExt<Message> + Unpin,
+{
+    match outcome {
+        ClientMessageOutcome::Ignored => true,
+        ClientMessageOutcome::Reply(event) => {
+            send_server_event(sender, format, *event, trace_id).await
+        }
+        ClientMessageOutcome::Revoked(reason) => {
+            close_revoked_ws(sender, format, reason, trace_id).await;
+            false
+        }
+    }
+}
+
+fn parse_client_message(
     user_id: Uuid,
     connection_id: Uuid,
-) -> bool {
-    if text.len() > realtime::WS_MAX_MESSAGE_BYTES {
-        warn!(%user_id, %connection_id, "websocket message exceeded max size");
-        return false;
+    raw: serde_json::Value,
+) -> Result<ClientMessage, ClientMessageOutcome> {
+    // Mutations that fail to parse still get a nack so the client can
+    // settle its pending operation.
+    let raw_op_id = raw
+        .get("op_id")
+        .and_then(serde_json::Value::as_str)
+        .map(ToString::to_string);
+
+    let message = match serde_json::from_value::<ClientMessage>(raw) {
+        Ok(message) => message,
+        Err(err) => {
+            debug!(%user_id, %connection_id, "websocket client message ignored");
+            return Err(match raw_op_id {
+                Some(op_id) => {
+                    ClientMessageOutcome::Reply(Box::new(nack_event(
+                        op_id,
+                        None,
+                        &AppError::Validation(vec![ErrorDetail {
+                            field: "message".to_string(),
+                            message: err.to_string(),
+                        }]),
+                    )))
+                }
+                None => ClientMessageOutcome::Ignored,
+            });
+        }
+    };
+
+    debug!(
+        %user_id,
+        %connection_id,
+        last_seen_ts = ?message.last_seen_ts(),
+        op_id = ?message.op_id(),
+        "websocket client message received",
+    );
+
+    Ok(message)
+}
+
+async fn handle_client_mutation(
+    state: &AppState,
+    user_id: Uuid,
+    connection_auth: &ConnectionAuth,
+    message: ClientMessage,
+) -> ClientMessageOutcome {
+    let (op_id, request_id) = match &message {
+        ClientMessage::Hello { .. } => return ClientMessageOutcome::Ignored,
+        ClientMessage::RequestCreate { op_id, .. } => (op_id.clone(), None),
+        ClientMessage::RequestUpdate { op_id, id, .. } => {
+            (op_id.clone(), Some(*id))
+        }
+    };
+
+    match middleware::revalidate_ws_connection(
+        &state.db,
+        user_id,
+        connection_auth,
+    )
+    .await
+    {
+        Ok(Some(reason)) => return ClientMessageOutcome::Revoked(reason),
+        Ok(None) => {}
+        Err(err) => {
+            return ClientMessageOutcome::Reply(Box::new(nack_event(
+                op_id, request_id, &err,
+            )));
+        }
     }
 
-    match serde_json::from_str::<ClientMessage>(text) {
-        Ok(message) => {
-            let last_seen_ts = message.last_seen_ts();
-            debug!(
-                %user_id,
-                %connection_id,
-                last_seen_ts = ?last_seen_ts,
-                "websocket client message received",
-            );
-            last_seen_ts.is_some()
+    let result = match message {
+        ClientMessage::Hello { .. } => return ClientMessageOutcome::Ignored,
+        ClientMessage::RequestCreate { data, .. } => {
+            create_request_from_socket(state, user_id, data).await
         }
-        Err(_) => {
-            debug!(%user_id, %connection_id, "websocket client message ignored");
-            false
+        ClientMessage::RequestUpdate { id, data, .. } => {
+            match parse_client_payload::<UpdateRequestInput>(data) {
+                Ok(input) => {
+                    apply_request_update(state, user_id, id, input).await
+                }
+                Err(err) => Err(err),
+            }
         }
+    };
+
+    ClientMessageOutcome::Reply(Box::new(mutation_reply(
+        op_id, request_id, result,
+    )))
+}
+
+async fn create_request_from_socket(
+    state: &AppState,
+    user_id: Uuid,
+    data: serde_json::Value,
+) -> Result<RequestRow, AppError> {
+    let body = parse_client_payload::<templates::CreateRequestBody>(data)?;
+    let user = sqlx::query_as::<_, AuthUserRow>(
+        "SELECT id, COALESCE(email, '') AS email, display_name
+         FROM app.app_users
+         WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::Unauthorized("auth user not found".to_string()))?;
+    let input = templates::resolve_create_input(&state.db, &user, body).await?;
+    validate_create_input(&input)?;
+    insert_request(state, user_id, input).await
+}
+
+fn parse_client_payload<T>(data: serde_json::Value) -> Result<T, AppError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_value(data).map_err(|err| {
+        AppError::Validation(vec![ErrorDetail {
+            field: "data".to_string(),
+            message: err.to_string(),
+        }])
+    })
+}
+
+fn mutation_reply(
+    op_id: String,
+    request_id: Option<Uuid>,
+    result: Result<RequestRow, AppError>,
+) -> ServerEvent {
+    match result {
+        Ok(record) => ServerEvent::Ack(AckEventPayload {
+            op_id,
+            request: record,
+        }),
+        Err(err) => nack_event(op_id, request_id, &err),
     }
 }
 
+fn nack_event(
+    op_id: String,
+    request_id: Option<Uuid>,
+    error: &AppError,
+) -> ServerEvent {
+    error.log();
+    ServerEvent::Nack(NackEventPayload {
+        op_id,
+        id: request_id,
+        error: NackError::from(error),
+    })
+}
+
 async fn me(
     State(state): State<AppState>,
     session: Session,
@@ -473,15 +1146,13 @@ async fn update_me(
     publish_event(
         &state,
         &[current_user.id],
-        "profile.patch",
-        None,
-        json!({
-            "user": {
-                "id": user.id,
-                "email": user.email,
-                "display_name": user.display_name,
+        ServerEvent::ProfilePatch(ProfilePatchEventPayload {
+            user: ProfileSnapshot {
+                id: user.id,
+                email: user.email.clone(),
+                display_name: user.display_name.clone(),
             },
-            "changed_fields": ["display_name"],
+            changed_fields: vec!["display_name".to_string()],
         }),
     )
     .await;
@@ -517,6 +1188,11 @@ async fn update_preferences(
     middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
     let current = fetch_or_create_preferences(&state.db, user.id).await?;
     let update = normalize_preferences_update(input, &current)?;
+    if let Some(view_id) = update.default_view_id
+        && update.default_view_id != current.default_view_id
+    {
+        views::ensure_view_visible(&state.db, &user, view_id).await?;
+    }
     let updated = upsert_preferences(&state.db, user.id, &update).await?;
 
     Ok(response::ok(StatusCode::OK, updated))
@@ -551,15 +1227,13 @@ async fn list_assignee_suggestions(
     let term = query.q.as_deref().map(str::trim).unwrap_or("");
     let limit = query.limit.unwrap_or(50).clamp(1, 200) as i64;
 
-    let suggestions = sqlx::query_as::<_, AssigneeSuggestionRow>(
+    let suggestions = sqlx::query_as::<_, AssigneeSuggestionRow>(&format!(
         "SELECT
             u.id,
             u.email AS email,
             u.display_name,
-            COUNT(req.id)::bigint AS assignment_count
+            {ASSIGNMENT_COUNT_SQL} AS assignment_count
          FROM app.app_users u
-         LEFT JOIN app.requests req
-           ON req.assignee_user_id = u.id
          WHERE u.email IS NOT NULL
            AND u.id <> $3
            AND lower(split_part(u.email, '@', 2)) = lower($1)
@@ -576,10 +1250,9 @@ async fn list_assignee_suggestions(
                  ) NOT LIKE ('%' || term || '%')
              )
            )
-         GROUP BY u.id, u.email, u.display_name
          ORDER BY assignment_count DESC, u.email ASC
-         LIMIT $4",
-    )
+         LIMIT $4"
+    ))
     .bind(&domain)
     .bind(term)
     .bind(user.id)
@@ -598,137 +1271,121 @@ async fn list_requests(
 ) -> Result<impl IntoResponse, AppError> {
     let user = require_authenticated_user(&state, &session, &headers).await?;
 
-    let page = query.page.unwrap_or(1).max(1);
+    let query = views::apply_view(&state.db, &user, query).await?;
     let limit = query.limit.unwrap_or(20).clamp(1, 100);
-    let offset = ((page - 1) * limit) as i64;
-
-    if let Some(status) = &query.status {
-        validate_status(status)?;
-    }
-    if let Some(category) = &query.category {
-        validate_category(category)?;
-    }
-    if let Some(priority) = &query.priority {
-        validate_priority(priority)?;
-    }
-
-    let sort_clause = match query.sort.as_deref() {
-        Some("created_at") => "req.created_at ASC",
-        Some("updated_at") => "req.updated_at ASC",
-        Some("-updated_at") => "req.updated_at DESC",
-        _ => "req.created_at DESC",
-    };
-
-    let status_filter = query.status.clone();
-    let category_filter = query.category.clone();
-    let priority_filter = query.priority.clone();
-    let search_filter = query
-        .q
+    let filter = RequestListFilter::from_query(&query)?;
+    let sort =
+        RequestSort::parse(query.sort.as_deref(), filter.is_searching())?;
+    let cursor = query
+        .cursor
         .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(ToString::to_string);
-
-    let list_query = format!(
-        "SELECT {}
-         FROM app.request_participants participants
-         JOIN app.requests req ON req.id = participants.request_id
-         LEFT JOIN app.app_users assignee ON assignee.id = req.assignee_user_id
-         WHERE participants.user_id = $1
-           AND ($2::text IS NULL OR req.status = $2)
-           AND ($3::text IS NULL OR req.category = $3)
-           AND ($4::text IS NULL OR req.priority = $4)
-           AND (
-             $5::text IS NULL
-             OR NOT EXISTS (
-               SELECT 1
-               FROM unnest(regexp_split_to_array(lower($5), '[[:space:]]+')) AS t(term)
-               WHERE term <> ''
-                 AND concat_ws(
-                   ' ',
-                   lower(req.title),
-                   lower(COALESCE(req.description, '')),
-                   lower(req.category),
-                   lower(req.status),
-                   lower(req.priority),
-                   lower(COALESCE(assignee.email, '')),
-                   lower(COALESCE(assignee.display_name, ''))
-                 ) NOT LIKE ('%' || term || '%')
-             )
-           )
-         ORDER BY {sort_clause}
-         LIMIT $6 OFFSET $7",
-        request_projection_sql()
-    );
-
-    let items = sqlx::query_as::<_, RequestRow>(&list_query)
-        .bind(user.id)
-        .bind(status_filter.clone())
-        .bind(category_filter.clone())
-        .bind(priority_filter.clone())
-        .bind(search_filter.clone())
-        .bind(limit as i64)
-        .bind(offset)
+        .map(|raw| RequestCursor::decode(raw, sort))
+        .transpose()?;
+    // Cursor mode ignores `page`; offset paging stays for older clients.
+    let page = match cursor {
+        Some(_) => None,
+        None => Some(query.page.unwrap_or(1).max(1)),
+    };
+    let offset = page.map_or(0, |page| ((page - 1) * limit) as i64);
+
+    let mut list = QueryBuilder::<Postgres>::new("SELECT ");
+    list.push(request_projection_sql())
+        .push(
+            ", CASE
+                 WHEN search.query IS NULL THEN NULL
+                 ELSE ",
+        )
+        .push(SEARCH_SNIPPET_SQL)
+        .push(
+            " END AS snippet,
+             ts_rank_cd(search.document, search.query) AS search_rank",
+        );
+    filter.push_from_where(&mut list, user.id);
+    if let Some(cursor) = &cursor {
+        sort.push_keyset(&mut list, cursor);
+    }
+    sort.push_order_by(&mut list);
+    list.push(" LIMIT ")
+        .push_bind(pagination::page_fetch_limit(limit))
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    let items = list
+        .build_query_as::<RequestListItem>()
         .fetch_all(&state.db)
         .await?;
+    let (items, next_cursor) = pagination::split_page(items, limit, |item| {
+        sort.cursor_after(item).encode()
+    });
+
+    let total = if query.include_total.unwrap_or(true) {
+        let mut count = QueryBuilder::<Postgres>::new("SELECT COUNT(*)");
+        filter.push_from_where(&mut count, user.id);
+        let total: i64 =
+            count.build_query_scalar().fetch_one(&state.db).await?;
+        Some(total as u64)
+    } else {
+        None
+    };
 
-    let total: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*)
-         FROM app.request_participants participants
-         JOIN app.requests req ON req.id = participants.request_id
-         LEFT JOIN app.app_users assignee ON assignee.id = req.assignee_user_id
-         WHERE participants.user_id = $1
-           AND ($2::text IS NULL OR req.status = $2)
-           AND ($3::text IS NULL OR req.category = $3)
-           AND ($4::text IS NULL OR req.priority = $4)
-           AND (
-             $5::text IS NULL
-             OR NOT EXISTS (
-               SELECT 1
-               FROM unnest(regexp_split_to_array(lower($5), '[[:space:]]+')) AS t(term)
-               WHERE term <> ''
-                 AND concat_ws(
-                   ' ',
-                   lower(req.title),
-                   lower(COALESCE(req.description, '')),
-                   lower(req.category),
-                   lower(req.status),
-                   lower(req.priority),
-                   lower(COALESCE(assignee.email, '')),
-                   lower(COALESCE(assignee.display_name, ''))
-                 ) NOT LIKE ('%' || term || '%')
-             )
-           )",
-    )
-    .bind(user.id)
-    .bind(status_filter)
-    .bind(category_filter)
-    .bind(priority_filter)
-    .bind(search_filter)
-    .fetch_one(&state.db)
-    .await?;
-
-    Ok(response::list(items, page, limit, total as u64))
+    Ok(response::list(items, page, limit, total, next_cursor))
 }
 
 async fn create_request(
     State(state): State<AppState>,
     session: Session,
     headers: HeaderMap,
-    Json(input): Json<CreateRequestInput>,
+    Json(body): Json<templates::CreateRequestBody>,
 ) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let input = templates::resolve_create_input(&state.db, &user, body).await?;
     validate_create_input(&input)?;
 
-    let user = require_authenticated_user(&state, &session, &headers).await?;
     middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let record = insert_request(&state, user.id, input).await?;
+
+    Ok(response::ok(StatusCode::CREATED, record))
+}
+
+async fn insert_request(
+    state: &AppState,
+    user_id: Uuid,
+    input: CreateRequestInput,
+) -> Result<RequestRow, AppError> {
+    let mut tx = state.db.begin().await?;
+    let (record, audit_entry) =
+        create_request_record(&mut tx, user_id, input, None).await?;
+    tx.commit().await?;
+
+    publish_request_created(state, &record, audit_entry).await?;
+    Ok(record)
+}
+
+// Run inside a transaction: routing locks the rule, so concurrent creates
+// take turns.
+async fn create_request_record(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    input: CreateRequestInput,
+    audit_marker: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<(RequestRow, AuditLogRow), AppError> {
     let normalized_assignee_email =
         normalize_assignee_email(input.assignee_email.as_deref())?;
-    let assignee_user_id = resolve_assignee_user_id(
-        &state.db,
+    let requested_assignee = resolve_assignee_user_id(
+        &mut *conn,
         normalized_assignee_email.as_deref(),
     )
-    .await?
-    .unwrap_or(user.id);
+    .await?;
+    let routed = match requested_assignee {
+        Some(_) => None,
+        None => {
+            routing::route_assignee(&mut *conn, user_id, &input.category)
+                .await?
+        }
+    };
+    let assignee_user_id = requested_assignee
+        .or(routed.map(|routed| routed.user_id))
+        .unwrap_or(user_id);
 
     let request_id: Uuid = sqlx::query_scalar(
         "INSERT INTO app.requests (
@@ -738,58 +1395,109 @@ async fn create_request(
             category,
             status,
             priority,
-            assignee_user_id
+            assignee_user_id,
+            checklist_required,
+            created_at,
+            updated_at,
+            imported_at
+         )
+         VALUES (
+           $1, $2, $3, $4, 'open', $5, $6, $7,
+           COALESCE($8, NOW()), NOW(),
+           CASE WHEN $8 IS NOT NULL THEN NOW() END
          )
-         VALUES ($1, $2, $3, $4, 'open', $5, $6)
          RETURNING id",
     )
-    .bind(user.id)
+    .bind(user_id)
     .bind(input.title.trim())
     .bind(input.description.as_deref())
     .bind(input.category)
     .bind(input.priority)
     .bind(assignee_user_id)
-    .fetch_one(&state.db)
+    .bind(input.checklist_required)
+    .bind(input.created_at)
+    .fetch_one(&mut *conn)
     .await?;
-    let record = fetch_owned_request(&state.db, request_id, user.id).await?;
-
+    checklists::insert_initial_items(&mut *conn, request_id, &input.checklist)
+        .await?;
+    if let Some(description) = input.description.as_deref() {
+        mentions::record_mentions(
+            &mut *conn,
+            request_id,
+            None,
+            user_id,
+            description,
+            true,
+        )
+        .await?;
+    }
+    sla::refresh_request_sla(&mut *conn, request_id).await?;
+    let record = fetch_owned_request(&mut *conn, request_id, user_id).await?;
+
+    let mut new_value = json!({
+        "title": record.title,
+        "status": record.status,
+        "category": record.category,
+        "priority": record.priority,
+        "assignee_email": record.assignee_email,
+    });
+    if let (Some(object), Some(routed)) = (new_value.as_object_mut(), routed) {
+        object.insert("routing".to_string(), routed.audit_value());
+    }
+    if let (Some(object), Some(marker)) =
+        (new_value.as_object_mut(), audit_marker)
+    {
+        object.extend(marker);
+    }
     let audit_entry = insert_audit_log(
-        &state.db,
+        &mut *conn,
         record.id,
-        user.id,
+        user_id,
         "created",
         json!({}),
-        json!({
-            "title": record.title,
-            "status": record.status,
-            "category": record.category,
-            "priority": record.priority,
-            "assignee_email": record.assignee_email,
-        }),
+        new_value,
     )
     .await?;
 
+    Ok((record, audit_entry))
+}
+
+async fn publish_request_created(
+    state: &AppState,
+    record: &RequestRow,
+    audit_entry: AuditLogRow,
+) -> Result<(), AppError> {
     let recipients = fetch_request_recipient_ids(&state.db, record.id).await?;
     publish_event(
-        &state,
+        state,
         &recipients,
-        "request.created",
-        Some(record.id),
-        json!(RequestCreatedEventPayload {
+        ServerEvent::RequestCreated(RequestCreatedEventPayload {
             request: record.clone(),
         }),
     )
     .await;
+    let actor_user_id = audit_entry.actor_user_id;
     publish_event(
-        &state,
+        state,
         &recipients,
-        "audit.append",
-        Some(record.id),
-        json!(AuditAppendEventPayload { audit: audit_entry }),
+        ServerEvent::AuditAppend(AuditAppendEventPayload {
+            audit: audit_entry,
+        }),
     )
     .await;
+    notifications::notify_request_change(
+        state,
+        actor_user_id,
+        None,
+        record,
+        &recipients,
+    )
+    .await?;
+    let new_mentions =
+        mentions::fetch_description_mentions(&state.db, record.id).await?;
+    mentions::publish_mentions(state, new_mentions).await?;
 
-    Ok(response::ok(StatusCode::CREATED, record))
+    Ok(())
 }
 
 async fn get_request(
@@ -825,9 +1533,42 @@ async fn update_request(
 ) -> Result<impl IntoResponse, AppError> {
     let user = require_authenticated_user(&state, &session, &headers).await?;
     middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let updated = apply_request_update(&state, user.id, id, input).await?;
 
-    let existing = fetch_editable_request(&state.db, id, user.id).await?;
-    let recipients_before = fetch_request_recipient_ids(&state.db, id).await?;
+    Ok(response::ok(StatusCode::OK, updated))
+}
+
+async fn apply_request_update(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+    input: UpdateRequestInput,
+) -> Result<RequestRow, AppError> {
+    let mut tx = state.db.begin().await?;
+    let update = update_request_record(&mut tx, user_id, id, input).await?;
+    tx.commit().await?;
+
+    publish_request_update(state, user_id, update).await
+}
+
+struct RequestUpdate {
+    existing: RequestRow,
+    updated: RequestRow,
+    changed_fields: Vec<String>,
+    audit_entry: AuditLogRow,
+    recipients_before: Vec<Uuid>,
+    recipients_after: Vec<Uuid>,
+    new_mentions: Vec<mentions::MentionRow>,
+}
+
+async fn update_request_record(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    id: Uuid,
+    input: UpdateRequestInput,
+) -> Result<RequestUpdate, AppError> {
+    let existing = fetch_editable_request(&mut *conn, id, user_id).await?;
+    let recipients_before = fetch_request_recipient_ids(&mut *conn, id).await?;
 
     if let Some(category) = &input.category {
         validate_category(category)?;
@@ -857,11 +1598,19 @@ async fn update_request(
     let next_category = input.category.unwrap_or(existing.category.clone());
     let next_status = input.status.unwrap_or(existing.status.clone());
     let next_priority = input.priority.unwrap_or(existing.priority.clone());
+    let next_checklist_required = input
+        .checklist_required
+        .unwrap_or(existing.checklist_required);
+    checklists::ensure_resolvable(
+        &existing,
+        &next_status,
+        next_checklist_required,
+    )?;
     let next_assignee_user_id = if let Some(raw_assignee_email) =
         input.assignee_email
     {
         let normalized = normalize_assignee_email(Some(&raw_assignee_email))?;
-        resolve_assignee_user_id(&state.db, normalized.as_deref()).await?
+        resolve_assignee_user_id(&mut *conn, normalized.as_deref()).await?
     } else {
         existing.assignee_user_id
     };
@@ -874,6 +1623,7 @@ async fn update_request(
              status = $5,
              priority = $6,
              assignee_user_id = $7,
+             checklist_required = $8,
              updated_at = NOW()
          WHERE id = $1
          RETURNING id",
@@ -885,47 +1635,74 @@ async fn update_request(
     .bind(&next_status)
     .bind(&next_priority)
     .bind(next_assignee_user_id)
-    .fetch_one(&state.db)
+    .bind(next_checklist_required)
+    .fetch_one(&mut *conn)
     .await?;
-    let updated = fetch_visible_request(&state.db, updated_id, user.id).await?;
+    sla::refresh_request_sla(&mut *conn, updated_id).await?;
+    let updated =
+        fetch_visible_request(&mut *conn, updated_id, user_id).await?;
+    let new_mentions = match updated.description.as_deref() {
+        Some(description) if updated.description != existing.description => {
+            mentions::record_mentions(
+                &mut *conn,
+                updated.id,
+                None,
+                user_id,
+                description,
+                true,
+            )
+            .await?
+        }
+        _ => Vec::new(),
+    };
 
     let changed_fields = collect_changed_fields(&existing, &updated);
 
     let audit_entry = insert_audit_log(
-        &state.db,
+        &mut *conn,
         updated.id,
-        user.id,
+        user_id,
         "updated",
-        json!({
-            "title": existing.title,
-            "description": existing.description,
-            "category": existing.category,
-            "status": existing.status,
-            "priority": existing.priority,
-            "assignee_email": existing.assignee_email,
-        }),
-        json!({
-            "title": updated.title,
-            "description": updated.description,
-            "category": updated.category,
-            "status": updated.status,
-            "priority": updated.priority,
-            "assignee_email": updated.assignee_email,
-        }),
+        request_audit_snapshot(&existing),
+        request_audit_snapshot(&updated),
     )
     .await?;
 
     let recipients_after =
-        fetch_request_recipient_ids(&state.db, updated.id).await?;
+        fetch_request_recipient_ids(&mut *conn, updated.id).await?;
+
+    Ok(RequestUpdate {
+        existing,
+        updated,
+        changed_fields,
+        audit_entry,
+        recipients_before,
+        recipients_after,
+        new_mentions,
+    })
+}
+
+async fn publish_request_update(
+    state: &AppState,
+    user_id: Uuid,
+    update: RequestUpdate,
+) -> Result<RequestRow, AppError> {
+    let RequestUpdate {
+        existing,
+        updated,
+        changed_fields,
+        audit_entry,
+        recipients_before,
+        recipients_after,
+        new_mentions,
+    } = update;
     let (existing_recipients, newly_visible_recipients) =
         split_recipients_by_visibility(&recipients_before, &recipients_after);
 
     publish_event(
-        &state,
+        state,
         &existing_recipients,
-        "request.patch",
-        Some(updated.id),
-        json!(RequestPatchEventPayload {
+        ServerEvent::RequestPatch(RequestPatchEventPayload {
             request: updated.clone(),
             changed_fields,
             previous_status: existing.status.clone(),
@@ -933,25 +1710,32 @@ async fn update_request(
     )
     .await;
     publish_event(
-        &state,
+        state,
         &newly_visible_recipients,
-        "request.created",
-        Some(updated.id),
-        json!(RequestCreatedEventPayload {
+        ServerEvent::RequestCreated(RequestCreatedEventPayload {
             request: updated.clone(),
         }),
     )
     .await;
     publish_event(
-        &state,
+        state,
         &recipients_after,
-        "audit.append",
-        Some(updated.id),
-        json!(AuditAppendEventPayload { audit: audit_entry }),
+        ServerEvent::AuditAppend(AuditAppendEventPayload {
+            audit: audit_entry,
+        }),
     )
     .await;
+    notifications::notify_request_change(
+        state,
+        user_id,
+        Some(&existing),
+        &updated,
+        &recipients_after,
+    )
+    .await?;
+    mentions::publish_mentions(state, new_mentions).await?;
 
-    Ok(response::ok(StatusCode::OK, updated))
+    Ok(updated)
 }
 
 async fn delete_request(
@@ -972,14 +1756,7 @@ async fn delete_request(
         existing.id,
         user.id,
         "deleted",
-        json!({
-            "title": existing.title,
-            "description": existing.description,
-            "category": existing.category,
-            "status": existing.status,
-            "priority": existing.priority,
-            "assignee_email": existing.assignee_email,
-        }),
+        request_audit_snapshot(&existing),
         json!({}),
     )
     .await?;
@@ -992,17 +1769,15 @@ async fn delete_request(
     publish_event(
         &state,
         &recipients,
-        "audit.append",
-        Some(existing.id),
-        json!(AuditAppendEventPayload { audit: audit_entry }),
+        ServerEvent::AuditAppend(AuditAppendEventPayload {
+            audit: audit_entry,
+        }),
     )
     .await;
     publish_event(
         &state,
         &recipients,
-        "request.deleted",
-        Some(existing.id),
-        json!(RequestDeletedEventPayload {
+        ServerEvent::RequestDeleted(RequestDeletedEventPayload {
             id: existing.id,
             status: existing.status,
         }),
@@ -1034,24 +1809,18 @@ async fn get_request_audit(
         Err(other) => return Err(other),
     }
 
-    let items = sqlx::query_as::<_, AuditLogRow>(
-        "SELECT
-            logs.id,
-            logs.request_id,
-            logs.actor_user_id,
-            COALESCE(actor.email, logs.actor_user_id::text) AS actor_email,
-            logs.action,
-            logs.old_value,
-            logs.new_value,
-            logs.created_at
+    let query = format!(
+        "SELECT {}
          FROM app.request_audit_logs logs
          LEFT JOIN app.app_users actor ON actor.id = logs.actor_user_id
          WHERE logs.request_id = $1
          ORDER BY logs.created_at DESC",
-    )
-    .bind(id)
-    .fetch_all(&state.db)
-    .await?;
+        audit_log_projection_sql()
+    );
+    let items = sqlx::query_as::<_, AuditLogRow>(&query)
+        .bind(id)
+        .fetch_all(&state.db)
+        .await?;
 
     Ok(response::ok(StatusCode::OK, items))
 }
@@ -1079,12 +1848,16 @@ async fn fetch_or_create_preferences(
            INSERT INTO app.user_preferences (user_id)
            VALUES ($1)
            ON CONFLICT (user_id) DO NOTHING
-           RETURNING email_digest, browser_alerts, default_page_size
+           RETURNING
+             email_digest,
+             browser_alerts,
+             default_page_size,
+             default_view_id
          )
-         SELECT email_digest, browser_alerts, default_page_size
+         SELECT email_digest, browser_alerts, default_page_size, default_view_id
          FROM inserted
          UNION ALL
-         SELECT email_digest, browser_alerts, default_page_size
+         SELECT email_digest, browser_alerts, default_page_size, default_view_id
          FROM app.user_preferences
          WHERE user_id = $1
          LIMIT 1",
@@ -1107,33 +1880,44 @@ async fn upsert_preferences(
            user_id,
            email_digest,
            browser_alerts,
-           default_page_size
+           default_page_size,
+           default_view_id
          )
-         VALUES ($1, $2, $3, $4)
+         VALUES ($1, $2, $3, $4, $5)
          ON CONFLICT (user_id) DO UPDATE SET
            email_digest = EXCLUDED.email_digest,
            browser_alerts = EXCLUDED.browser_alerts,
-           default_page_size = EXCLUDED.default_page_size
-         RETURNING email_digest, browser_alerts, default_page_size",
+           default_page_size = EXCLUDED.default_page_size,
+           default_view_id = EXCLUDED.default_view_id
+         RETURNING
+           email_digest,
+           browser_alerts,
+           default_page_size,
+           default_view_id",
     )
     .bind(user_id)
     .bind(update.email_digest)
     .bind(update.browser_alerts)
     .bind(update.default_page_size)
+    .bind(update.default_view_id)
     .fetch_one(pool)
     .await
     .map_err(AppError::from)
 }
 
-async fn fetch_owned_request(
-    pool: &PgPool,
+async fn fetch_owned_request<'e, E>(
+    executor: E,
     request_id: Uuid,
     owner_id: Uuid,
-) -> Result<RequestRow, AppError> {
+) -> Result<RequestRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
     let query = format!(
         "SELECT {}
          FROM app.requests req
          LEFT JOIN app.app_users assignee ON assignee.id = req.assignee_user_id
+         LEFT JOIN app.request_slas sla ON sla.request_id = req.id
          WHERE req.id = $1 AND req.owner_user_id = $2",
         request_projection_sql()
     );
@@ -1141,20 +1925,24 @@ async fn fetch_owned_request(
     sqlx::query_as::<_, RequestRow>(&query)
         .bind(request_id)
         .bind(owner_id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| AppError::NotFound("request not found".to_string()))
 }
 
-async fn fetch_editable_request(
-    pool: &PgPool,
+async fn fetch_editable_request<'e, E>(
+    executor: E,
     request_id: Uuid,
     user_id: Uuid,
-) -> Result<RequestRow, AppError> {
+) -> Result<RequestRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
     let query = format!(
         "SELECT {}
          FROM app.requests req
          LEFT JOIN app.app_users assignee ON assignee.id = req.assignee_user_id
+         LEFT JOIN app.request_slas sla ON sla.request_id = req.id
          WHERE req.id = $1
            AND (req.owner_user_id = $2 OR req.assignee_user_id = $2)",
         request_projection_sql()
@@ -1163,16 +1951,19 @@ async fn fetch_editable_request(
     sqlx::query_as::<_, RequestRow>(&query)
         .bind(request_id)
         .bind(user_id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| AppError::NotFound("request not found".to_string()))
 }
 
-async fn fetch_visible_request(
-    pool: &PgPool,
+async fn fetch_visible_request<'e, E>(
+    executor: E,
     request_id: Uuid,
     user_id: Uuid,
-) -> Result<RequestRow, AppError> {
+) -> Result<RequestRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
     let query = format!(
         "SELECT {}
          FROM app.requests req
@@ -1180,6 +1971,7 @@ async fn fetch_visible_request(
            ON participants.request_id = req.id
           AND participants.user_id = $2
          LEFT JOIN app.app_users assignee ON assignee.id = req.assignee_user_id
+         LEFT JOIN app.request_slas sla ON sla.request_id = req.id
          WHERE req.id = $1",
         request_projection_sql()
     );
@@ -1187,7 +1979,7 @@ async fn fetch_visible_request(
     sqlx::query_as::<_, RequestRow>(&query)
         .bind(request_id)
         .bind(user_id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .ok_or_else(|| AppError::NotFound("request not found".to_string()))
 }
@@ -1218,6 +2010,31 @@ fn collect_changed_fields(
         fields.push("assignee_email".to_string());
         fields.push("assignee_display_name".to_string());
     }
+    if existing.checklist_required != updated.checklist_required {
+        fields.push("checklist_required".to_string());
+    }
+    if existing.sla_first_response_due_at != updated.sla_first_response_due_at {
+        fields.push("sla_first_response_due_at".to_string());
+    }
+    if existing.sla_resolution_due_at != updated.sla_resolution_due_at {
+        fields.push("sla_resolution_due_at".to_string());
+    }
+    if existing.sla_remaining_seconds.is_some()
+        != updated.sla_remaining_seconds.is_some()
+        || existing.sla_first_response_due_at
+            != updated.sla_first_response_due_at
+        || existing.sla_resolution_due_at != updated.sla_resolution_due_at
+    {
+        fields.push("sla_remaining_seconds".to_string());
+    }
+    if existing.sla_first_response_breached
+        != updated.sla_first_response_breached
+    {
+        fields.push("sla_first_response_breached".to_string());
+    }
+    if existing.sla_resolution_breached != updated.sla_resolution_breached {
+        fields.push("sla_resolution_breached".to_string());
+    }
     if existing.updated_at != updated.updated_at {
         fields.push("updated_at".to_string());
     }
@@ -1229,14 +2046,29 @@ fn collect_changed_fields(
     fields
 }
 
-async fn insert_audit_log(
-    pool: &PgPool,
+fn request_audit_snapshot(request: &RequestRow) -> serde_json::Value {
+    json!({
+        "title": request.title,
+        "description": request.description,
+        "category": request.category,
+        "status": request.status,
+        "priority": request.priority,
+        "assignee_email": request.assignee_email,
+        "checklist_required": request.checklist_required,
+    })
+}
+
+async fn insert_audit_log<'e, E>(
+    executor: E,
     request_id: Uuid,
     actor_user_id: Uuid,
     action: &str,
     old_value: serde_json::Value,
     new_value: serde_json::Value,
-) -> Result<AuditLogRow, AppError> {
+) -> Result<AuditLogRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
     sqlx::query_as::<_, AuditLogRow>(
         "WITH inserted AS (
            INSERT INTO app.request_audit_logs (
@@ -1273,22 +2105,25 @@ async fn insert_audit_log(
     .bind(action)
     .bind(old_value)
     .bind(new_value)
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await
     .map_err(AppError::from)
 }
 
-async fn fetch_request_recipient_ids(
-    pool: &PgPool,
+async fn fetch_request_recipient_ids<'e, E>(
+    executor: E,
     request_id: Uuid,
-) -> Result<Vec<Uuid>, AppError> {
+) -> Result<Vec<Uuid>, AppError>
+where
+    E: PgExecutor<'e>,
+{
     sqlx::query_scalar::<_, Uuid>(
         "SELECT user_id
          FROM app.request_participants
          WHERE request_id = $1",
     )
     .bind(request_id)
-    .fetch_all(pool)
+    .fetch_all(executor)
     .await
     .map_err(AppError::from)
 }
@@ -1312,26 +2147,61 @@ fn split_recipients_by_visibility(
     (existing, newly_visible)
 }
 
+const BATCH_EVENT_CHUNK: usize = 100;
+
+async fn publish_event_batches(
+    state: &AppState,
+    by_user: HashMap<Uuid, Vec<ServerEvent>>,
+) {
+    for (user_id, events) in by_user {
+        let webhook_envelopes: Vec<_> = events
+            .iter()
+            .filter(|event| event.is_webhook_event())
+            .filter_map(|event| event.to_envelope(None).ok())
+            .collect();
+        webhooks::enqueue(state, &[user_id], &webhook_envelopes).await;
+
+        let mut events = events.into_iter().peekable();
+        while events.peek().is_some() {
+            let chunk: Vec<ServerEvent> =
+                events.by_ref().take(BATCH_EVENT_CHUNK).collect();
+            publish_event(
+                state,
+                &[user_id],
+                ServerEvent::RequestBatch(RequestBatchEventPayload {
+                    events: chunk,
+                }),
+            )
+            .await;
+        }
+    }
+}
+
 async fn publish_event(
     state: &AppState,
     recipients: &[Uuid],
-    event_type: &str,
-    request_id: Option<Uuid>,
-    payload: serde_json::Value,
+    event: ServerEvent,
 ) {
     if recipients.is_empty() {
         return;
     }
 
-    let message = match EventEnvelope::new(
-        event_type.to_string(),
-        request_id,
-        None,
-        payload,
-    )
-    .encode()
-    {
-        Ok(encoded) => encoded,
+    let envelope = match event.to_envelope(None) {
+        Ok(envelope) => envelope,
+        Err(error) => {
+            warn!(error = %error, "failed to serialize websocket event");
+            return;
+        }
+    };
+    if event.is_webhook_event() {
+        webhooks::enqueue(state, recipients, std::slice::from_ref(&envelope))
+            .await;
+    }
+    let event_type = envelope.event_type.clone();
+    let request_id = envelope.request_id;
+
+    let message = match event.to_outbound(envelope) {
+        Ok(outbound) => Arc::new(outbound),
         Err(error) => {
             warn!(
                 event_type,
@@ -1358,6 +2228,32 @@ async fn publish_event(
     }
 }
 
+// Requests assigned to the user aliased `u`, as shown in assignee
+// suggestions and used by least-loaded routing.
+const ASSIGNMENT_COUNT_SQL: &str = "(
+  SELECT COUNT(*)::bigint
+  FROM app.requests assigned
+  WHERE assigned.assignee_user_id = u.id
+)";
+
+// Source text is HTML-escaped before highlighting so the only markup in
+// the snippet is `<mark>`.
+const SEARCH_SNIPPET_SQL: &str = "ts_headline(
+       'english',
+       replace(
+         replace(
+           replace(
+             concat_ws(' - ', req.title, req.description),
+             '&', '&amp;'
+           ),
+           '<', '&lt;'
+         ),
+         '>', '&gt;'
+       ),
+       search.query,
+       'StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2'
+     )";
+
 fn request_projection_sql() -> &'static str {
     "req.id,
      req.owner_user_id,
@@ -1376,14 +2272,57 @@ fn request_projection_sql() -> &'static str {
          'user'
        )
      END AS assignee_display_name,
+     (
+       SELECT COUNT(*)
+       FROM app.request_checklist_items item
+       WHERE item.request_id = req.id
+     ) AS checklist_total,
+     (
+       SELECT COUNT(*)
+       FROM app.request_checklist_items item
+       WHERE item.request_id = req.id AND item.done
+     ) AS checklist_done,
+     req.checklist_required,
+     sla.first_response_due_at AS sla_first_response_due_at,
+     sla.resolution_due_at AS sla_resolution_due_at,
+     CASE
+       WHEN sla.request_id IS NULL OR req.status = 'resolved' THEN NULL
+       WHEN sla.first_responded_at IS NULL THEN EXTRACT(
+         EPOCH FROM LEAST(sla.first_response_due_at, sla.resolution_due_at)
+           - NOW()
+       )::BIGINT
+       ELSE EXTRACT(EPOCH FROM sla.resolution_due_at - NOW())::BIGINT
+     END AS sla_remaining_seconds,
+     COALESCE(
+       COALESCE(sla.first_responded_at, NOW()) > sla.first_response_due_at,
+       FALSE
+     ) AS sla_first_response_breached,
+     COALESCE(
+       COALESCE(req.resolved_at, NOW()) > sla.resolution_due_at,
+       FALSE
+     ) AS sla_resolution_breached,
      req.created_at,
      req.updated_at"
 }
 
-async fn resolve_assignee_user_id(
-    pool: &PgPool,
+fn audit_log_projection_sql() -> &'static str {
+    "logs.id,
+     logs.request_id,
+     logs.actor_user_id,
+     COALESCE(actor.email, logs.actor_user_id::text) AS actor_email,
+     logs.action,
+     logs.old_value,
+     logs.new_value,
+     logs.created_at"
+}
+
+async fn resolve_assignee_user_id<'e, E>(
+    executor: E,
     assignee_email: Option<&str>,
-) -> Result<Option<Uuid>, AppError> {
+) -> Result<Option<Uuid>, AppError>
+where
+    E: PgExecutor<'e>,
+{
     let Some(email) = assignee_email else {
         return Ok(None);
     };
@@ -1395,7 +2334,7 @@ async fn resolve_assignee_user_id(
          LIMIT 1",
     )
     .bind(email)
-    .fetch_optional(pool)
+    .fetch_optional(executor)
     .await?;
 
     user_id
@@ -1458,6 +2397,39 @@ fn email_domain(email: &str) -> Option<String> {
     Some(normalized)
 }
 
+// Shared views and templates are usable by the whole workspace, but only
+// their owner may change them.
+fn ensure_owner(
+    owner_user_id: Uuid,
+    user: &AuthUserRow,
+    kind: &str,
+) -> Result<(), AppError> {
+    if owner_user_id != user.id {
+        return Err(AppError::NotFound(format!("{kind} not found")));
+    }
+
+    Ok(())
+}
+
+fn share_domain(
+    user: &AuthUserRow,
+    shared: bool,
+    kind: &str,
+) -> Result<Option<String>, AppError> {
+    if !shared {
+        return Ok(None);
+    }
+
+    email_domain(&user.email).map(Some).ok_or_else(|| {
+        AppError::Validation(vec![ErrorDetail {
+            field: "shared".to_string(),
+            message: format!(
+                "{kind}s can only be shared from an account with an email"
+            ),
+        }])
+    })
+}
+
 fn normalize_display_name(raw: Option<&str>) -> Result<String, AppError> {
     let Some(value) = raw else {
         return Err(AppError::Validation(vec![ErrorDetail {
@@ -1502,9 +2474,24 @@ fn normalize_preferences_update(
         email_digest: input.email_digest.unwrap_or(current.email_digest),
         browser_alerts: input.browser_alerts.unwrap_or(current.browser_alerts),
         default_page_size,
+        default_view_id: input
+            .default_view_id
+            .unwrap_or(current.default_view_id),
     })
 }
 
+// `Some(None)` is an explicit `null`; an absent field is `None` via
+// `#[serde(default)]`.
+fn deserialize_present<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 fn validate_create_input(input: &CreateRequestInput) -> Result<(), AppError> {
     let mut details = Vec::new();
 
@@ -1555,6 +2542,8 @@ fn validate_create_input(input: &CreateRequestInput) -> Result<(), AppError> {
         });
     }
 
+    details.extend(checklists::validate_titles("checklist", &input.checklist));
+
     if details.is_empty() {
         Ok(())
     } else {
@@ -1610,6 +2599,34 @@ mod tests {
         validate_priority("high").expect("high should pass");
     }
 
+    #[test]
+    fn parse_client_payload_rejects_wrong_shape() {
+        let err = parse_client_payload::<CreateRequestInput>(json!({
+            "title": 42
+        }))
+        .expect_err("invalid payload should fail");
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn nack_event_carries_op_id_and_error_details() {
+        let envelope = nack_event(
+            "op-7".to_string(),
+            None,
+            &AppError::Validation(vec![ErrorDetail {
+                field: "title".to_string(),
+                message: "title is required".to_string(),
+            }]),
+        )
+        .to_envelope(None)
+        .expect("nack should serialize");
+
+        assert_eq!(envelope.event_type, "nack");
+        assert_eq!(envelope.payload["op_id"], "op-7");
+        assert_eq!(envelope.payload["error"]["code"], "VALIDATION_ERROR");
+        assert_eq!(envelope.payload["error"]["details"][0]["field"], "title");
+    }
+
     #[test]
     fn validate_create_input_rejects_empty_title() {
         let input = CreateRequestInput {
@@ -1618,6 +2635,9 @@ mod tests {
             category: "IT".to_string(),
             priority: "low".to_string(),
             assignee_email: None,
+            checklist: Vec::new(),
+            checklist_required: false,
+            created_at: None,
         };
 
         let err =
@@ -1639,6 +2659,9 @@ mod tests {
             category: "IT".to_string(),
             priority: "medium".to_string(),
             assignee_email: None,
+            checklist: Vec::new(),
+            checklist_required: false,
+            created_at: None,
         };
 
         let err = validate_create_input(&input)
@@ -1654,6 +2677,9 @@ mod tests {
             category: "IT".to_string(),
             priority: "medium".to_string(),
             assignee_email: Some("invalid-email".to_string()),
+            checklist: Vec::new(),
+            checklist_required: false,
+            created_at: None,
         };
 
         let err = validate_create_input(&input)
@@ -1688,6 +2714,7 @@ mod tests {
             email_digest: true,
             browser_alerts: true,
             default_page_size: 20,
+            default_view_id: None,
         };
 
         let err = normalize_preferences_update(
@@ -1695,6 +2722,7 @@ mod tests {
                 email_digest: None,
                 browser_alerts: None,
                 default_page_size: Some(33),
+                default_view_id: None,
             },
             &current,
         )
@@ -1709,6 +2737,7 @@ mod tests {
             email_digest: true,
             browser_alerts: false,
             default_page_size: 20,
+            default_view_id: Some(Uuid::nil()),
         };
 
         let update = normalize_preferences_update(
@@ -1716,6 +2745,7 @@ mod tests {
                 email_digest: Some(false),
                 browser_alerts: None,
                 default_page_size: Some(50),
+                default_view_id: None,
             },
             &current,
         )
@@ -1724,6 +2754,7 @@ mod tests {
         assert!(!update.email_digest);
         assert!(!update.browser_alerts);
         assert_eq!(update.default_page_size, 50);
+        assert_eq!(update.default_view_id, Some(Uuid::nil()));
     }
 
     #[test]
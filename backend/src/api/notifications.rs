@@ -0,0 +1,333 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::{FromRow, PgExecutor};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::events::{
+    NotificationEventPayload, NotificationsReadEventPayload, ServerEvent,
+};
+use super::{
+    RequestRow, pagination, publish_event, require_authenticated_user,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
+pub(super) struct NotificationRow {
+    id: Uuid,
+    user_id: Uuid,
+    kind: String,
+    pub(super) request_id: Uuid,
+    request_title: String,
+    actor_user_id: Option<Uuid>,
+    actor_display_name: Option<String>,
+    data: Value,
+    read_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ListNotificationsQuery {
+    unread_only: Option<bool>,
+    limit: Option<u64>,
+    cursor: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+struct UnreadCount {
+    unread: i64,
+}
+
+fn notification_select_sql() -> &'static str {
+    "SELECT
+       notification.id,
+       notification.user_id,
+       notification.kind,
+       notification.request_id,
+       req.title AS request_title,
+       notification.actor_user_id,
+       actor.display_name AS actor_display_name,
+       notification.data,
+       notification.read_at,
+       notification.created_at
+     FROM app.notifications notification
+     JOIN app.requests req ON req.id = notification.request_id
+     LEFT JOIN app.app_users actor ON actor.id = notification.actor_user_id"
+}
+
+pub(super) async fn list_notifications(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Query(query): Query<ListNotificationsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+
+    if let Some(cursor) = query.cursor {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+               SELECT 1 FROM app.notifications WHERE id = $1 AND user_id = $2
+             )",
+        )
+        .bind(cursor)
+        .bind(user.id)
+        .fetch_one(&state.db)
+        .await?;
+        if !exists {
+            return Err(AppError::Validation(vec![ErrorDetail {
+                field: "cursor".to_string(),
+                message: "cursor does not match a notification".to_string(),
+            }]));
+        }
+    }
+
+    let sql = format!(
+        "{}
+         WHERE notification.user_id = $1
+           AND (NOT $2 OR notification.read_at IS NULL)
+           AND (
+             $3::uuid IS NULL
+             OR (notification.created_at, notification.id) < (
+               SELECT created_at, id FROM app.notifications WHERE id = $3
+             )
+           )
+         ORDER BY notification.created_at DESC, notification.id DESC
+         LIMIT $4",
+        notification_select_sql()
+    );
+    let items = sqlx::query_as::<_, NotificationRow>(&sql)
+        .bind(user.id)
+        .bind(query.unread_only.unwrap_or(false))
+        .bind(query.cursor)
+        .bind(pagination::page_fetch_limit(limit))
+        .fetch_all(&state.db)
+        .await?;
+    let (items, next_cursor) =
+        pagination::split_page(items, limit, |item| item.id.to_string());
+
+    Ok(response::list(items, None, limit, None, next_cursor))
+}
+
+pub(super) async fn get_unread_count(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let unread = fetch_unread_count(&state.db, user.id).await?;
+
+    Ok(response::ok(StatusCode::OK, UnreadCount { unread }))
+}
+
+pub(super) async fn mark_read(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let updated = sqlx::query(
+        "UPDATE app.notifications
+         SET read_at = COALESCE(read_at, NOW())
+         WHERE id = $1 AND user_id = $2",
+    )
+    .bind(id)
+    .bind(user.id)
+    .execute(&state.db)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        return Err(AppError::NotFound("notification not found".to_string()));
+    }
+
+    let sql =
+        format!("{} WHERE notification.id = $1", notification_select_sql());
+    let notification = sqlx::query_as::<_, NotificationRow>(&sql)
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+    publish_read(&state, user.id, vec![id]).await?;
+
+    Ok(response::ok(StatusCode::OK, notification))
+}
+
+pub(super) async fn mark_all_read(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let ids: Vec<Uuid> = sqlx::query_scalar(
+        "UPDATE app.notifications
+         SET read_at = NOW()
+         WHERE user_id = $1 AND read_at IS NULL
+         RETURNING id",
+    )
+    .bind(user.id)
+    .fetch_all(&state.db)
+    .await?;
+    let updated = ids.len();
+    publish_read(&state, user.id, ids).await?;
+
+    Ok(response::ok(StatusCode::OK, json!({ "updated": updated })))
+}
+
+pub(super) async fn notify_request_change(
+    state: &AppState,
+    actor_user_id: Uuid,
+    existing: Option<&RequestRow>,
+    updated: &RequestRow,
+    recipients: &[Uuid],
+) -> Result<(), AppError> {
+    let previous_assignee = existing.and_then(|row| row.assignee_user_id);
+    if let Some(assignee) = updated.assignee_user_id
+        && previous_assignee != Some(assignee)
+    {
+        notify(
+            state,
+            "assigned",
+            updated.id,
+            Some(actor_user_id),
+            json!({}),
+            &[assignee],
+        )
+        .await?;
+    }
+
+    if let Some(existing) = existing
+        && existing.status != updated.status
+    {
+        notify(
+            state,
+            "status_changed",
+            updated.id,
+            Some(actor_user_id),
+            json!({ "from": existing.status, "to": updated.status }),
+            recipients,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub(super) async fn notify(
+    state: &AppState,
+    kind: &str,
+    request_id: Uuid,
+    actor_user_id: Option<Uuid>,
+    data: Value,
+    recipients: &[Uuid],
+) -> Result<(), AppError> {
+    let recipients: Vec<Uuid> = recipients
+        .iter()
+        .copied()
+        .filter(|user_id| Some(*user_id) != actor_user_id)
+        .collect();
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = sqlx::query_scalar(
+        "INSERT INTO app.notifications (
+           user_id,
+           kind,
+           request_id,
+           actor_user_id,
+           data
+         )
+         SELECT DISTINCT recipient, $2, $3, $4, $5
+         FROM UNNEST($1::uuid[]) AS recipients(recipient)
+         RETURNING id",
+    )
+    .bind(&recipients)
+    .bind(kind)
+    .bind(request_id)
+    .bind(actor_user_id)
+    .bind(&data)
+    .fetch_all(&state.db)
+    .await?;
+
+    let sql = format!(
+        "{}
+         WHERE notification.id = ANY($1)
+           AND NOT EXISTS (
+             SELECT 1
+             FROM app.user_preferences prefs
+             WHERE prefs.user_id = notification.user_id
+               AND NOT prefs.browser_alerts
+           )",
+        notification_select_sql()
+    );
+    let alerts = sqlx::query_as::<_, NotificationRow>(&sql)
+        .bind(&ids)
+        .fetch_all(&state.db)
+        .await?;
+    for notification in alerts {
+        let recipient = notification.user_id;
+        publish_event(
+            state,
+            &[recipient],
+            ServerEvent::NotificationCreated(NotificationEventPayload {
+                notification,
+            }),
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+async fn publish_read(
+    state: &AppState,
+    user_id: Uuid,
+    notification_ids: Vec<Uuid>,
+) -> Result<(), AppError> {
+    let unread_count = fetch_unread_count(&state.db, user_id).await?;
+    publish_event(
+        state,
+        &[user_id],
+        ServerEvent::NotificationsRead(NotificationsReadEventPayload {
+            notification_ids,
+            unread_count,
+        }),
+    )
+    .await;
+
+    Ok(())
+}
+
+async fn fetch_unread_count<'e, E>(
+    executor: E,
+    user_id: Uuid,
+) -> Result<i64, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_scalar(
+        "SELECT COUNT(*)
+         FROM app.notifications
+         WHERE user_id = $1 AND read_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_one(executor)
+    .await
+    .map_err(AppError::from)
+}
@@ -0,0 +1,348 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use super::RequestListItem;
+use crate::error::{AppError, ErrorDetail};
+
+// Listings fetch one row past the page; getting it back means another page
+// exists.
+pub(super) fn page_fetch_limit(limit: u64) -> i64 {
+    limit as i64 + 1
+}
+
+pub(super) fn split_page<T, C>(
+    mut items: Vec<T>,
+    limit: u64,
+    cursor_after: impl FnOnce(&T) -> C,
+) -> (Vec<T>, Option<C>) {
+    if items.len() <= limit as usize {
+        return (items, None);
+    }
+
+    items.truncate(limit as usize);
+    let next_cursor = items.last().map(cursor_after);
+    (items, next_cursor)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    CreatedAt,
+    UpdatedAt,
+    Priority,
+    Status,
+    Title,
+    Relevance,
+}
+
+impl SortKey {
+    fn name(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+            Self::Priority => "priority",
+            Self::Status => "status",
+            Self::Title => "title",
+            Self::Relevance => "relevance",
+        }
+    }
+
+    // Priority and status sort by workflow rank rather than alphabetically.
+    fn expression(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "req.created_at",
+            Self::UpdatedAt => "req.updated_at",
+            Self::Priority => {
+                "CASE req.priority
+                   WHEN 'low' THEN 0
+                   WHEN 'medium' THEN 1
+                   ELSE 2
+                 END"
+            }
+            Self::Status => {
+                "CASE req.status
+                   WHEN 'open' THEN 0
+                   WHEN 'in_progress' THEN 1
+                   ELSE 2
+                 END"
+            }
+            Self::Title => "req.title",
+            Self::Relevance => "ts_rank_cd(search.document, search.query)",
+        }
+    }
+
+    fn cursor_cast(self) -> &'static str {
+        match self {
+            Self::CreatedAt | Self::UpdatedAt => "::timestamptz",
+            Self::Priority | Self::Status => "::integer",
+            Self::Title => "::text",
+            Self::Relevance => "::real",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct RequestSort {
+    key: SortKey,
+    descending: bool,
+}
+
+impl RequestSort {
+    pub(super) fn parse(
+        value: Option<&str>,
+        searching: bool,
+    ) -> Result<Self, AppError> {
+        let Some(value) =
+            value.map(str::trim).filter(|value| !value.is_empty())
+        else {
+            return Ok(Self::default());
+        };
+
+        if value == "relevance" {
+            return Ok(if searching {
+                Self {
+                    key: SortKey::Relevance,
+                    descending: true,
+                }
+            } else {
+                Self::default()
+            });
+        }
+
+        let (descending, name) = match value.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, value),
+        };
+        let key = match name {
+            "created_at" => SortKey::CreatedAt,
+            "updated_at" => SortKey::UpdatedAt,
+            "priority" => SortKey::Priority,
+            "status" => SortKey::Status,
+            "title" => SortKey::Title,
+            _ => {
+                return Err(AppError::Validation(vec![ErrorDetail {
+                    field: "sort".to_string(),
+                    message: "sort must be one of created_at, updated_at, \
+                              priority, status, title or relevance, \
+                              optionally prefixed with -"
+                        .to_string(),
+                }]));
+            }
+        };
+
+        Ok(Self { key, descending })
+    }
+
+    fn token(self) -> String {
+        match (self.key, self.descending) {
+            (SortKey::Relevance, _) => SortKey::Relevance.name().to_string(),
+            (key, true) => format!("-{}", key.name()),
+            (key, false) => key.name().to_string(),
+        }
+    }
+
+    pub(super) fn push_order_by(
+        self,
+        builder: &mut QueryBuilder<'_, Postgres>,
+    ) {
+        let direction = if self.descending { "DESC" } else { "ASC" };
+        builder
+            .push(" ORDER BY ")
+            .push(self.key.expression())
+            .push(format_args!(" {direction}, req.id {direction}"));
+    }
+
+    pub(super) fn push_keyset(
+        self,
+        builder: &mut QueryBuilder<'_, Postgres>,
+        cursor: &RequestCursor,
+    ) {
+        let comparison = if self.descending { "<" } else { ">" };
+        builder
+            .push(" AND (")
+            .push(self.key.expression())
+            .push(format_args!(", req.id) {comparison} ("))
+            .push_bind(cursor.value.clone())
+            .push(self.key.cursor_cast())
+            .push(", ")
+            .push_bind(cursor.id)
+            .push(")");
+    }
+
+    pub(super) fn cursor_after(self, item: &RequestListItem) -> RequestCursor {
+        let request = &item.request;
+        let value = match self.key {
+            SortKey::CreatedAt => format_timestamp(request.created_at),
+            SortKey::UpdatedAt => format_timestamp(request.updated_at),
+            SortKey::Priority => priority_rank(&request.priority).to_string(),
+            SortKey::Status => status_rank(&request.status).to_string(),
+            SortKey::Title => request.title.clone(),
+            SortKey::Relevance => {
+                item.search_rank.unwrap_or_default().to_string()
+            }
+        };
+
+        RequestCursor {
+            sort: self.token(),
+            value,
+            id: request.id,
+        }
+    }
+}
+
+impl Default for RequestSort {
+    fn default() -> Self {
+        Self {
+            key: SortKey::CreatedAt,
+            descending: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct RequestCursor {
+    #[serde(rename = "s")]
+    sort: String,
+    #[serde(rename = "v")]
+    value: String,
+    id: Uuid,
+}
+
+impl RequestCursor {
+    pub(super) fn encode(&self) -> String {
+        let json =
+            serde_json::to_vec(self).expect("cursor serialization is total");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    // Cursors name the sort they were minted for, so one cannot be replayed
+    // against a different order.
+    pub(super) fn decode(
+        raw: &str,
+        sort: RequestSort,
+    ) -> Result<Self, AppError> {
+        let cursor = URL_SAFE_NO_PAD
+            .decode(raw)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+            .ok_or_else(|| invalid_cursor("cursor is malformed"))?;
+
+        if cursor.sort != sort.token() {
+            return Err(invalid_cursor(
+                "cursor does not match the requested sort",
+            ));
+        }
+
+        let value_is_valid = match sort.key {
+            SortKey::CreatedAt | SortKey::UpdatedAt => {
+                DateTime::parse_from_rfc3339(&cursor.value).is_ok()
+            }
+            SortKey::Priority | SortKey::Status => {
+                cursor.value.parse::<i32>().is_ok()
+            }
+            SortKey::Title => true,
+            SortKey::Relevance => cursor.value.parse::<f32>().is_ok(),
+        };
+        if !value_is_valid {
+            return Err(invalid_cursor("cursor is malformed"));
+        }
+
+        Ok(cursor)
+    }
+}
+
+fn priority_rank(priority: &str) -> i32 {
+    match priority {
+        "low" => 0,
+        "medium" => 1,
+        _ => 2,
+    }
+}
+
+fn status_rank(status: &str) -> i32 {
+    match status {
+        "open" => 0,
+        "in_progress" => 1,
+        _ => 2,
+    }
+}
+
+fn format_timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn invalid_cursor(message: &str) -> AppError {
+    AppError::Validation(vec![ErrorDetail {
+        field: "cursor".to_string(),
+        message: message.to_string(),
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_page_keeps_limit_and_cursors_only_on_overflow() {
+        let (items, next) = split_page(vec![1, 2, 3], 2, |item| *item);
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(next, Some(2));
+
+        let (items, next) = split_page(vec![1, 2], 2, |item| *item);
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn sort_parses_direction_and_rejects_unknown_keys() {
+        let sort = RequestSort::parse(Some("-priority"), false)
+            .expect("-priority should parse");
+        assert_eq!(sort.key, SortKey::Priority);
+        assert!(sort.descending);
+
+        let sort = RequestSort::parse(Some("relevance"), false)
+            .expect("relevance should parse");
+        assert_eq!(sort, RequestSort::default());
+
+        let err = RequestSort::parse(Some("owner_user_id; DROP"), false)
+            .expect_err("unknown sort should fail");
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn cursor_round_trips_for_matching_sort() {
+        let sort = RequestSort::parse(Some("-updated_at"), false)
+            .expect("sort should parse");
+        let cursor = RequestCursor {
+            sort: sort.token(),
+            value: "2026-03-07T22:00:00.123456Z".to_string(),
+            id: Uuid::new_v4(),
+        };
+
+        let decoded = RequestCursor::decode(&cursor.encode(), sort)
+            .expect("cursor should decode");
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn cursor_rejects_sort_mismatch_and_garbage() {
+        let cursor = RequestCursor {
+            sort: RequestSort::default().token(),
+            value: "2026-03-07T22:00:00Z".to_string(),
+            id: Uuid::new_v4(),
+        };
+        let ascending = RequestSort::parse(Some("created_at"), false)
+            .expect("sort should parse");
+
+        let err = RequestCursor::decode(&cursor.encode(), ascending)
+            .expect_err("sort mismatch should fail");
+        assert!(matches!(err, AppError::Validation(_)));
+
+        let err = RequestCursor::decode("not-a-cursor", RequestSort::default())
+            .expect_err("garbage should fail");
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+}
@@ -0,0 +1,707 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::{FromRow, PgConnection, PgExecutor};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::{
+    ASSIGNMENT_COUNT_SQL, deserialize_present, is_valid_email,
+    require_authenticated_user, sla, validate_category,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const MAX_MEMBERS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum RoutingStrategy {
+    RoundRobin,
+    LeastLoaded,
+    Fixed,
+}
+
+impl RoutingStrategy {
+    const VALUES: &str = "round_robin, least_loaded, fixed";
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "round_robin" => Some(Self::RoundRobin),
+            "least_loaded" => Some(Self::LeastLoaded),
+            "fixed" => Some(Self::Fixed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+struct RoutingRuleRow {
+    id: Uuid,
+    workspace_domain: String,
+    category: Option<String>,
+    strategy: String,
+    active: bool,
+    assignee_emails: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateRuleInput {
+    category: Option<String>,
+    strategy: String,
+    active: Option<bool>,
+    assignee_emails: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateRuleInput {
+    #[serde(default, deserialize_with = "deserialize_present")]
+    category: Option<Option<String>>,
+    strategy: Option<String>,
+    active: Option<bool>,
+    assignee_emails: Option<Vec<String>>,
+}
+
+#[derive(Debug)]
+struct RuleFields {
+    category: Option<String>,
+    strategy: String,
+    active: bool,
+    assignee_emails: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct RoutedAssignee {
+    pub(super) user_id: Uuid,
+    pub(super) rule_id: Uuid,
+    pub(super) strategy: RoutingStrategy,
+}
+
+impl RoutedAssignee {
+    pub(super) fn audit_value(self) -> Value {
+        json!({
+            "rule_id": self.rule_id,
+            "strategy": self.strategy,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct MatchedRule {
+    id: Uuid,
+    strategy: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct RoutingMember {
+    user_id: Uuid,
+    available: bool,
+    assignment_count: i64,
+    last_routed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+struct AvailabilityResponse {
+    available: bool,
+    out_of_office_until: Option<DateTime<Utc>>,
+    out_of_office: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateAvailabilityInput {
+    available: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    out_of_office_until: Option<Option<DateTime<Utc>>>,
+}
+
+const RULE_SELECT_SQL: &str = "SELECT
+       rule.id,
+       rule.workspace_domain,
+       rule.category,
+       rule.strategy,
+       rule.active,
+       ARRAY(
+         SELECT COALESCE(member_user.email, '')
+         FROM app.routing_rule_members member
+         JOIN app.app_users member_user ON member_user.id = member.user_id
+         WHERE member.rule_id = rule.id
+         ORDER BY member.position ASC
+       ) AS assignee_emails,
+       rule.created_at,
+       rule.updated_at
+     FROM app.routing_rules rule";
+
+const ROUTABLE_USER_SQL: &str = "u.is_active
+       AND u.deleted_at IS NULL
+       AND u.available
+       AND (u.out_of_office_until IS NULL OR u.out_of_office_until <= NOW())";
+
+pub(super) async fn list_rules(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let workspace = sla::workspace_of(&user)?;
+
+    let rules = sqlx::query_as::<_, RoutingRuleRow>(&format!(
+        "{RULE_SELECT_SQL}
+         WHERE rule.workspace_domain = $1
+         ORDER BY rule.category ASC NULLS LAST, rule.id ASC"
+    ))
+    .bind(workspace)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, rules))
+}
+
+pub(super) async fn create_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreateRuleInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        sla::admin_workspace_of(&state.db, &user, "routing rules").await?;
+
+    let fields = validate_rule(RuleFields {
+        category: input.category,
+        strategy: input.strategy,
+        active: input.active.unwrap_or(true),
+        assignee_emails: input.assignee_emails,
+    })?;
+    let members =
+        resolve_members(&state.db, &workspace, &fields.assignee_emails).await?;
+
+    let mut tx = state.db.begin().await?;
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.routing_rules (
+           workspace_domain,
+           category,
+           strategy,
+           active,
+           created_by_user_id
+         )
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING id",
+    )
+    .bind(&workspace)
+    .bind(&fields.category)
+    .bind(&fields.strategy)
+    .bind(fields.active)
+    .bind(user.id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(map_routing_write_error)?;
+    replace_members(&mut tx, id, &members).await?;
+    let rule = fetch_rule(&mut *tx, &workspace, id).await?;
+    tx.commit().await?;
+
+    Ok(response::ok(StatusCode::CREATED, rule))
+}
+
+pub(super) async fn get_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let rule = fetch_rule(&state.db, &sla::workspace_of(&user)?, id).await?;
+
+    Ok(response::ok(StatusCode::OK, rule))
+}
+
+pub(super) async fn update_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateRuleInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        sla::admin_workspace_of(&state.db, &user, "routing rules").await?;
+
+    let existing = fetch_rule(&state.db, &workspace, id).await?;
+    let fields = validate_rule(RuleFields {
+        category: input.category.unwrap_or(existing.category),
+        strategy: input.strategy.unwrap_or(existing.strategy),
+        active: input.active.unwrap_or(existing.active),
+        assignee_emails: input
+            .assignee_emails
+            .unwrap_or(existing.assignee_emails),
+    })?;
+    let members =
+        resolve_members(&state.db, &workspace, &fields.assignee_emails).await?;
+
+    let mut tx = state.db.begin().await?;
+    sqlx::query(
+        "UPDATE app.routing_rules
+         SET category = $2,
+             strategy = $3,
+             active = $4
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(&fields.category)
+    .bind(&fields.strategy)
+    .bind(fields.active)
+    .execute(&mut *tx)
+    .await
+    .map_err(map_routing_write_error)?;
+    replace_members(&mut tx, id, &members).await?;
+    let rule = fetch_rule(&mut *tx, &workspace, id).await?;
+    tx.commit().await?;
+
+    Ok(response::ok(StatusCode::OK, rule))
+}
+
+pub(super) async fn delete_rule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let workspace =
+        sla::admin_workspace_of(&state.db, &user, "routing rules").await?;
+    let existing = fetch_rule(&state.db, &workspace, id).await?;
+    sqlx::query("DELETE FROM app.routing_rules WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn get_availability(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let availability = fetch_availability(&state.db, user.id).await?;
+
+    Ok(response::ok(StatusCode::OK, availability))
+}
+
+pub(super) async fn update_availability(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<UpdateAvailabilityInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let current = fetch_availability(&state.db, user.id).await?;
+    sqlx::query(
+        "UPDATE app.app_users
+         SET available = $2,
+             out_of_office_until = $3,
+             updated_at = NOW()
+         WHERE id = $1",
+    )
+    .bind(user.id)
+    .bind(input.available.unwrap_or(current.available))
+    .bind(
+        input
+            .out_of_office_until
+            .unwrap_or(current.out_of_office_until),
+    )
+    .execute(&state.db)
+    .await?;
+    let availability = fetch_availability(&state.db, user.id).await?;
+
+    Ok(response::ok(StatusCode::OK, availability))
+}
+
+pub(super) async fn route_assignee(
+    conn: &mut PgConnection,
+    owner_user_id: Uuid,
+    category: &str,
+) -> Result<Option<RoutedAssignee>, AppError> {
+    let Some(rule) = sqlx::query_as::<_, MatchedRule>(
+        "SELECT rule.id, rule.strategy
+         FROM app.routing_rules rule
+         JOIN app.app_users owner
+           ON lower(btrim(split_part(owner.email, '@', 2))) = rule.workspace_domain
+         WHERE owner.id = $1
+           AND rule.active
+           AND (rule.category = $2 OR rule.category IS NULL)
+         ORDER BY rule.category IS NULL ASC
+         LIMIT 1
+         FOR UPDATE OF rule",
+    )
+    .bind(owner_user_id)
+    .bind(category)
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let Some(strategy) = RoutingStrategy::parse(&rule.strategy) else {
+        return Ok(None);
+    };
+
+    let members = sqlx::query_as::<_, RoutingMember>(&format!(
+        "SELECT
+           member.user_id,
+           ({ROUTABLE_USER_SQL}) AS available,
+           {ASSIGNMENT_COUNT_SQL} AS assignment_count,
+           member.last_routed_at
+         FROM app.routing_rule_members member
+         JOIN app.app_users u ON u.id = member.user_id
+         WHERE member.rule_id = $1
+         ORDER BY member.position ASC"
+    ))
+    .bind(rule.id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let Some(user_id) = pick_assignee(strategy, &members) else {
+        return Ok(None);
+    };
+
+    sqlx::query(
+        "UPDATE app.routing_rule_members
+         SET last_routed_at = clock_timestamp()
+         WHERE rule_id = $1 AND user_id = $2",
+    )
+    .bind(rule.id)
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(Some(RoutedAssignee {
+        user_id,
+        rule_id: rule.id,
+        strategy,
+    }))
+}
+
+fn pick_assignee(
+    strategy: RoutingStrategy,
+    members: &[RoutingMember],
+) -> Option<Uuid> {
+    let mut available = members
+        .iter()
+        .enumerate()
+        .filter(|(_, member)| member.available);
+    let picked = match strategy {
+        RoutingStrategy::Fixed => available.next(),
+        RoutingStrategy::LeastLoaded => {
+            available.min_by_key(|(position, member)| {
+                (member.assignment_count, *position)
+            })
+        }
+        // Never-routed members sort first, then whoever waited longest.
+        RoutingStrategy::RoundRobin => {
+            available.min_by_key(|(position, member)| {
+                (member.last_routed_at, *position)
+            })
+        }
+    };
+
+    picked.map(|(_, member)| member.user_id)
+}
+
+async fn fetch_rule<'e, E>(
+    executor: E,
+    workspace: &str,
+    id: Uuid,
+) -> Result<RoutingRuleRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, RoutingRuleRow>(&format!(
+        "{RULE_SELECT_SQL}
+         WHERE rule.id = $1 AND rule.workspace_domain = $2"
+    ))
+    .bind(id)
+    .bind(workspace)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("routing rule not found".to_string()))
+}
+
+async fn fetch_availability<'e, E>(
+    executor: E,
+    user_id: Uuid,
+) -> Result<AvailabilityResponse, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, AvailabilityResponse>(
+        "SELECT
+           available,
+           out_of_office_until,
+           COALESCE(out_of_office_until > NOW(), FALSE) AS out_of_office
+         FROM app.app_users
+         WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::Unauthorized("auth user not found".to_string()))
+}
+
+async fn resolve_members<'e, E>(
+    executor: E,
+    workspace: &str,
+    emails: &[String],
+) -> Result<Vec<Uuid>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let found = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT id, lower(email)
+         FROM app.app_users
+         WHERE lower(email) = ANY($1)
+           AND lower(btrim(split_part(email, '@', 2))) = $2
+           AND is_active
+           AND deleted_at IS NULL",
+    )
+    .bind(emails)
+    .bind(workspace)
+    .fetch_all(executor)
+    .await?;
+
+    let mut members = Vec::with_capacity(emails.len());
+    let mut details = Vec::new();
+    for (index, email) in emails.iter().enumerate() {
+        match found.iter().find(|(_, found_email)| found_email == email) {
+            Some((id, _)) => members.push(*id),
+            None => details.push(ErrorDetail {
+                field: format!("assignee_emails[{index}]"),
+                message: "No user in this workspace has this email address"
+                    .to_string(),
+            }),
+        }
+    }
+
+    if details.is_empty() {
+        Ok(members)
+    } else {
+        Err(AppError::Validation(details))
+    }
+}
+
+async fn replace_members(
+    conn: &mut PgConnection,
+    rule_id: Uuid,
+    members: &[Uuid],
+) -> Result<(), AppError> {
+    sqlx::query(
+        "DELETE FROM app.routing_rule_members
+         WHERE rule_id = $1 AND NOT (user_id = ANY($2))",
+    )
+    .bind(rule_id)
+    .bind(members)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "INSERT INTO app.routing_rule_members (rule_id, user_id, position)
+         SELECT $1, member.user_id, (member.position - 1)::integer
+         FROM unnest($2::uuid[]) WITH ORDINALITY AS member(user_id, position)
+         ON CONFLICT (rule_id, user_id) DO UPDATE
+         SET position = EXCLUDED.position",
+    )
+    .bind(rule_id)
+    .bind(members)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+fn validate_rule(mut fields: RuleFields) -> Result<RuleFields, AppError> {
+    let mut details = Vec::new();
+    let mut invalid = |field: String, message: String| {
+        details.push(ErrorDetail { field, message });
+    };
+
+    if let Some(category) = &fields.category
+        && validate_category(category).is_err()
+    {
+        invalid(
+            "category".to_string(),
+            "category must be one of IT, Ops, Admin, HR".to_string(),
+        );
+    }
+    if RoutingStrategy::parse(&fields.strategy).is_none() {
+        invalid(
+            "strategy".to_string(),
+            format!("strategy must be one of {}", RoutingStrategy::VALUES),
+        );
+    }
+
+    fields.assignee_emails = fields
+        .assignee_emails
+        .iter()
+        .map(|email| email.trim().to_lowercase())
+        .collect();
+    if fields.assignee_emails.is_empty()
+        || fields.assignee_emails.len() > MAX_MEMBERS
+    {
+        invalid(
+            "assignee_emails".to_string(),
+            format!("assignee_emails must list 1-{MAX_MEMBERS} users"),
+        );
+    }
+    for (index, email) in fields.assignee_emails.iter().enumerate() {
+        if !is_valid_email(email) {
+            invalid(
+                format!("assignee_emails[{index}]"),
+                "must be a valid email address".to_string(),
+            );
+        } else if fields.assignee_emails[..index].contains(email) {
+            invalid(
+                format!("assignee_emails[{index}]"),
+                "is listed more than once".to_string(),
+            );
+        }
+    }
+
+    if details.is_empty() {
+        Ok(fields)
+    } else {
+        Err(AppError::Validation(details))
+    }
+}
+
+fn map_routing_write_error(error: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_error) = &error
+        && db_error.constraint()
+            == Some("idx_routing_rules_workspace_category_unique")
+    {
+        return AppError::Validation(vec![ErrorDetail {
+            field: "category".to_string(),
+            message: "a routing rule for this category already exists"
+                .to_string(),
+        }]);
+    }
+
+    AppError::Database(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn member(
+        id: u128,
+        available: bool,
+        assignment_count: i64,
+        last_routed_minute: Option<u32>,
+    ) -> RoutingMember {
+        RoutingMember {
+            user_id: Uuid::from_u128(id),
+            available,
+            assignment_count,
+            last_routed_at: last_routed_minute.map(|minute| {
+                Utc.with_ymd_and_hms(2026, 3, 2, 9, minute, 0).unwrap()
+            }),
+        }
+    }
+
+    #[test]
+    fn fixed_routes_to_the_first_available_member() {
+        let members = [member(1, false, 0, None), member(2, true, 9, None)];
+
+        assert_eq!(
+            pick_assignee(RoutingStrategy::Fixed, &members),
+            Some(Uuid::from_u128(2))
+        );
+    }
+
+    #[test]
+    fn least_loaded_breaks_ties_in_rule_order() {
+        let members = [
+            member(1, true, 4, None),
+            member(2, true, 2, None),
+            member(3, true, 2, None),
+            member(4, false, 0, None),
+        ];
+
+        assert_eq!(
+            pick_assignee(RoutingStrategy::LeastLoaded, &members),
+            Some(Uuid::from_u128(2))
+        );
+    }
+
+    #[test]
+    fn round_robin_routes_to_whoever_waited_longest() {
+        let members = [
+            member(1, true, 0, Some(30)),
+            member(2, true, 0, Some(10)),
+            member(3, false, 0, None),
+        ];
+        assert_eq!(
+            pick_assignee(RoutingStrategy::RoundRobin, &members),
+            Some(Uuid::from_u128(2))
+        );
+
+        let joined = [member(1, true, 0, Some(30)), member(2, true, 0, None)];
+        assert_eq!(
+            pick_assignee(RoutingStrategy::RoundRobin, &joined),
+            Some(Uuid::from_u128(2))
+        );
+    }
+
+    #[test]
+    fn nobody_is_picked_when_every_member_is_away() {
+        let members = [member(1, false, 0, None)];
+
+        assert_eq!(pick_assignee(RoutingStrategy::Fixed, &members), None);
+    }
+
+    #[test]
+    fn validate_rule_reports_bad_and_duplicate_members() {
+        let err = validate_rule(RuleFields {
+            category: Some("Sales".to_string()),
+            strategy: "random".to_string(),
+            active: true,
+            assignee_emails: vec![
+                " Ana@Example.com ".to_string(),
+                "ana@example.com".to_string(),
+                "nobody".to_string(),
+            ],
+        })
+        .expect_err("rule should fail");
+
+        let AppError::Validation(details) = err else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> =
+            details.iter().map(|detail| detail.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "category",
+                "strategy",
+                "assignee_emails[1]",
+                "assignee_emails[2]"
+            ]
+        );
+    }
+}
@@ -0,0 +1,850 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{
+    DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, PgConnection, PgPool, types::Json as SqlJson};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use tower_sessions::Session;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{
+    AuditLogRow, AuthUserRow, CreateRequestInput, RequestRow, checklists,
+    create_request_record, fetch_visible_request, publish_request_created,
+    require_authenticated_user, templates, validate_create_input, workers,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+const SCHEDULER_BATCH: i64 = 100;
+// Every valid rule fires at least once a year.
+const MAX_LOOKAHEAD_DAYS: u64 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct RecurrenceRule {
+    frequency: Frequency,
+    by_day: Vec<Weekday>,
+    by_month_day: Vec<i32>,
+    hour: u32,
+    minute: u32,
+}
+
+impl RecurrenceRule {
+    pub(super) fn parse(raw: &str) -> Result<Self, String> {
+        let trimmed = raw.trim();
+        let body = trimmed
+            .get(..6)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("RRULE:"))
+            .map_or(trimmed, |_| &trimmed[6..]);
+
+        let mut frequency = None;
+        let mut by_day = None;
+        let mut by_month_day = None;
+        let mut hour = None;
+        let mut minute = None;
+        for part in body.split(';').filter(|part| !part.trim().is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("{part} must be KEY=VALUE"))?;
+            let key = key.trim().to_ascii_uppercase();
+            let value = value.trim();
+            let duplicate = match key.as_str() {
+                "FREQ" => frequency.replace(parse_frequency(value)?).is_some(),
+                "BYDAY" => by_day.replace(parse_by_day(value)?).is_some(),
+                "BYMONTHDAY" => {
+                    by_month_day.replace(parse_by_month_day(value)?).is_some()
+                }
+                "BYHOUR" => {
+                    hour.replace(parse_bounded(value, "BYHOUR", 23)?).is_some()
+                }
+                "BYMINUTE" => minute
+                    .replace(parse_bounded(value, "BYMINUTE", 59)?)
+                    .is_some(),
+                _ => return Err(format!("{key} is not supported")),
+            };
+            if duplicate {
+                return Err(format!("{key} is given more than once"));
+            }
+        }
+
+        let frequency = frequency.ok_or("FREQ is required")?;
+        let by_day = by_day.unwrap_or_default();
+        let by_month_day = by_month_day.unwrap_or_default();
+        match frequency {
+            Frequency::Daily if !by_month_day.is_empty() => {
+                return Err("BYMONTHDAY requires FREQ=MONTHLY".to_string());
+            }
+            Frequency::Weekly if by_day.is_empty() => {
+                return Err("FREQ=WEEKLY requires BYDAY".to_string());
+            }
+            Frequency::Weekly if !by_month_day.is_empty() => {
+                return Err("BYMONTHDAY requires FREQ=MONTHLY".to_string());
+            }
+            Frequency::Monthly if by_month_day.is_empty() => {
+                return Err("FREQ=MONTHLY requires BYMONTHDAY".to_string());
+            }
+            Frequency::Monthly if !by_day.is_empty() => {
+                return Err(
+                    "BYDAY is not supported with FREQ=MONTHLY".to_string()
+                );
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            frequency,
+            by_day,
+            by_month_day,
+            hour: hour.unwrap_or(0),
+            minute: minute.unwrap_or(0),
+        })
+    }
+
+    pub(super) fn next_after(
+        &self,
+        after: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let time = NaiveTime::from_hms_opt(self.hour, self.minute, 0)?;
+        let start = after.date_naive();
+        (0..=MAX_LOOKAHEAD_DAYS)
+            .filter_map(|offset| start.checked_add_days(Days::new(offset)))
+            .filter(|date| self.matches(*date))
+            .map(|date| Utc.from_utc_datetime(&date.and_time(time)))
+            .find(|candidate| *candidate > after)
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        match self.frequency {
+            Frequency::Daily | Frequency::Weekly => {
+                self.by_day.is_empty() || self.by_day.contains(&date.weekday())
+            }
+            Frequency::Monthly => {
+                let is_last_day = date
+                    .succ_opt()
+                    .is_none_or(|next| next.month() != date.month());
+                self.by_month_day.iter().any(|day| {
+                    *day == date.day() as i32 || (*day == -1 && is_last_day)
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={frequency}")?;
+        if !self.by_day.is_empty() {
+            let days: Vec<_> =
+                self.by_day.iter().map(|day| weekday_code(*day)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<_> =
+                self.by_month_day.iter().map(i32::to_string).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        write!(f, ";BYHOUR={};BYMINUTE={}", self.hour, self.minute)
+    }
+}
+
+fn parse_frequency(value: &str) -> Result<Frequency, String> {
+    match value.to_ascii_uppercase().as_str() {
+        "DAILY" => Ok(Frequency::Daily),
+        "WEEKLY" => Ok(Frequency::Weekly),
+        "MONTHLY" => Ok(Frequency::Monthly),
+        _ => Err("FREQ must be one of DAILY, WEEKLY, MONTHLY".to_string()),
+    }
+}
+
+fn parse_by_day(value: &str) -> Result<Vec<Weekday>, String> {
+    let mut days = Vec::new();
+    for code in value.split(',') {
+        let day = match code.trim().to_ascii_uppercase().as_str() {
+            "MO" => Weekday::Mon,
+            "TU" => Weekday::Tue,
+            "WE" => Weekday::Wed,
+            "TH" => Weekday::Thu,
+            "FR" => Weekday::Fri,
+            "SA" => Weekday::Sat,
+            "SU" => Weekday::Sun,
+            _ => return Err(format!("BYDAY value {code} is not MO..SU")),
+        };
+        if !days.contains(&day) {
+            days.push(day);
+        }
+    }
+    days.sort_by_key(Weekday::num_days_from_monday);
+    Ok(days)
+}
+
+fn parse_by_month_day(value: &str) -> Result<Vec<i32>, String> {
+    let mut days = Vec::new();
+    for raw in value.split(',') {
+        let day = raw
+            .trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|day| (1..=31).contains(day) || *day == -1)
+            .ok_or_else(|| {
+                format!("BYMONTHDAY value {raw} must be 1..31 or -1")
+            })?;
+        if !days.contains(&day) {
+            days.push(day);
+        }
+    }
+    days.sort_by_key(|day| if *day == -1 { i32::MAX } else { *day });
+    Ok(days)
+}
+
+fn parse_bounded(value: &str, key: &str, max: u32) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|number| *number <= max)
+        .ok_or_else(|| format!("{key} must be 0..{max}"))
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+#[derive(Debug, Serialize, FromRow)]
+struct RequestScheduleRow {
+    id: Uuid,
+    owner_user_id: Uuid,
+    template_id: Option<Uuid>,
+    source_request_id: Option<Uuid>,
+    variables: SqlJson<HashMap<String, String>>,
+    rule: String,
+    active: bool,
+    next_run_at: DateTime<Utc>,
+    last_run_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateScheduleInput {
+    template_id: Option<Uuid>,
+    source_request_id: Option<Uuid>,
+    #[serde(default)]
+    variables: HashMap<String, String>,
+    rule: String,
+    #[serde(default = "default_active")]
+    active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateScheduleInput {
+    variables: Option<HashMap<String, String>>,
+    rule: Option<String>,
+    active: Option<bool>,
+}
+
+fn schedule_projection_sql() -> &'static str {
+    "schedule.id,
+     schedule.owner_user_id,
+     schedule.template_id,
+     schedule.source_request_id,
+     schedule.variables,
+     schedule.rule,
+     schedule.active,
+     schedule.next_run_at,
+     schedule.last_run_at,
+     schedule.created_at,
+     schedule.updated_at"
+}
+
+pub(super) async fn list_schedules(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+
+    let query = format!(
+        "SELECT {}
+         FROM app.request_schedules schedule
+         WHERE schedule.owner_user_id = $1
+         ORDER BY schedule.active DESC, schedule.next_run_at ASC",
+        schedule_projection_sql()
+    );
+    let schedules = sqlx::query_as::<_, RequestScheduleRow>(&query)
+        .bind(user.id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(response::ok(StatusCode::OK, schedules))
+}
+
+pub(super) async fn create_schedule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreateScheduleInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let rule = parse_rule_field(&input.rule)?;
+    let next_run_at = first_run(&rule, Utc::now())?;
+    match (input.template_id, input.source_request_id) {
+        (Some(template_id), None) => {
+            check_template_input(
+                &state.db,
+                &user,
+                template_id,
+                &input.variables,
+            )
+            .await?;
+        }
+        (None, Some(source_request_id)) => {
+            check_source_request(&state.db, &user, source_request_id).await?;
+            if !input.variables.is_empty() {
+                return Err(AppError::Validation(vec![ErrorDetail {
+                    field: "variables".to_string(),
+                    message: "variables require a template_id".to_string(),
+                }]));
+            }
+        }
+        _ => {
+            return Err(AppError::Validation(vec![ErrorDetail {
+                field: "template_id".to_string(),
+                message: "exactly one of template_id or source_request_id \
+                          is required"
+                    .to_string(),
+            }]));
+        }
+    }
+
+    let schedule_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.request_schedules (
+           owner_user_id,
+           template_id,
+           source_request_id,
+           variables,
+           rule,
+           active,
+           next_run_at
+         )
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING id",
+    )
+    .bind(user.id)
+    .bind(input.template_id)
+    .bind(input.source_request_id)
+    .bind(SqlJson(&input.variables))
+    .bind(rule.to_string())
+    .bind(input.active)
+    .bind(next_run_at)
+    .fetch_one(&state.db)
+    .await?;
+
+    let schedule =
+        fetch_owned_schedule(&state.db, user.id, schedule_id).await?;
+    Ok(response::ok(StatusCode::CREATED, schedule))
+}
+
+pub(super) async fn get_schedule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let schedule = fetch_owned_schedule(&state.db, user.id, id).await?;
+
+    Ok(response::ok(StatusCode::OK, schedule))
+}
+
+pub(super) async fn update_schedule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateScheduleInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_schedule(&state.db, user.id, id).await?;
+    let rule =
+        parse_rule_field(input.rule.as_deref().unwrap_or(&existing.rule))?;
+    let active = input.active.unwrap_or(existing.active);
+    let variables = input.variables.unwrap_or(existing.variables.0);
+
+    if let Some(template_id) = existing.template_id {
+        check_template_input(&state.db, &user, template_id, &variables).await?;
+    } else if !variables.is_empty() {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "variables".to_string(),
+            message: "variables require a template_id".to_string(),
+        }]));
+    }
+
+    let reschedule = input.rule.is_some() || (active && !existing.active);
+    let next_run_at = if reschedule {
+        first_run(&rule, Utc::now())?
+    } else {
+        existing.next_run_at
+    };
+
+    sqlx::query(
+        "UPDATE app.request_schedules
+         SET variables = $2,
+             rule = $3,
+             active = $4,
+             next_run_at = $5
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(SqlJson(&variables))
+    .bind(rule.to_string())
+    .bind(active)
+    .bind(next_run_at)
+    .execute(&state.db)
+    .await?;
+
+    let schedule = fetch_owned_schedule(&state.db, user.id, id).await?;
+    Ok(response::ok(StatusCode::OK, schedule))
+}
+
+pub(super) async fn delete_schedule(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_schedule(&state.db, user.id, id).await?;
+    sqlx::query("DELETE FROM app.request_schedules WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn spawn_scheduler(state: AppState) -> JoinHandle<()> {
+    workers::spawn_periodic("scheduler", SCHEDULER_INTERVAL, move || {
+        let state = state.clone();
+        async move { run_due_schedules(&state, Utc::now()).await }
+    })
+}
+
+// Occurrences missed while no scheduler was running collapse into one.
+pub async fn run_due_schedules(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError> {
+    let due: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id
+         FROM app.request_schedules
+         WHERE active AND next_run_at <= $1
+         ORDER BY next_run_at ASC
+         LIMIT $2",
+    )
+    .bind(now)
+    .bind(SCHEDULER_BATCH)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut created = 0;
+    for schedule_id in due {
+        match run_schedule(state, schedule_id, now).await {
+            Ok(true) => created += 1,
+            Ok(false) => {}
+            Err(err) => {
+                warn!(%schedule_id, error = %err, "schedule run failed");
+            }
+        }
+    }
+
+    Ok(created)
+}
+
+async fn run_schedule(
+    state: &AppState,
+    schedule_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<bool, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let query = format!(
+        "SELECT {}
+         FROM app.request_schedules schedule
+         WHERE schedule.id = $1
+           AND schedule.active
+           AND schedule.next_run_at <= $2
+         FOR UPDATE SKIP LOCKED",
+        schedule_projection_sql()
+    );
+    let Some(schedule) = sqlx::query_as::<_, RequestScheduleRow>(&query)
+        .bind(schedule_id)
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let scheduled_for = schedule.next_run_at;
+
+    let claimed = sqlx::query(
+        "INSERT INTO app.request_schedule_runs (schedule_id, scheduled_for)
+         VALUES ($1, $2)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(schedule.id)
+    .bind(scheduled_for)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        == 1;
+
+    let next_run_at = RecurrenceRule::parse(&schedule.rule)
+        .ok()
+        .and_then(|rule| rule.next_after(now));
+    let mut created = None;
+    if claimed {
+        match create_scheduled_request(state, &mut tx, &schedule).await {
+            Ok(record) => created = Some(record),
+            Err(AppError::Validation(details)) => {
+                warn!(
+                    schedule_id = %schedule.id,
+                    ?details,
+                    "schedule can no longer create requests; deactivating"
+                );
+                deactivate(&mut tx, schedule.id).await?;
+                tx.commit().await?;
+                return Ok(false);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    match next_run_at {
+        Some(next_run_at) => {
+            sqlx::query(
+                "UPDATE app.request_schedules
+                 SET next_run_at = $2,
+                     last_run_at = $3
+                 WHERE id = $1",
+            )
+            .bind(schedule.id)
+            .bind(next_run_at)
+            .bind(scheduled_for)
+            .execute(&mut *tx)
+            .await?;
+        }
+        None => deactivate(&mut tx, schedule.id).await?,
+    }
+    tx.commit().await?;
+
+    let Some((record, audit_entry)) = created else {
+        return Ok(false);
+    };
+    publish_request_created(state, &record, audit_entry).await?;
+    Ok(true)
+}
+
+async fn create_scheduled_request(
+    state: &AppState,
+    tx: &mut PgConnection,
+    schedule: &RequestScheduleRow,
+) -> Result<(RequestRow, AuditLogRow), AppError> {
+    let input = scheduled_request_input(state, &mut *tx, schedule).await?;
+    validate_create_input(&input)?;
+
+    let mut marker = serde_json::Map::new();
+    marker.insert("system_generated".to_string(), json!(true));
+    marker.insert("schedule_id".to_string(), json!(schedule.id));
+    marker.insert("scheduled_for".to_string(), json!(schedule.next_run_at));
+    let (record, audit_entry) = create_request_record(
+        &mut *tx,
+        schedule.owner_user_id,
+        input,
+        Some(marker),
+    )
+    .await?;
+
+    sqlx::query(
+        "UPDATE app.request_schedule_runs
+         SET request_id = $3
+         WHERE schedule_id = $1 AND scheduled_for = $2",
+    )
+    .bind(schedule.id)
+    .bind(schedule.next_run_at)
+    .bind(record.id)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok((record, audit_entry))
+}
+
+async fn scheduled_request_input(
+    state: &AppState,
+    tx: &mut PgConnection,
+    schedule: &RequestScheduleRow,
+) -> Result<CreateRequestInput, AppError> {
+    if let Some(template_id) = schedule.template_id {
+        let owner = sqlx::query_as::<_, AuthUserRow>(
+            "SELECT id, email, display_name
+             FROM app.app_users
+             WHERE id = $1",
+        )
+        .bind(schedule.owner_user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let body = templates::CreateRequestBody::from_template(
+            template_id,
+            schedule.variables.0.clone(),
+        );
+        return templates::resolve_create_input(&state.db, &owner, body).await;
+    }
+
+    let source_request_id = schedule.source_request_id.ok_or_else(|| {
+        AppError::Internal("schedule has no template or source".to_string())
+    })?;
+    let source = fetch_visible_request(
+        &mut *tx,
+        source_request_id,
+        schedule.owner_user_id,
+    )
+    .await
+    .map_err(|err| match err {
+        AppError::NotFound(_) => AppError::Validation(vec![ErrorDetail {
+            field: "source_request_id".to_string(),
+            message: "source request is no longer visible".to_string(),
+        }]),
+        err => err,
+    })?;
+
+    let checklist =
+        checklists::fetch_item_titles(&mut *tx, source_request_id).await?;
+
+    Ok(CreateRequestInput {
+        title: source.title,
+        description: source.description,
+        category: source.category,
+        priority: source.priority,
+        assignee_email: source.assignee_email,
+        checklist,
+        checklist_required: source.checklist_required,
+        created_at: None,
+    })
+}
+
+async fn deactivate(
+    tx: &mut PgConnection,
+    schedule_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE app.request_schedules
+         SET active = FALSE
+         WHERE id = $1",
+    )
+    .bind(schedule_id)
+    .execute(tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn fetch_owned_schedule(
+    pool: &PgPool,
+    user_id: Uuid,
+    schedule_id: Uuid,
+) -> Result<RequestScheduleRow, AppError> {
+    let query = format!(
+        "SELECT {}
+         FROM app.request_schedules schedule
+         WHERE schedule.id = $1 AND schedule.owner_user_id = $2",
+        schedule_projection_sql()
+    );
+
+    sqlx::query_as::<_, RequestScheduleRow>(&query)
+        .bind(schedule_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("schedule not found".to_string()))
+}
+
+async fn check_template_input(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    template_id: Uuid,
+    variables: &HashMap<String, String>,
+) -> Result<(), AppError> {
+    let body = templates::CreateRequestBody::from_template(
+        template_id,
+        variables.clone(),
+    );
+    let input = templates::resolve_create_input(pool, user, body).await?;
+    validate_create_input(&input)
+}
+
+async fn check_source_request(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    source_request_id: Uuid,
+) -> Result<(), AppError> {
+    match fetch_visible_request(pool, source_request_id, user.id).await {
+        Ok(_) => Ok(()),
+        Err(AppError::NotFound(_)) => {
+            Err(AppError::Validation(vec![ErrorDetail {
+                field: "source_request_id".to_string(),
+                message: "source request not found".to_string(),
+            }]))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn parse_rule_field(raw: &str) -> Result<RecurrenceRule, AppError> {
+    RecurrenceRule::parse(raw).map_err(|message| {
+        AppError::Validation(vec![ErrorDetail {
+            field: "rule".to_string(),
+            message,
+        }])
+    })
+}
+
+fn first_run(
+    rule: &RecurrenceRule,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, AppError> {
+    rule.next_after(now).ok_or_else(|| {
+        AppError::Validation(vec![ErrorDetail {
+            field: "rule".to_string(),
+            message: "rule never occurs".to_string(),
+        }])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("timestamp should parse")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parse_normalizes_rules() {
+        let rule = RecurrenceRule::parse("RRULE:freq=weekly;byday=FR,MO,MO")
+            .expect("rule should parse");
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;BYDAY=MO,FR;BYHOUR=0;BYMINUTE=0"
+        );
+
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=-1,1")
+            .expect("rule should parse");
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;BYMONTHDAY=1,-1;BYHOUR=0;BYMINUTE=0"
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unsupported_rules() {
+        for raw in [
+            "",
+            "FREQ=YEARLY",
+            "FREQ=WEEKLY",
+            "FREQ=MONTHLY",
+            "FREQ=DAILY;INTERVAL=2",
+            "FREQ=DAILY;BYHOUR=24",
+            "FREQ=DAILY;FREQ=DAILY",
+            "FREQ=MONTHLY;BYMONTHDAY=0",
+            "FREQ=MONTHLY;BYMONTHDAY=1;BYDAY=MO",
+        ] {
+            assert!(RecurrenceRule::parse(raw).is_err(), "{raw} should fail");
+        }
+    }
+
+    #[test]
+    fn next_after_finds_every_monday() {
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=MO;BYHOUR=9")
+            .expect("rule should parse");
+
+        // 2026-03-09 is a Monday.
+        assert_eq!(
+            rule.next_after(at("2026-03-07T12:00:00Z")),
+            Some(at("2026-03-09T09:00:00Z"))
+        );
+        assert_eq!(
+            rule.next_after(at("2026-03-09T09:00:00Z")),
+            Some(at("2026-03-16T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn next_after_handles_month_ends() {
+        let first = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=1")
+            .expect("rule should parse");
+        assert_eq!(
+            first.next_after(at("2026-12-15T00:00:00Z")),
+            Some(at("2027-01-01T00:00:00Z"))
+        );
+
+        let last = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=-1")
+            .expect("rule should parse");
+        assert_eq!(
+            last.next_after(at("2026-02-02T00:00:00Z")),
+            Some(at("2026-02-28T00:00:00Z"))
+        );
+
+        let thirty_first = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=31")
+            .expect("rule should parse");
+        assert_eq!(
+            thirty_first.next_after(at("2026-04-01T00:00:00Z")),
+            Some(at("2026-05-31T00:00:00Z"))
+        );
+    }
+}
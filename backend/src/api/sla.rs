@@ -0,0 +1,1252 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgConnection, PgExecutor, PgPool};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::business_hours::{
+    BusinessCalendar, add_working_time, working_time_between,
+};
+use super::events::{ServerEvent, SlaEventPayload};
+use super::{
+    AuthUserRow, deserialize_present, email_domain,
+    fetch_request_recipient_ids, publish_event, require_authenticated_user,
+    validate_category, validate_priority, workers,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const SLA_WORKER_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_TARGET_MINUTES: i32 = 525_600;
+const MAX_HOLIDAYS: usize = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum SlaTarget {
+    FirstResponse,
+    Resolution,
+}
+
+impl SlaTarget {
+    fn column_prefix(self) -> &'static str {
+        match self {
+            Self::FirstResponse => "first_response",
+            Self::Resolution => "resolution",
+        }
+    }
+
+    fn pending_sql(self) -> &'static str {
+        match self {
+            Self::FirstResponse => "sla.first_responded_at IS NULL",
+            Self::Resolution => "req.status <> 'resolved'",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+struct BusinessCalendarRow {
+    id: Uuid,
+    workspace_domain: String,
+    name: String,
+    timezone: String,
+    work_days: Vec<i16>,
+    day_start: NaiveTime,
+    day_end: NaiveTime,
+    holidays: Vec<NaiveDate>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl BusinessCalendarRow {
+    fn to_calendar(&self) -> BusinessCalendar {
+        BusinessCalendar::new(
+            self.timezone.parse().unwrap_or(Tz::UTC),
+            &self.work_days,
+            self.day_start,
+            self.day_end,
+            &self.holidays,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+struct SlaPolicyRow {
+    id: Uuid,
+    workspace_domain: String,
+    category: String,
+    priority: String,
+    first_response_minutes: i32,
+    resolution_minutes: i32,
+    warning_percent: i16,
+    calendar_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateCalendarInput {
+    name: String,
+    timezone: String,
+    work_days: Vec<i16>,
+    day_start: String,
+    day_end: String,
+    #[serde(default)]
+    holidays: Vec<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateCalendarInput {
+    name: Option<String>,
+    timezone: Option<String>,
+    work_days: Option<Vec<i16>>,
+    day_start: Option<String>,
+    day_end: Option<String>,
+    holidays: Option<Vec<NaiveDate>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreatePolicyInput {
+    category: String,
+    priority: String,
+    first_response_minutes: i32,
+    resolution_minutes: i32,
+    warning_percent: Option<i16>,
+    calendar_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdatePolicyInput {
+    first_response_minutes: Option<i32>,
+    resolution_minutes: Option<i32>,
+    warning_percent: Option<i16>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    calendar_id: Option<Option<Uuid>>,
+}
+
+struct CalendarFields {
+    name: String,
+    timezone: String,
+    work_days: Vec<i16>,
+    day_start: NaiveTime,
+    day_end: NaiveTime,
+    holidays: Vec<NaiveDate>,
+}
+
+const CALENDAR_COLUMNS: &str = "id,
+     workspace_domain,
+     name,
+     timezone,
+     work_days,
+     day_start,
+     day_end,
+     holidays,
+     created_at,
+     updated_at";
+
+const POLICY_COLUMNS: &str = "id,
+     workspace_domain,
+     category,
+     priority,
+     first_response_minutes,
+     resolution_minutes,
+     warning_percent,
+     calendar_id,
+     created_at,
+     updated_at";
+
+pub(super) async fn list_calendars(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let workspace = workspace_of(&user)?;
+
+    let calendars = sqlx::query_as::<_, BusinessCalendarRow>(&format!(
+        "SELECT {CALENDAR_COLUMNS}
+         FROM app.business_calendars
+         WHERE workspace_domain = $1
+         ORDER BY lower(name) ASC"
+    ))
+    .bind(workspace)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, calendars))
+}
+
+pub(super) async fn create_calendar(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreateCalendarInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        admin_workspace_of(&state.db, &user, "business calendars").await?;
+
+    let fields = validate_calendar(
+        &input.name,
+        &input.timezone,
+        input.work_days,
+        &input.day_start,
+        &input.day_end,
+        input.holidays,
+    )?;
+    let calendar = sqlx::query_as::<_, BusinessCalendarRow>(&format!(
+        "INSERT INTO app.business_calendars (
+           workspace_domain,
+           name,
+           timezone,
+           work_days,
+           day_start,
+           day_end,
+           holidays,
+           created_by_user_id
+         )
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING {CALENDAR_COLUMNS}"
+    ))
+    .bind(workspace)
+    .bind(fields.name)
+    .bind(fields.timezone)
+    .bind(fields.work_days)
+    .bind(fields.day_start)
+    .bind(fields.day_end)
+    .bind(fields.holidays)
+    .bind(user.id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(map_sla_write_error)?;
+
+    Ok(response::ok(StatusCode::CREATED, calendar))
+}
+
+pub(super) async fn get_calendar(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let calendar = fetch_calendar(&state.db, &workspace_of(&user)?, id).await?;
+
+    Ok(response::ok(StatusCode::OK, calendar))
+}
+
+pub(super) async fn update_calendar(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateCalendarInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        admin_workspace_of(&state.db, &user, "business calendars").await?;
+
+    let mut tx = state.db.begin().await?;
+    let existing = fetch_calendar(&mut *tx, &workspace, id).await?;
+    let fields = validate_calendar(
+        input.name.as_deref().unwrap_or(&existing.name),
+        input.timezone.as_deref().unwrap_or(&existing.timezone),
+        input.work_days.unwrap_or(existing.work_days),
+        &input
+            .day_start
+            .unwrap_or_else(|| existing.day_start.to_string()),
+        &input
+            .day_end
+            .unwrap_or_else(|| existing.day_end.to_string()),
+        input.holidays.unwrap_or(existing.holidays),
+    )?;
+    let calendar = sqlx::query_as::<_, BusinessCalendarRow>(&format!(
+        "UPDATE app.business_calendars
+         SET name = $2,
+             timezone = $3,
+             work_days = $4,
+             day_start = $5,
+             day_end = $6,
+             holidays = $7
+         WHERE id = $1
+         RETURNING {CALENDAR_COLUMNS}"
+    ))
+    .bind(id)
+    .bind(fields.name)
+    .bind(fields.timezone)
+    .bind(fields.work_days)
+    .bind(fields.day_start)
+    .bind(fields.day_end)
+    .bind(fields.holidays)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(map_sla_write_error)?;
+
+    let policies = sqlx::query_as::<_, SlaPolicyRow>(&format!(
+        "SELECT {POLICY_COLUMNS}
+         FROM app.sla_policies
+         WHERE calendar_id = $1"
+    ))
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await?;
+    for policy in &policies {
+        refresh_policy_requests(&mut tx, policy).await?;
+    }
+    tx.commit().await?;
+
+    Ok(response::ok(StatusCode::OK, calendar))
+}
+
+pub(super) async fn delete_calendar(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let workspace =
+        admin_workspace_of(&state.db, &user, "business calendars").await?;
+    let existing = fetch_calendar(&state.db, &workspace, id).await?;
+    let in_use: bool = sqlx::query_scalar(
+        "SELECT EXISTS (
+           SELECT 1 FROM app.sla_policies WHERE calendar_id = $1
+         )",
+    )
+    .bind(existing.id)
+    .fetch_one(&state.db)
+    .await?;
+    if in_use {
+        return Err(validation_error(
+            "id",
+            "calendar is used by an SLA policy",
+        ));
+    }
+
+    sqlx::query("DELETE FROM app.business_calendars WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await
+        .map_err(map_sla_write_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn list_policies(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let workspace = workspace_of(&user)?;
+
+    let policies = sqlx::query_as::<_, SlaPolicyRow>(&format!(
+        "SELECT {POLICY_COLUMNS}
+         FROM app.sla_policies
+         WHERE workspace_domain = $1
+         ORDER BY category ASC,
+                  array_position(ARRAY['high', 'medium', 'low'], priority::text)"
+    ))
+    .bind(workspace)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, policies))
+}
+
+pub(super) async fn create_policy(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreatePolicyInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        admin_workspace_of(&state.db, &user, "SLA policies").await?;
+
+    let mut details = Vec::new();
+    if validate_category(&input.category).is_err() {
+        details.push(ErrorDetail {
+            field: "category".to_string(),
+            message: "category must be one of IT, Ops, Admin, HR".to_string(),
+        });
+    }
+    if validate_priority(&input.priority).is_err() {
+        details.push(ErrorDetail {
+            field: "priority".to_string(),
+            message: "priority must be one of low, medium, high".to_string(),
+        });
+    }
+    let warning_percent = input.warning_percent.unwrap_or(80);
+    details.extend(validate_targets(
+        input.first_response_minutes,
+        input.resolution_minutes,
+        warning_percent,
+    ));
+    if !details.is_empty() {
+        return Err(AppError::Validation(details));
+    }
+
+    let mut tx = state.db.begin().await?;
+    if let Some(calendar_id) = input.calendar_id {
+        ensure_calendar(&mut tx, &workspace, calendar_id).await?;
+    }
+    let policy = sqlx::query_as::<_, SlaPolicyRow>(&format!(
+        "INSERT INTO app.sla_policies (
+           workspace_domain,
+           category,
+           priority,
+           first_response_minutes,
+           resolution_minutes,
+           warning_percent,
+           calendar_id,
+           created_by_user_id
+         )
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING {POLICY_COLUMNS}"
+    ))
+    .bind(&workspace)
+    .bind(&input.category)
+    .bind(&input.priority)
+    .bind(input.first_response_minutes)
+    .bind(input.resolution_minutes)
+    .bind(warning_percent)
+    .bind(input.calendar_id)
+    .bind(user.id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(map_sla_write_error)?;
+    refresh_policy_requests(&mut tx, &policy).await?;
+    tx.commit().await?;
+
+    Ok(response::ok(StatusCode::CREATED, policy))
+}
+
+pub(super) async fn get_policy(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let policy = fetch_policy(&state.db, &workspace_of(&user)?, id).await?;
+
+    Ok(response::ok(StatusCode::OK, policy))
+}
+
+pub(super) async fn update_policy(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdatePolicyInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+    let workspace =
+        admin_workspace_of(&state.db, &user, "SLA policies").await?;
+
+    let mut tx = state.db.begin().await?;
+    let existing = fetch_policy(&mut *tx, &workspace, id).await?;
+    let first_response_minutes = input
+        .first_response_minutes
+        .unwrap_or(existing.first_response_minutes);
+    let resolution_minutes = input
+        .resolution_minutes
+        .unwrap_or(existing.resolution_minutes);
+    let warning_percent =
+        input.warning_percent.unwrap_or(existing.warning_percent);
+    let details = validate_targets(
+        first_response_minutes,
+        resolution_minutes,
+        warning_percent,
+    );
+    if !details.is_empty() {
+        return Err(AppError::Validation(details));
+    }
+    let calendar_id = input.calendar_id.unwrap_or(existing.calendar_id);
+    if let Some(calendar_id) = calendar_id {
+        ensure_calendar(&mut tx, &workspace, calendar_id).await?;
+    }
+
+    let policy = sqlx::query_as::<_, SlaPolicyRow>(&format!(
+        "UPDATE app.sla_policies
+         SET first_response_minutes = $2,
+             resolution_minutes = $3,
+             warning_percent = $4,
+             calendar_id = $5
+         WHERE id = $1
+         RETURNING {POLICY_COLUMNS}"
+    ))
+    .bind(id)
+    .bind(first_response_minutes)
+    .bind(resolution_minutes)
+    .bind(warning_percent)
+    .bind(calendar_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    refresh_policy_requests(&mut tx, &policy).await?;
+    tx.commit().await?;
+
+    Ok(response::ok(StatusCode::OK, policy))
+}
+
+pub(super) async fn delete_policy(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let workspace =
+        admin_workspace_of(&state.db, &user, "SLA policies").await?;
+    let existing = fetch_policy(&state.db, &workspace, id).await?;
+    sqlx::query("DELETE FROM app.sla_policies WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, FromRow)]
+struct SlaRequestRow {
+    status: String,
+    started_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    policy_id: Uuid,
+    first_response_minutes: i32,
+    resolution_minutes: i32,
+    warning_percent: i16,
+    calendar_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+struct StatusChange {
+    at: DateTime<Utc>,
+    status: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SlaTargets {
+    first_response: TimeDelta,
+    resolution: TimeDelta,
+    warning_percent: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SlaDeadlines {
+    first_response_due_at: DateTime<Utc>,
+    first_response_warn_at: DateTime<Utc>,
+    first_responded_at: Option<DateTime<Utc>>,
+    resolution_due_at: DateTime<Utc>,
+    resolution_warn_at: DateTime<Utc>,
+}
+
+pub(super) async fn refresh_request_sla(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+) -> Result<(), AppError> {
+    let request = sqlx::query_as::<_, SlaRequestRow>(
+        "SELECT req.status,
+                COALESCE(req.imported_at, req.created_at) AS started_at,
+                req.updated_at,
+                policy.id AS policy_id,
+                policy.first_response_minutes,
+                policy.resolution_minutes,
+                policy.warning_percent,
+                policy.calendar_id
+         FROM app.requests req
+         JOIN app.app_users owner ON owner.id = req.owner_user_id
+         JOIN app.sla_policies policy
+           ON policy.workspace_domain = lower(btrim(split_part(owner.email, '@', 2)))
+          AND policy.category = req.category
+          AND policy.priority = req.priority
+         WHERE req.id = $1",
+    )
+    .bind(request_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(request) = request else {
+        sqlx::query("DELETE FROM app.request_slas WHERE request_id = $1")
+            .bind(request_id)
+            .execute(&mut *conn)
+            .await?;
+        return Ok(());
+    };
+
+    let calendar = match request.calendar_id {
+        Some(calendar_id) => Some(
+            sqlx::query_as::<_, BusinessCalendarRow>(&format!(
+                "SELECT {CALENDAR_COLUMNS}
+                 FROM app.business_calendars
+                 WHERE id = $1"
+            ))
+            .bind(calendar_id)
+            .fetch_one(&mut *conn)
+            .await?
+            .to_calendar(),
+        ),
+        None => None,
+    };
+    let mut history: Vec<StatusChange> =
+        sqlx::query_as::<_, (DateTime<Utc>, String)>(
+            "SELECT created_at, new_value->>'status'
+         FROM app.request_audit_logs
+         WHERE request_id = $1
+           AND action IN ('created', 'updated')
+           AND new_value->>'status' IS NOT NULL
+         ORDER BY created_at ASC, id ASC",
+        )
+        .bind(request_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|(at, status)| StatusChange { at, status })
+        .collect();
+    // The change being made is not audited yet.
+    if history.last().map(|change| change.status.as_str())
+        != Some(request.status.as_str())
+    {
+        history.push(StatusChange {
+            at: request.updated_at,
+            status: request.status.clone(),
+        });
+    }
+
+    let deadlines = compute_deadlines(
+        calendar.as_ref(),
+        request.started_at,
+        &history,
+        SlaTargets {
+            first_response: TimeDelta::minutes(i64::from(
+                request.first_response_minutes,
+            )),
+            resolution: TimeDelta::minutes(i64::from(
+                request.resolution_minutes,
+            )),
+            warning_percent: i32::from(request.warning_percent),
+        },
+    );
+
+    sqlx::query(
+        "INSERT INTO app.request_slas AS sla (
+           request_id,
+           policy_id,
+           first_response_due_at,
+           first_response_warn_at,
+           first_responded_at,
+           resolution_due_at,
+           resolution_warn_at
+         )
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         ON CONFLICT (request_id) DO UPDATE
+         SET policy_id = EXCLUDED.policy_id,
+             first_response_due_at = EXCLUDED.first_response_due_at,
+             first_response_warn_at = EXCLUDED.first_response_warn_at,
+             first_responded_at = EXCLUDED.first_responded_at,
+             resolution_due_at = EXCLUDED.resolution_due_at,
+             resolution_warn_at = EXCLUDED.resolution_warn_at,
+             first_response_warning_sent_at = CASE
+               WHEN EXCLUDED.first_response_warn_at > NOW() THEN NULL
+               ELSE sla.first_response_warning_sent_at
+             END,
+             first_response_breach_sent_at = CASE
+               WHEN EXCLUDED.first_response_due_at > NOW() THEN NULL
+               ELSE sla.first_response_breach_sent_at
+             END,
+             resolution_warning_sent_at = CASE
+               WHEN EXCLUDED.resolution_warn_at > NOW() THEN NULL
+               ELSE sla.resolution_warning_sent_at
+             END,
+             resolution_breach_sent_at = CASE
+               WHEN EXCLUDED.resolution_due_at > NOW() THEN NULL
+               ELSE sla.resolution_breach_sent_at
+             END,
+             computed_at = NOW()",
+    )
+    .bind(request_id)
+    .bind(request.policy_id)
+    .bind(deadlines.first_response_due_at)
+    .bind(deadlines.first_response_warn_at)
+    .bind(deadlines.first_responded_at)
+    .bind(deadlines.resolution_due_at)
+    .bind(deadlines.resolution_warn_at)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn refresh_policy_requests(
+    conn: &mut PgConnection,
+    policy: &SlaPolicyRow,
+) -> Result<(), AppError> {
+    let request_ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT req.id
+         FROM app.requests req
+         JOIN app.app_users owner ON owner.id = req.owner_user_id
+         WHERE lower(btrim(split_part(owner.email, '@', 2))) = $1
+           AND req.category = $2
+           AND req.priority = $3
+           AND (
+             req.status <> 'resolved'
+             OR EXISTS (
+               SELECT 1 FROM app.request_slas sla
+               WHERE sla.request_id = req.id
+             )
+           )",
+    )
+    .bind(&policy.workspace_domain)
+    .bind(&policy.category)
+    .bind(&policy.priority)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for request_id in request_ids {
+        refresh_request_sla(&mut *conn, request_id).await?;
+    }
+
+    Ok(())
+}
+
+fn compute_deadlines(
+    calendar: Option<&BusinessCalendar>,
+    created_at: DateTime<Utc>,
+    history: &[StatusChange],
+    targets: SlaTargets,
+) -> SlaDeadlines {
+    let warning = |target: TimeDelta| target * targets.warning_percent / 100;
+
+    SlaDeadlines {
+        first_response_due_at: add_working_time(
+            calendar,
+            created_at,
+            targets.first_response,
+        ),
+        first_response_warn_at: add_working_time(
+            calendar,
+            created_at,
+            warning(targets.first_response),
+        ),
+        first_responded_at: history
+            .iter()
+            .find(|change| change.status != "open")
+            .map(|change| change.at.max(created_at)),
+        resolution_due_at: resolution_deadline(
+            calendar,
+            created_at,
+            history,
+            targets.resolution,
+        ),
+        resolution_warn_at: resolution_deadline(
+            calendar,
+            created_at,
+            history,
+            warning(targets.resolution),
+        ),
+    }
+}
+
+fn resolution_deadline(
+    calendar: Option<&BusinessCalendar>,
+    created_at: DateTime<Utc>,
+    history: &[StatusChange],
+    target: TimeDelta,
+) -> DateTime<Utc> {
+    let mut remaining = target;
+    let mut since = created_at;
+    let mut resolved = false;
+
+    for change in history {
+        let now_resolved = change.status == "resolved";
+        if now_resolved == resolved {
+            continue;
+        }
+        let at = change.at.max(since);
+        if !resolved {
+            let spent = working_time_between(calendar, since, at);
+            if spent >= remaining {
+                return add_working_time(calendar, since, remaining);
+            }
+            remaining -= spent;
+        }
+        since = at;
+        resolved = now_resolved;
+    }
+
+    add_working_time(calendar, since, remaining)
+}
+
+pub fn spawn_sla_worker(state: AppState) -> JoinHandle<()> {
+    workers::spawn_periodic("sla", SLA_WORKER_INTERVAL, move || {
+        let state = state.clone();
+        async move { run_due_sla_events(&state, Utc::now()).await }
+    })
+}
+
+// Events are claimed with a conditional update, so concurrent replicas
+// never publish the same one.
+pub async fn run_due_sla_events(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError> {
+    let mut due: Vec<(bool, SlaTarget, Uuid, DateTime<Utc>)> = Vec::new();
+
+    for target in [SlaTarget::FirstResponse, SlaTarget::Resolution] {
+        let prefix = target.column_prefix();
+        let pending = target.pending_sql();
+
+        let breached: Vec<(Uuid, DateTime<Utc>)> = sqlx::query_as(&format!(
+            "UPDATE app.request_slas sla
+             SET {prefix}_breach_sent_at = $1
+             FROM app.requests req
+             WHERE req.id = sla.request_id
+               AND {pending}
+               AND sla.{prefix}_due_at <= $1
+               AND sla.{prefix}_breach_sent_at IS NULL
+             RETURNING sla.request_id, sla.{prefix}_due_at"
+        ))
+        .bind(now)
+        .fetch_all(&state.db)
+        .await?;
+        due.extend(
+            breached
+                .into_iter()
+                .map(|(request_id, due_at)| (true, target, request_id, due_at)),
+        );
+
+        let warned: Vec<(Uuid, DateTime<Utc>)> = sqlx::query_as(&format!(
+            "UPDATE app.request_slas sla
+             SET {prefix}_warning_sent_at = $1
+             FROM app.requests req
+             WHERE req.id = sla.request_id
+               AND {pending}
+               AND sla.{prefix}_warn_at <= $1
+               AND sla.{prefix}_due_at > $1
+               AND sla.{prefix}_warning_sent_at IS NULL
+             RETURNING sla.request_id, sla.{prefix}_due_at"
+        ))
+        .bind(now)
+        .fetch_all(&state.db)
+        .await?;
+        due.extend(
+            warned.into_iter().map(|(request_id, due_at)| {
+                (false, target, request_id, due_at)
+            }),
+        );
+    }
+
+    let published = due.len();
+    for (breached, target, request_id, due_at) in due {
+        let recipients =
+            fetch_request_recipient_ids(&state.db, request_id).await?;
+        let payload = SlaEventPayload {
+            request_id,
+            target,
+            due_at,
+        };
+        let event = if breached {
+            ServerEvent::SlaBreached(payload)
+        } else {
+            ServerEvent::SlaWarning(payload)
+        };
+        publish_event(state, &recipients, event).await;
+    }
+
+    Ok(published)
+}
+
+pub(super) fn workspace_of(user: &AuthUserRow) -> Result<String, AppError> {
+    email_domain(&user.email).ok_or_else(|| {
+        AppError::NotFound("account has no workspace".to_string())
+    })
+}
+
+// SLA settings, routing and escalation rules act on other members'
+// requests, so only a workspace admin may change them.
+pub(super) async fn admin_workspace_of<'e, E>(
+    executor: E,
+    user: &AuthUserRow,
+    settings: &str,
+) -> Result<String, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let workspace = workspace_of(user)?;
+    let admin: Option<bool> = sqlx::query_scalar(
+        "SELECT workspace_admin FROM app.app_users WHERE id = $1",
+    )
+    .bind(user.id)
+    .fetch_optional(executor)
+    .await?;
+    if admin != Some(true) {
+        return Err(AppError::Forbidden(format!(
+            "only workspace admins can change {settings}"
+        )));
+    }
+
+    Ok(workspace)
+}
+
+pub async fn set_workspace_admin(
+    pool: &PgPool,
+    email: &str,
+    admin: bool,
+) -> Result<(), AppError> {
+    let updated = sqlx::query(
+        "UPDATE app.app_users
+         SET workspace_admin = $2
+         WHERE lower(email) = lower($1) AND deleted_at IS NULL",
+    )
+    .bind(email.trim())
+    .bind(admin)
+    .execute(pool)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        return Err(AppError::NotFound(format!(
+            "no active user with email {email}"
+        )));
+    }
+
+    Ok(())
+}
+
+async fn fetch_calendar<'e, E>(
+    executor: E,
+    workspace: &str,
+    id: Uuid,
+) -> Result<BusinessCalendarRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, BusinessCalendarRow>(&format!(
+        "SELECT {CALENDAR_COLUMNS}
+         FROM app.business_calendars
+         WHERE id = $1 AND workspace_domain = $2"
+    ))
+    .bind(id)
+    .bind(workspace)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("calendar not found".to_string()))
+}
+
+async fn fetch_policy<'e, E>(
+    executor: E,
+    workspace: &str,
+    id: Uuid,
+) -> Result<SlaPolicyRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, SlaPolicyRow>(&format!(
+        "SELECT {POLICY_COLUMNS}
+         FROM app.sla_policies
+         WHERE id = $1 AND workspace_domain = $2"
+    ))
+    .bind(id)
+    .bind(workspace)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("SLA policy not found".to_string()))
+}
+
+async fn ensure_calendar(
+    conn: &mut PgConnection,
+    workspace: &str,
+    calendar_id: Uuid,
+) -> Result<(), AppError> {
+    match fetch_calendar(&mut *conn, workspace, calendar_id).await {
+        Ok(_) => Ok(()),
+        Err(AppError::NotFound(_)) => {
+            Err(validation_error("calendar_id", "calendar not found"))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn validate_calendar(
+    name: &str,
+    timezone: &str,
+    mut work_days: Vec<i16>,
+    day_start: &str,
+    day_end: &str,
+    mut holidays: Vec<NaiveDate>,
+) -> Result<CalendarFields, AppError> {
+    let mut details = Vec::new();
+
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > 80 {
+        details.push(ErrorDetail {
+            field: "name".to_string(),
+            message: "name must be 1-80 characters".to_string(),
+        });
+    }
+    let timezone = timezone.trim();
+    if timezone.parse::<Tz>().is_err() {
+        details.push(ErrorDetail {
+            field: "timezone".to_string(),
+            message: "timezone must be an IANA time zone such as Europe/Berlin"
+                .to_string(),
+        });
+    }
+    work_days.sort_unstable();
+    work_days.dedup();
+    if work_days.is_empty()
+        || work_days.iter().any(|day| !(1..=7).contains(day))
+    {
+        details.push(ErrorDetail {
+            field: "work_days".to_string(),
+            message: "work_days must list ISO weekdays from 1 (Monday) to 7"
+                .to_string(),
+        });
+    }
+    let day_start = parse_time("day_start", day_start)
+        .map_err(|detail| details.push(detail))
+        .ok();
+    let day_end = parse_time("day_end", day_end)
+        .map_err(|detail| details.push(detail))
+        .ok();
+    if let (Some(start), Some(end)) = (day_start, day_end)
+        && start >= end
+    {
+        details.push(ErrorDetail {
+            field: "day_end".to_string(),
+            message: "day_end must be after day_start".to_string(),
+        });
+    }
+    holidays.sort_unstable();
+    holidays.dedup();
+    if holidays.len() > MAX_HOLIDAYS {
+        details.push(ErrorDetail {
+            field: "holidays".to_string(),
+            message: format!("holidays must have at most {MAX_HOLIDAYS} dates"),
+        });
+    }
+
+    match (day_start, day_end) {
+        (Some(day_start), Some(day_end)) if details.is_empty() => {
+            Ok(CalendarFields {
+                name: name.to_string(),
+                timezone: timezone.to_string(),
+                work_days,
+                day_start,
+                day_end,
+                holidays,
+            })
+        }
+        _ => Err(AppError::Validation(details)),
+    }
+}
+
+fn parse_time(field: &str, value: &str) -> Result<NaiveTime, ErrorDetail> {
+    let value = value.trim();
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .map_err(|_| ErrorDetail {
+            field: field.to_string(),
+            message: format!("{field} must be a time such as 09:00"),
+        })
+}
+
+fn validate_targets(
+    first_response_minutes: i32,
+    resolution_minutes: i32,
+    warning_percent: i16,
+) -> Vec<ErrorDetail> {
+    let mut details = Vec::new();
+
+    for (field, minutes) in [
+        ("first_response_minutes", first_response_minutes),
+        ("resolution_minutes", resolution_minutes),
+    ] {
+        if !(1..=MAX_TARGET_MINUTES).contains(&minutes) {
+            details.push(ErrorDetail {
+                field: field.to_string(),
+                message: format!(
+                    "{field} must be between 1 and {MAX_TARGET_MINUTES}"
+                ),
+            });
+        }
+    }
+    if details.is_empty() && resolution_minutes < first_response_minutes {
+        details.push(ErrorDetail {
+            field: "resolution_minutes".to_string(),
+            message:
+                "resolution_minutes must be at least first_response_minutes"
+                    .to_string(),
+        });
+    }
+    if !(1..=99).contains(&warning_percent) {
+        details.push(ErrorDetail {
+            field: "warning_percent".to_string(),
+            message: "warning_percent must be between 1 and 99".to_string(),
+        });
+    }
+
+    details
+}
+
+fn map_sla_write_error(error: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_error) = &error {
+        match db_error.constraint() {
+            Some("idx_business_calendars_workspace_name_unique") => {
+                return validation_error(
+                    "name",
+                    "a calendar with this name already exists",
+                );
+            }
+            Some("sla_policies_workspace_domain_category_priority_key") => {
+                return validation_error(
+                    "priority",
+                    "a policy for this category and priority already exists",
+                );
+            }
+            Some("sla_policies_calendar_id_fkey") => {
+                return validation_error(
+                    "id",
+                    "calendar is used by an SLA policy",
+                );
+            }
+            _ => {}
+        }
+    }
+
+    AppError::Database(error)
+}
+
+fn validation_error(field: &str, message: &str) -> AppError {
+    AppError::Validation(vec![ErrorDetail {
+        field: field.to_string(),
+        message: message.to_string(),
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("timestamp should parse")
+            .to_utc()
+    }
+
+    fn change(at: &str, status: &str) -> StatusChange {
+        StatusChange {
+            at: utc(at),
+            status: status.to_string(),
+        }
+    }
+
+    fn targets() -> SlaTargets {
+        SlaTargets {
+            first_response: TimeDelta::hours(1),
+            resolution: TimeDelta::hours(8),
+            warning_percent: 75,
+        }
+    }
+
+    #[test]
+    fn deadlines_count_from_creation() {
+        let created_at = utc("2026-03-02T08:00:00Z");
+        let deadlines = compute_deadlines(
+            None,
+            created_at,
+            &[change("2026-03-02T08:00:00Z", "open")],
+            targets(),
+        );
+
+        assert_eq!(
+            deadlines,
+            SlaDeadlines {
+                first_response_due_at: utc("2026-03-02T09:00:00Z"),
+                first_response_warn_at: utc("2026-03-02T08:45:00Z"),
+                first_responded_at: None,
+                resolution_due_at: utc("2026-03-02T16:00:00Z"),
+                resolution_warn_at: utc("2026-03-02T14:00:00Z"),
+            }
+        );
+    }
+
+    #[test]
+    fn time_spent_resolved_before_reopening_does_not_count() {
+        let created_at = utc("2026-03-02T08:00:00Z");
+        let history = [
+            change("2026-03-02T08:00:00Z", "open"),
+            change("2026-03-02T08:30:00Z", "in_progress"),
+            change("2026-03-02T10:00:00Z", "resolved"),
+            change("2026-03-03T10:00:00Z", "open"),
+        ];
+        let deadlines =
+            compute_deadlines(None, created_at, &history, targets());
+
+        assert_eq!(
+            deadlines.first_responded_at,
+            Some(utc("2026-03-02T08:30:00Z"))
+        );
+        // Two of eight hours were used before resolving; the remaining six
+        // run from the reopening.
+        assert_eq!(deadlines.resolution_due_at, utc("2026-03-03T16:00:00Z"));
+    }
+
+    #[test]
+    fn late_resolution_keeps_the_missed_deadline() {
+        let created_at = utc("2026-03-02T08:00:00Z");
+        let history = [
+            change("2026-03-02T08:00:00Z", "open"),
+            change("2026-03-02T20:00:00Z", "resolved"),
+        ];
+        let deadlines =
+            compute_deadlines(None, created_at, &history, targets());
+
+        assert_eq!(deadlines.resolution_due_at, utc("2026-03-02T16:00:00Z"));
+        assert_eq!(
+            deadlines.first_responded_at,
+            Some(utc("2026-03-02T20:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn validate_calendar_collects_every_invalid_field() {
+        let err = validate_calendar(
+            " ",
+            "Mars/Olympus",
+            vec![0, 8],
+            "17:00",
+            "09:00",
+            Vec::new(),
+        )
+        .err()
+        .expect("calendar should fail");
+
+        let AppError::Validation(details) = err else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> =
+            details.iter().map(|detail| detail.field.as_str()).collect();
+        assert_eq!(fields, ["name", "timezone", "work_days", "day_end"]);
+    }
+}
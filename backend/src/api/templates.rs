@@ -0,0 +1,740 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json as SqlJson};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::{
+    AuthUserRow, CreateRequestInput, checklists, email_domain, ensure_owner,
+    is_valid_email, require_authenticated_user, share_domain,
+    validate_category, validate_priority,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const MAX_PLACEHOLDERS: usize = 20;
+
+#[derive(Debug, FromRow)]
+struct RequestTemplateRow {
+    id: Uuid,
+    owner_user_id: Uuid,
+    name: String,
+    title_template: String,
+    description_template: Option<String>,
+    category: String,
+    priority: String,
+    assignee_email: Option<String>,
+    checklist: SqlJson<Vec<String>>,
+    checklist_required: bool,
+    shared: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct RequestTemplate {
+    id: Uuid,
+    owner_user_id: Uuid,
+    name: String,
+    title_template: String,
+    description_template: Option<String>,
+    category: String,
+    priority: String,
+    assignee_email: Option<String>,
+    checklist: Vec<String>,
+    checklist_required: bool,
+    placeholders: Vec<String>,
+    shared: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<RequestTemplateRow> for RequestTemplate {
+    fn from(row: RequestTemplateRow) -> Self {
+        let placeholders = template_placeholders(
+            &row.title_template,
+            row.description_template.as_deref(),
+        )
+        .unwrap_or_default();
+
+        Self {
+            id: row.id,
+            owner_user_id: row.owner_user_id,
+            name: row.name,
+            title_template: row.title_template,
+            description_template: row.description_template,
+            category: row.category,
+            priority: row.priority,
+            assignee_email: row.assignee_email,
+            checklist: row.checklist.0,
+            checklist_required: row.checklist_required,
+            placeholders,
+            shared: row.shared,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateTemplateInput {
+    name: String,
+    title_template: String,
+    description_template: Option<String>,
+    category: String,
+    priority: String,
+    assignee_email: Option<String>,
+    #[serde(default)]
+    checklist: Vec<String>,
+    #[serde(default)]
+    checklist_required: bool,
+    #[serde(default)]
+    shared: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateTemplateInput {
+    name: Option<String>,
+    title_template: Option<String>,
+    description_template: Option<String>,
+    category: Option<String>,
+    priority: Option<String>,
+    assignee_email: Option<String>,
+    checklist: Option<Vec<String>>,
+    checklist_required: Option<bool>,
+    shared: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateRequestBody {
+    template_id: Option<Uuid>,
+    variables: Option<HashMap<String, String>>,
+    title: Option<String>,
+    description: Option<String>,
+    category: Option<String>,
+    priority: Option<String>,
+    assignee_email: Option<String>,
+    checklist: Option<Vec<String>>,
+    checklist_required: Option<bool>,
+}
+
+impl CreateRequestBody {
+    pub(super) fn from_template(
+        template_id: Uuid,
+        variables: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            template_id: Some(template_id),
+            variables: Some(variables),
+            title: None,
+            description: None,
+            category: None,
+            priority: None,
+            assignee_email: None,
+            checklist: None,
+            checklist_required: None,
+        }
+    }
+}
+
+fn template_projection_sql() -> &'static str {
+    "template.id,
+     template.owner_user_id,
+     template.name,
+     template.title_template,
+     template.description_template,
+     template.category,
+     template.priority,
+     template.assignee_email,
+     template.checklist,
+     template.checklist_required,
+     template.workspace_domain IS NOT NULL AS shared,
+     template.created_at,
+     template.updated_at"
+}
+
+pub(super) async fn list_templates(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+
+    let query = format!(
+        "SELECT {}
+         FROM app.request_templates template
+         WHERE template.owner_user_id = $1 OR template.workspace_domain = $2
+         ORDER BY template.owner_user_id = $1 DESC, lower(template.name) ASC",
+        template_projection_sql()
+    );
+    let templates = sqlx::query_as::<_, RequestTemplateRow>(&query)
+        .bind(user.id)
+        .bind(email_domain(&user.email))
+        .fetch_all(&state.db)
+        .await?
+        .into_iter()
+        .map(RequestTemplate::from)
+        .collect::<Vec<_>>();
+
+    Ok(response::ok(StatusCode::OK, templates))
+}
+
+pub(super) async fn create_template(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreateTemplateInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let fields = validate_template(TemplateFields {
+        name: input.name,
+        title_template: input.title_template,
+        description_template: input.description_template,
+        category: input.category,
+        priority: input.priority,
+        assignee_email: input.assignee_email,
+        checklist: input.checklist,
+        checklist_required: input.checklist_required,
+    })?;
+    let workspace_domain = share_domain(&user, input.shared, "template")?;
+
+    let template_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.request_templates (
+           owner_user_id,
+           name,
+           title_template,
+           description_template,
+           category,
+           priority,
+           assignee_email,
+           checklist,
+           checklist_required,
+           workspace_domain
+         )
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+         RETURNING id",
+    )
+    .bind(user.id)
+    .bind(&fields.name)
+    .bind(&fields.title_template)
+    .bind(fields.description_template.as_deref())
+    .bind(&fields.category)
+    .bind(&fields.priority)
+    .bind(fields.assignee_email.as_deref())
+    .bind(SqlJson(&fields.checklist))
+    .bind(fields.checklist_required)
+    .bind(workspace_domain)
+    .fetch_one(&state.db)
+    .await
+    .map_err(map_template_write_error)?;
+
+    let template =
+        fetch_visible_template(&state.db, &user, template_id).await?;
+    Ok(response::ok(
+        StatusCode::CREATED,
+        RequestTemplate::from(template),
+    ))
+}
+
+pub(super) async fn get_template(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let template = fetch_visible_template(&state.db, &user, id).await?;
+
+    Ok(response::ok(
+        StatusCode::OK,
+        RequestTemplate::from(template),
+    ))
+}
+
+pub(super) async fn update_template(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateTemplateInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_template(&state.db, &user, id).await?;
+    let fields = validate_template(TemplateFields {
+        name: input.name.unwrap_or(existing.name),
+        title_template: input.title_template.unwrap_or(existing.title_template),
+        description_template: input
+            .description_template
+            .or(existing.description_template),
+        category: input.category.unwrap_or(existing.category),
+        priority: input.priority.unwrap_or(existing.priority),
+        assignee_email: input.assignee_email.or(existing.assignee_email),
+        checklist: input.checklist.unwrap_or(existing.checklist.0),
+        checklist_required: input
+            .checklist_required
+            .unwrap_or(existing.checklist_required),
+    })?;
+    let workspace_domain = share_domain(
+        &user,
+        input.shared.unwrap_or(existing.shared),
+        "template",
+    )?;
+
+    sqlx::query(
+        "UPDATE app.request_templates
+         SET name = $2,
+             title_template = $3,
+             description_template = $4,
+             category = $5,
+             priority = $6,
+             assignee_email = $7,
+             checklist = $8,
+             checklist_required = $9,
+             workspace_domain = $10
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(&fields.name)
+    .bind(&fields.title_template)
+    .bind(fields.description_template.as_deref())
+    .bind(&fields.category)
+    .bind(&fields.priority)
+    .bind(fields.assignee_email.as_deref())
+    .bind(SqlJson(&fields.checklist))
+    .bind(fields.checklist_required)
+    .bind(workspace_domain)
+    .execute(&state.db)
+    .await
+    .map_err(map_template_write_error)?;
+
+    let template = fetch_visible_template(&state.db, &user, id).await?;
+    Ok(response::ok(
+        StatusCode::OK,
+        RequestTemplate::from(template),
+    ))
+}
+
+pub(super) async fn delete_template(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_template(&state.db, &user, id).await?;
+    sqlx::query("DELETE FROM app.request_templates WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn resolve_create_input(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    body: CreateRequestBody,
+) -> Result<CreateRequestInput, AppError> {
+    let Some(template_id) = body.template_id else {
+        if body.variables.is_some() {
+            return Err(AppError::Validation(vec![ErrorDetail {
+                field: "variables".to_string(),
+                message: "variables require a template_id".to_string(),
+            }]));
+        }
+
+        return Ok(CreateRequestInput {
+            title: body.title.unwrap_or_default(),
+            description: body.description,
+            category: body.category.unwrap_or_default(),
+            priority: body.priority.unwrap_or_default(),
+            assignee_email: body.assignee_email,
+            checklist: body.checklist.unwrap_or_default(),
+            checklist_required: body.checklist_required.unwrap_or_default(),
+            created_at: None,
+        });
+    };
+
+    let template = match fetch_visible_template(pool, user, template_id).await {
+        Ok(template) => template,
+        Err(AppError::NotFound(_)) => {
+            return Err(AppError::Validation(vec![ErrorDetail {
+                field: "template_id".to_string(),
+                message: "template not found".to_string(),
+            }]));
+        }
+        Err(err) => return Err(err),
+    };
+
+    let variables = body.variables.unwrap_or_default();
+    let title = render(&template.title_template, &variables);
+    let description = template
+        .description_template
+        .as_deref()
+        .map(|description| render(description, &variables));
+
+    let mut details = Vec::new();
+    let placeholders = template_placeholders(
+        &template.title_template,
+        template.description_template.as_deref(),
+    )
+    .unwrap_or_default();
+    for name in &placeholders {
+        let missing = variables
+            .get(name)
+            .is_none_or(|value| value.trim().is_empty());
+        if missing {
+            details.push(ErrorDetail {
+                field: format!("variables.{name}"),
+                message: format!("{name} is required by the template"),
+            });
+        }
+    }
+    let mut unknown: Vec<_> = variables
+        .keys()
+        .filter(|name| !placeholders.contains(name))
+        .collect();
+    unknown.sort();
+    for name in unknown {
+        details.push(ErrorDetail {
+            field: format!("variables.{name}"),
+            message: format!("{name} is not a placeholder of the template"),
+        });
+    }
+    if !details.is_empty() {
+        return Err(AppError::Validation(details));
+    }
+
+    Ok(CreateRequestInput {
+        title: body.title.unwrap_or(title),
+        description: body.description.or(description),
+        category: body.category.unwrap_or(template.category),
+        priority: body.priority.unwrap_or(template.priority),
+        assignee_email: body.assignee_email.or(template.assignee_email),
+        checklist: body.checklist.unwrap_or(template.checklist.0),
+        checklist_required: body
+            .checklist_required
+            .unwrap_or(template.checklist_required),
+        created_at: None,
+    })
+}
+
+async fn fetch_visible_template(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    template_id: Uuid,
+) -> Result<RequestTemplateRow, AppError> {
+    let query = format!(
+        "SELECT {}
+         FROM app.request_templates template
+         WHERE template.id = $1
+           AND (
+             template.owner_user_id = $2
+             OR template.workspace_domain = $3
+           )",
+        template_projection_sql()
+    );
+
+    sqlx::query_as::<_, RequestTemplateRow>(&query)
+        .bind(template_id)
+        .bind(user.id)
+        .bind(email_domain(&user.email))
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("template not found".to_string()))
+}
+
+async fn fetch_owned_template(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    template_id: Uuid,
+) -> Result<RequestTemplateRow, AppError> {
+    let template = fetch_visible_template(pool, user, template_id).await?;
+    ensure_owner(template.owner_user_id, user, "template")?;
+    Ok(template)
+}
+
+#[derive(Debug)]
+struct TemplateFields {
+    name: String,
+    title_template: String,
+    description_template: Option<String>,
+    category: String,
+    priority: String,
+    assignee_email: Option<String>,
+    checklist: Vec<String>,
+    checklist_required: bool,
+}
+
+fn validate_template(
+    fields: TemplateFields,
+) -> Result<TemplateFields, AppError> {
+    let mut details = Vec::new();
+
+    let name = fields.name.trim().to_string();
+    if name.is_empty() {
+        details.push(ErrorDetail {
+            field: "name".to_string(),
+            message: "name is required".to_string(),
+        });
+    } else if name.chars().count() > 80 {
+        details.push(ErrorDetail {
+            field: "name".to_string(),
+            message: "name must be at most 80 characters".to_string(),
+        });
+    }
+
+    let title_template = fields.title_template.trim().to_string();
+    if title_template.is_empty() {
+        details.push(ErrorDetail {
+            field: "title_template".to_string(),
+            message: "title_template is required".to_string(),
+        });
+    } else if title_template.len() > 255 {
+        details.push(ErrorDetail {
+            field: "title_template".to_string(),
+            message: "title_template must be <= 255 characters".to_string(),
+        });
+    }
+
+    let description_template = fields
+        .description_template
+        .filter(|description| !description.trim().is_empty());
+    if let Some(description) = &description_template
+        && description.len() > 5000
+    {
+        details.push(ErrorDetail {
+            field: "description_template".to_string(),
+            message: "description_template must be <= 5000 characters"
+                .to_string(),
+        });
+    }
+
+    if let Err(message) =
+        template_placeholders(&title_template, description_template.as_deref())
+    {
+        details.push(ErrorDetail {
+            field: "title_template".to_string(),
+            message,
+        });
+    }
+
+    if let Err(AppError::Validation(errors)) =
+        validate_category(&fields.category)
+    {
+        details.extend(errors);
+    }
+    if let Err(AppError::Validation(errors)) =
+        validate_priority(&fields.priority)
+    {
+        details.extend(errors);
+    }
+
+    let assignee_email = fields
+        .assignee_email
+        .map(|email| email.trim().to_lowercase())
+        .filter(|email| !email.is_empty());
+    if let Some(email) = &assignee_email
+        && !is_valid_email(email)
+    {
+        details.push(ErrorDetail {
+            field: "assignee_email".to_string(),
+            message: "assignee_email must be a valid email address".to_string(),
+        });
+    }
+
+    details.extend(checklists::validate_titles("checklist", &fields.checklist));
+
+    if !details.is_empty() {
+        return Err(AppError::Validation(details));
+    }
+
+    Ok(TemplateFields {
+        name,
+        title_template,
+        description_template,
+        category: fields.category,
+        priority: fields.priority,
+        assignee_email,
+        checklist: fields
+            .checklist
+            .iter()
+            .map(|title| title.trim().to_string())
+            .collect(),
+        checklist_required: fields.checklist_required,
+    })
+}
+
+fn template_placeholders(
+    title: &str,
+    description: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    for text in std::iter::once(title).chain(description) {
+        for name in scan_placeholders(text)? {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    if names.len() > MAX_PLACEHOLDERS {
+        return Err(format!(
+            "templates may use at most {MAX_PLACEHOLDERS} placeholders"
+        ));
+    }
+    Ok(names)
+}
+
+fn scan_placeholders(text: &str) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            "placeholder is missing its closing }}".to_string()
+        })?;
+        let name = after[..end].trim();
+        let valid = !name.is_empty()
+            && name.len() <= 40
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+        if !valid {
+            return Err(format!(
+                "placeholder {{{{{name}}}}} must be letters, digits or _"
+            ));
+        }
+        names.push(name.to_string());
+        rest = &after[end + 2..];
+    }
+    Ok(names)
+}
+
+fn render(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = after[..end].trim();
+        out.push_str(variables.get(name).map_or("", |value| value.trim()));
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn map_template_write_error(error: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_error) = &error
+        && db_error.constraint()
+            == Some("idx_request_templates_owner_name_unique")
+    {
+        return AppError::Validation(vec![ErrorDetail {
+            field: "name".to_string(),
+            message: "a template with this name already exists".to_string(),
+        }]);
+    }
+
+    AppError::Database(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_collected_once_in_order() {
+        let names = template_placeholders(
+            "New hire laptop for {{ name }}",
+            Some("Start date {{start_date}}, owner {{name}}"),
+        )
+        .expect("placeholders should parse");
+        assert_eq!(names, ["name", "start_date"]);
+
+        assert!(template_placeholders("Broken {{name", None).is_err());
+        assert!(template_placeholders("Bad {{first name}}", None).is_err());
+    }
+
+    #[test]
+    fn render_fills_trimmed_values() {
+        let variables = HashMap::from([
+            ("name".to_string(), "  Ada ".to_string()),
+            ("team".to_string(), "HR".to_string()),
+        ]);
+
+        assert_eq!(
+            render("Laptop for {{name}} ({{ team }})", &variables),
+            "Laptop for Ada (HR)"
+        );
+    }
+
+    #[test]
+    fn validate_template_normalizes_and_collects_errors() {
+        let fields = validate_template(TemplateFields {
+            name: " Laptop ".to_string(),
+            title_template: "Laptop for {{name}}".to_string(),
+            description_template: Some("  ".to_string()),
+            category: "HR".to_string(),
+            priority: "medium".to_string(),
+            assignee_email: Some(" IT@Example.com ".to_string()),
+            checklist: vec![" Ship laptop ".to_string()],
+            checklist_required: true,
+        })
+        .expect("template should validate");
+        assert_eq!(fields.name, "Laptop");
+        assert!(fields.description_template.is_none());
+        assert_eq!(fields.assignee_email.as_deref(), Some("it@example.com"));
+        assert_eq!(fields.checklist, ["Ship laptop"]);
+
+        let err = validate_template(TemplateFields {
+            name: String::new(),
+            title_template: "{{".to_string(),
+            description_template: None,
+            category: "Finance".to_string(),
+            priority: "urgent".to_string(),
+            assignee_email: Some("nope".to_string()),
+            checklist: vec![" ".to_string()],
+            checklist_required: false,
+        })
+        .expect_err("template should fail");
+        let AppError::Validation(details) = err else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> =
+            details.iter().map(|detail| detail.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "name",
+                "title_template",
+                "category",
+                "priority",
+                "assignee_email",
+                "checklist[0]"
+            ]
+        );
+    }
+}
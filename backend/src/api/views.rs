@@ -0,0 +1,484 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool, types::Json as SqlJson};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::filters::RequestListFilter;
+use super::pagination::RequestSort;
+use super::{
+    AuthUserRow, ListRequestsQuery, email_domain, ensure_owner,
+    require_authenticated_user, share_domain,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+const DEFAULT_VIEW_ALIAS: &str = "default";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ViewFilters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sla: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    q: Option<String>,
+}
+
+impl ViewFilters {
+    pub(super) fn to_list_query(&self) -> ListRequestsQuery {
+        ListRequestsQuery {
+            status: self.status.clone(),
+            category: self.category.clone(),
+            priority: self.priority.clone(),
+            assignee: self.assignee.clone(),
+            owner: self.owner.clone(),
+            created_from: self.created_from.clone(),
+            created_to: self.created_to.clone(),
+            updated_from: self.updated_from.clone(),
+            updated_to: self.updated_to.clone(),
+            resolved_from: self.resolved_from.clone(),
+            resolved_to: self.resolved_to.clone(),
+            sla: self.sla.clone(),
+            q: self.q.clone(),
+            ..ListRequestsQuery::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, FromRow)]
+struct SavedViewRow {
+    id: Uuid,
+    owner_user_id: Uuid,
+    name: String,
+    filters: SqlJson<ViewFilters>,
+    sort: Option<String>,
+    page_size: Option<i32>,
+    shared: bool,
+    is_default: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateViewInput {
+    name: String,
+    #[serde(default)]
+    filters: ViewFilters,
+    sort: Option<String>,
+    page_size: Option<i32>,
+    #[serde(default)]
+    shared: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateViewInput {
+    name: Option<String>,
+    filters: Option<ViewFilters>,
+    sort: Option<String>,
+    page_size: Option<i32>,
+    shared: Option<bool>,
+}
+
+fn saved_view_projection_sql() -> &'static str {
+    "view.id,
+     view.owner_user_id,
+     view.name,
+     view.filters,
+     view.sort,
+     view.page_size,
+     view.workspace_domain IS NOT NULL AS shared,
+     COALESCE(prefs.default_view_id = view.id, FALSE) AS is_default,
+     view.created_at,
+     view.updated_at"
+}
+
+pub(super) async fn list_views(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+
+    let query = format!(
+        "SELECT {}
+         FROM app.saved_views view
+         LEFT JOIN app.user_preferences prefs ON prefs.user_id = $1
+         WHERE view.owner_user_id = $1 OR view.workspace_domain = $2
+         ORDER BY view.owner_user_id = $1 DESC, lower(view.name) ASC",
+        saved_view_projection_sql()
+    );
+    let views = sqlx::query_as::<_, SavedViewRow>(&query)
+        .bind(user.id)
+        .bind(email_domain(&user.email))
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(response::ok(StatusCode::OK, views))
+}
+
+pub(super) async fn create_view(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreateViewInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let name = validate_view(
+        &input.name,
+        &input.filters,
+        input.sort.as_deref(),
+        input.page_size,
+    )?;
+    let workspace_domain = share_domain(&user, input.shared, "view")?;
+
+    let view_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.saved_views (
+           owner_user_id,
+           name,
+           filters,
+           sort,
+           page_size,
+           workspace_domain
+         )
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING id",
+    )
+    .bind(user.id)
+    .bind(name)
+    .bind(SqlJson(&input.filters))
+    .bind(input.sort.as_deref())
+    .bind(input.page_size)
+    .bind(workspace_domain)
+    .fetch_one(&state.db)
+    .await
+    .map_err(map_view_write_error)?;
+
+    let view = fetch_visible_view(&state.db, &user, view_id).await?;
+    Ok(response::ok(StatusCode::CREATED, view))
+}
+
+pub(super) async fn get_view(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let view = fetch_visible_view(&state.db, &user, id).await?;
+
+    Ok(response::ok(StatusCode::OK, view))
+}
+
+pub(super) async fn update_view(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateViewInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_view(&state.db, &user, id).await?;
+    let name = input.name.unwrap_or(existing.name);
+    let filters = input.filters.unwrap_or(existing.filters.0);
+    let sort = input.sort.or(existing.sort);
+    let page_size = input.page_size.or(existing.page_size);
+    let name = validate_view(&name, &filters, sort.as_deref(), page_size)?;
+    let workspace_domain =
+        share_domain(&user, input.shared.unwrap_or(existing.shared), "view")?;
+
+    sqlx::query(
+        "UPDATE app.saved_views
+         SET name = $2,
+             filters = $3,
+             sort = $4,
+             page_size = $5,
+             workspace_domain = $6
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(name)
+    .bind(SqlJson(&filters))
+    .bind(sort.as_deref())
+    .bind(page_size)
+    .bind(workspace_domain)
+    .execute(&state.db)
+    .await
+    .map_err(map_view_write_error)?;
+
+    let view = fetch_visible_view(&state.db, &user, id).await?;
+    Ok(response::ok(StatusCode::OK, view))
+}
+
+pub(super) async fn delete_view(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_view(&state.db, &user, id).await?;
+    sqlx::query("DELETE FROM app.saved_views WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn apply_view(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    mut query: ListRequestsQuery,
+) -> Result<ListRequestsQuery, AppError> {
+    let Some(raw) = query.view.as_deref().map(str::trim) else {
+        return Ok(query);
+    };
+
+    let view = if raw == DEFAULT_VIEW_ALIAS {
+        match default_view_id(pool, user.id).await? {
+            Some(id) => match fetch_visible_view(pool, user, id).await {
+                Ok(view) => Some(view),
+                Err(AppError::NotFound(_)) => None,
+                Err(err) => return Err(err),
+            },
+            None => None,
+        }
+    } else {
+        let id = Uuid::parse_str(raw).map_err(|_| {
+            AppError::Validation(vec![ErrorDetail {
+                field: "view".to_string(),
+                message: "view must be a view id or default".to_string(),
+            }])
+        })?;
+        Some(fetch_visible_view(pool, user, id).await?)
+    };
+
+    let Some(view) = view else {
+        return Ok(query);
+    };
+    let filters = view.filters.0;
+
+    query.status = query.status.or(filters.status);
+    query.category = query.category.or(filters.category);
+    query.priority = query.priority.or(filters.priority);
+    query.assignee = query.assignee.or(filters.assignee);
+    query.owner = query.owner.or(filters.owner);
+    query.created_from = query.created_from.or(filters.created_from);
+    query.created_to = query.created_to.or(filters.created_to);
+    query.updated_from = query.updated_from.or(filters.updated_from);
+    query.updated_to = query.updated_to.or(filters.updated_to);
+    query.resolved_from = query.resolved_from.or(filters.resolved_from);
+    query.resolved_to = query.resolved_to.or(filters.resolved_to);
+    query.sla = query.sla.or(filters.sla);
+    query.q = query.q.or(filters.q);
+    query.sort = query.sort.or(view.sort);
+    query.limit = query
+        .limit
+        .or(view.page_size.map(|page_size| page_size as u64));
+
+    Ok(query)
+}
+
+pub(super) async fn ensure_view_visible(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    view_id: Uuid,
+) -> Result<(), AppError> {
+    match fetch_visible_view(pool, user, view_id).await {
+        Ok(_) => Ok(()),
+        Err(AppError::NotFound(_)) => {
+            Err(AppError::Validation(vec![ErrorDetail {
+                field: "default_view_id".to_string(),
+                message: "view not found".to_string(),
+            }]))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+async fn default_view_id(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    let id = sqlx::query_scalar::<_, Option<Uuid>>(
+        "SELECT default_view_id
+         FROM app.user_preferences
+         WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .flatten();
+
+    Ok(id)
+}
+
+async fn fetch_visible_view(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    view_id: Uuid,
+) -> Result<SavedViewRow, AppError> {
+    let query = format!(
+        "SELECT {}
+         FROM app.saved_views view
+         LEFT JOIN app.user_preferences prefs ON prefs.user_id = $2
+         WHERE view.id = $1
+           AND (view.owner_user_id = $2 OR view.workspace_domain = $3)",
+        saved_view_projection_sql()
+    );
+
+    sqlx::query_as::<_, SavedViewRow>(&query)
+        .bind(view_id)
+        .bind(user.id)
+        .bind(email_domain(&user.email))
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("view not found".to_string()))
+}
+
+async fn fetch_owned_view(
+    pool: &PgPool,
+    user: &AuthUserRow,
+    view_id: Uuid,
+) -> Result<SavedViewRow, AppError> {
+    let view = fetch_visible_view(pool, user, view_id).await?;
+    ensure_owner(view.owner_user_id, user, "view")?;
+    Ok(view)
+}
+
+fn validate_view<'a>(
+    name: &'a str,
+    filters: &ViewFilters,
+    sort: Option<&str>,
+    page_size: Option<i32>,
+) -> Result<&'a str, AppError> {
+    let mut details = Vec::new();
+
+    let name = name.trim();
+    if name.is_empty() {
+        details.push(ErrorDetail {
+            field: "name".to_string(),
+            message: "name is required".to_string(),
+        });
+    } else if name.chars().count() > 80 {
+        details.push(ErrorDetail {
+            field: "name".to_string(),
+            message: "name must be at most 80 characters".to_string(),
+        });
+    }
+
+    if let Some(page_size) = page_size
+        && !(1..=100).contains(&page_size)
+    {
+        details.push(ErrorDetail {
+            field: "page_size".to_string(),
+            message: "page_size must be between 1 and 100".to_string(),
+        });
+    }
+
+    match RequestListFilter::from_query(&filters.to_list_query()) {
+        Ok(filter) => {
+            if let Err(AppError::Validation(sort_details)) =
+                RequestSort::parse(sort, filter.is_searching())
+            {
+                details.extend(sort_details);
+            }
+        }
+        Err(AppError::Validation(filter_details)) => {
+            details.extend(filter_details.into_iter().map(|detail| {
+                ErrorDetail {
+                    field: format!("filters.{}", detail.field),
+                    message: detail.message,
+                }
+            }));
+        }
+        Err(err) => return Err(err),
+    }
+
+    if details.is_empty() {
+        Ok(name)
+    } else {
+        Err(AppError::Validation(details))
+    }
+}
+
+fn map_view_write_error(error: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_error) = &error
+        && db_error.constraint() == Some("idx_saved_views_owner_name_unique")
+    {
+        return AppError::Validation(vec![ErrorDetail {
+            field: "name".to_string(),
+            message: "a view with this name already exists".to_string(),
+        }]);
+    }
+
+    AppError::Database(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_view_prefixes_filter_errors_and_checks_sort() {
+        let filters = ViewFilters {
+            status: Some("open,bogus".to_string()),
+            ..ViewFilters::default()
+        };
+
+        let err = validate_view(" ", &filters, Some("nope"), Some(500))
+            .expect_err("view should fail");
+        let AppError::Validation(details) = err else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> =
+            details.iter().map(|detail| detail.field.as_str()).collect();
+        assert_eq!(fields, ["name", "page_size", "filters.status"]);
+    }
+
+    #[test]
+    fn view_filters_reject_unknown_keys() {
+        let err = serde_json::from_value::<ViewFilters>(
+            serde_json::json!({ "stauts": "open" }),
+        );
+        assert!(err.is_err());
+    }
+}
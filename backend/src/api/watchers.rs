@@ -0,0 +1,360 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgConnection, PgExecutor};
+use tower_sessions::Session;
+use uuid::Uuid;
+
+use super::events::{
+    RequestCreatedEventPayload, RequestWatcherEventPayload, ServerEvent,
+};
+use super::{
+    fetch_owned_request, fetch_request_recipient_ids, fetch_visible_request,
+    is_valid_email, publish_event, require_authenticated_user,
+};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    response,
+};
+
+#[derive(Debug, Clone, Serialize, FromRow, JsonSchema)]
+pub(super) struct WatcherRow {
+    user_id: Uuid,
+    email: String,
+    display_name: String,
+    first_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct AddWatcherInput {
+    email: String,
+}
+
+pub(super) async fn list_watchers(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    fetch_visible_request(&state.db, id, user.id).await?;
+
+    let watchers = sqlx::query_as::<_, WatcherRow>(
+        "SELECT
+           users.id AS user_id,
+           users.email,
+           users.display_name,
+           participants.first_seen_at
+         FROM app.request_participants participants
+         JOIN app.app_users users ON users.id = participants.user_id
+         WHERE participants.request_id = $1
+           AND participants.source = 'watcher'
+         ORDER BY participants.first_seen_at ASC, users.email ASC",
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, watchers))
+}
+
+pub(super) async fn add_watcher(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<AddWatcherInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let mut tx = state.db.begin().await?;
+    fetch_owned_request(&mut *tx, id, user.id).await?;
+    let watcher_user_id = resolve_watcher_email(&mut *tx, &input.email).await?;
+    let newly_visible =
+        insert_watcher(&mut tx, id, watcher_user_id, "email").await?;
+    let watcher = fetch_watcher(&mut *tx, id, watcher_user_id).await?;
+    tx.commit().await?;
+
+    publish_watcher_added(&state, id, watcher.clone(), newly_visible).await?;
+    Ok(response::ok(StatusCode::CREATED, watcher))
+}
+
+pub(super) async fn watch_request(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let mut tx = state.db.begin().await?;
+    fetch_visible_request(&mut *tx, id, user.id).await?;
+    insert_watcher(&mut tx, id, user.id, "request").await?;
+    let watcher = fetch_watcher(&mut *tx, id, user.id).await?;
+    tx.commit().await?;
+
+    publish_watcher_added(&state, id, watcher.clone(), false).await?;
+    Ok(response::ok(StatusCode::OK, watcher))
+}
+
+pub(super) async fn unwatch_request(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    fetch_visible_request(&state.db, id, user.id).await?;
+    remove_participant(&state, id, user.id, false).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn remove_watcher(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path((id, watcher_user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    if watcher_user_id == user.id {
+        fetch_visible_request(&state.db, id, user.id).await?;
+        remove_participant(&state, id, user.id, false).await?;
+    } else {
+        fetch_owned_request(&state.db, id, user.id).await?;
+        remove_participant(&state, id, watcher_user_id, true).await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn insert_watcher(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    user_id: Uuid,
+    field: &str,
+) -> Result<bool, AppError> {
+    if let Some(role) = fetch_role(&mut *conn, request_id, user_id).await? {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: field.to_string(),
+            message: format!("already follows this request as its {role}"),
+        }]));
+    }
+
+    let was_participant: bool = sqlx::query_scalar(
+        "SELECT EXISTS (
+           SELECT 1
+           FROM app.request_participants
+           WHERE request_id = $1 AND user_id = $2
+         )",
+    )
+    .bind(request_id)
+    .bind(user_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO app.request_participants (request_id, user_id, source)
+         VALUES ($1, $2, 'watcher')
+         ON CONFLICT (request_id, user_id)
+         DO UPDATE SET
+           source = 'watcher',
+           last_seen_at = GREATEST(
+             app.request_participants.last_seen_at,
+             NOW()
+           )",
+    )
+    .bind(request_id)
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(!was_participant)
+}
+
+async fn remove_participant(
+    state: &AppState,
+    request_id: Uuid,
+    user_id: Uuid,
+    watchers_only: bool,
+) -> Result<(), AppError> {
+    let mut tx = state.db.begin().await?;
+    if let Some(role) = fetch_role(&mut *tx, request_id, user_id).await? {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "request".to_string(),
+            message: format!("the request's {role} cannot stop following it"),
+        }]));
+    }
+
+    let watcher = fetch_watcher(&mut *tx, request_id, user_id).await;
+    let recipients = fetch_request_recipient_ids(&mut *tx, request_id).await?;
+    let removed = sqlx::query(
+        "DELETE FROM app.request_participants
+         WHERE request_id = $1
+           AND user_id = $2
+           AND (NOT $3 OR source = 'watcher')",
+    )
+    .bind(request_id)
+    .bind(user_id)
+    .bind(watchers_only)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    tx.commit().await?;
+
+    match (removed, watcher) {
+        (0, _) if watchers_only => {
+            Err(AppError::NotFound("watcher not found".to_string()))
+        }
+        (_, Ok(watcher)) => {
+            publish_event(
+                state,
+                &recipients,
+                ServerEvent::RequestWatcherRemoved(
+                    RequestWatcherEventPayload {
+                        request_id,
+                        watcher,
+                    },
+                ),
+            )
+            .await;
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn fetch_role<'e, E>(
+    executor: E,
+    request_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<String>, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_scalar(
+        "SELECT CASE
+           WHEN req.owner_user_id = $2 THEN 'owner'
+           WHEN req.assignee_user_id = $2 THEN 'assignee'
+           WHEN EXISTS (
+             SELECT 1
+             FROM app.request_checklist_items item
+             WHERE item.request_id = req.id AND item.assignee_user_id = $2
+           ) THEN 'checklist item assignee'
+         END
+         FROM app.requests req
+         WHERE req.id = $1",
+    )
+    .bind(request_id)
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await
+    .map(Option::flatten)
+    .map_err(AppError::from)
+}
+
+pub(super) async fn fetch_watcher<'e, E>(
+    executor: E,
+    request_id: Uuid,
+    user_id: Uuid,
+) -> Result<WatcherRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, WatcherRow>(
+        "SELECT
+           users.id AS user_id,
+           users.email,
+           users.display_name,
+           participants.first_seen_at
+         FROM app.request_participants participants
+         JOIN app.app_users users ON users.id = participants.user_id
+         WHERE participants.request_id = $1
+           AND participants.user_id = $2
+           AND participants.source = 'watcher'",
+    )
+    .bind(request_id)
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("watcher not found".to_string()))
+}
+
+async fn resolve_watcher_email<'e, E>(
+    executor: E,
+    email: &str,
+) -> Result<Uuid, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let email = email.trim();
+    if !is_valid_email(email) {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "email".to_string(),
+            message: "email must be a valid email address".to_string(),
+        }]));
+    }
+
+    sqlx::query_scalar(
+        "SELECT id
+         FROM app.app_users
+         WHERE lower(email) = lower($1)
+           AND deleted_at IS NULL
+         LIMIT 1",
+    )
+    .bind(email)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| {
+        AppError::Validation(vec![ErrorDetail {
+            field: "email".to_string(),
+            message: "No user exists with this email address".to_string(),
+        }])
+    })
+}
+
+pub(super) async fn publish_watcher_added(
+    state: &AppState,
+    request_id: Uuid,
+    watcher: WatcherRow,
+    newly_visible: bool,
+) -> Result<(), AppError> {
+    if newly_visible {
+        let request =
+            fetch_visible_request(&state.db, request_id, watcher.user_id)
+                .await?;
+        publish_event(
+            state,
+            &[watcher.user_id],
+            ServerEvent::RequestCreated(RequestCreatedEventPayload { request }),
+        )
+        .await;
+    }
+
+    let recipients = fetch_request_recipient_ids(&state.db, request_id).await?;
+    publish_event(
+        state,
+        &recipients,
+        ServerEvent::RequestWatcherAdded(RequestWatcherEventPayload {
+            request_id,
+            watcher,
+        }),
+    )
+    .await;
+
+    Ok(())
+}
@@ -0,0 +1,882 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use futures_util::future::join_all;
+use hmac::{Hmac, Mac};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use sqlx::{FromRow, PgExecutor};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use tower_sessions::Session;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{pagination, require_authenticated_user, workers};
+use crate::{
+    AppState,
+    auth::middleware,
+    error::{AppError, ErrorDetail},
+    realtime::EventEnvelope,
+    response,
+};
+
+const WEBHOOK_EVENT_TYPES: [&str; 7] = [
+    "request.created",
+    "request.patch",
+    "request.deleted",
+    "comment.created",
+    "comment.deleted",
+    "sla.warning",
+    "sla.breached",
+];
+const WEBHOOK_WORKER_INTERVAL: Duration = Duration::from_secs(5);
+const WEBHOOK_BATCH: i64 = 50;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+// Longer than the request timeout, so only crashed attempts are picked
+// up again.
+const WEBHOOK_LEASE_SECONDS: i64 = 60;
+const WEBHOOK_MAX_ATTEMPTS: i32 = 8;
+const WEBHOOK_BACKOFF_BASE_SECONDS: i64 = 30;
+const WEBHOOK_BACKOFF_MAX_SECONDS: i64 = 6 * 60 * 60;
+const SIGNATURE_HEADER: &str = "x-reqstly-signature";
+const EVENT_HEADER: &str = "x-reqstly-event";
+const DELIVERY_HEADER: &str = "x-reqstly-delivery";
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub(super) struct WebhookRow {
+    id: Uuid,
+    url: String,
+    events: Vec<String>,
+    active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub(super) struct WebhookDeliveryRow {
+    id: Uuid,
+    subscription_id: Uuid,
+    event_type: String,
+    request_id: Option<Uuid>,
+    status: String,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    last_attempt_at: Option<DateTime<Utc>>,
+    response_status: Option<i32>,
+    last_error: Option<String>,
+    redelivery_of: Option<Uuid>,
+    body: Value,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CreateWebhookInput {
+    url: String,
+    secret: String,
+    events: Vec<String>,
+    active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct UpdateWebhookInput {
+    url: Option<String>,
+    secret: Option<String>,
+    events: Option<Vec<String>>,
+    active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ListDeliveriesQuery {
+    status: Option<String>,
+    limit: Option<u64>,
+    cursor: Option<Uuid>,
+}
+
+#[derive(Debug, FromRow)]
+struct ClaimedDelivery {
+    id: Uuid,
+    event_type: String,
+    body: Value,
+    attempts: i32,
+    url: String,
+    secret: String,
+}
+
+struct AttemptFailure {
+    response_status: Option<i32>,
+    message: String,
+}
+
+const WEBHOOK_COLUMNS: &str = "id, url, events, active, created_at, updated_at";
+
+fn delivery_select_sql() -> &'static str {
+    "SELECT
+       delivery.id,
+       delivery.subscription_id,
+       delivery.event_type,
+       delivery.request_id,
+       delivery.status,
+       delivery.attempts,
+       delivery.next_attempt_at,
+       delivery.last_attempt_at,
+       delivery.response_status,
+       delivery.last_error,
+       delivery.redelivery_of,
+       delivery.body,
+       delivery.created_at
+     FROM app.webhook_deliveries delivery"
+}
+
+pub(super) async fn list_webhooks(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+
+    let webhooks = sqlx::query_as::<_, WebhookRow>(&format!(
+        "SELECT {WEBHOOK_COLUMNS}
+         FROM app.webhook_subscriptions
+         WHERE owner_user_id = $1
+         ORDER BY created_at ASC, id ASC"
+    ))
+    .bind(user.id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, webhooks))
+}
+
+pub(super) async fn create_webhook(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Json(input): Json<CreateWebhookInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let (url, events) =
+        validate_webhook(&input.url, Some(&input.secret), &input.events)?;
+    let webhook = sqlx::query_as::<_, WebhookRow>(&format!(
+        "INSERT INTO app.webhook_subscriptions (
+           owner_user_id,
+           url,
+           secret,
+           events,
+           active
+         )
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING {WEBHOOK_COLUMNS}"
+    ))
+    .bind(user.id)
+    .bind(url)
+    .bind(&input.secret)
+    .bind(events)
+    .bind(input.active.unwrap_or(true))
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::CREATED, webhook))
+}
+
+pub(super) async fn get_webhook(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    let webhook = fetch_owned_webhook(&state.db, id, user.id).await?;
+
+    Ok(response::ok(StatusCode::OK, webhook))
+}
+
+pub(super) async fn update_webhook(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateWebhookInput>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_webhook(&state.db, id, user.id).await?;
+    let url = input.url.unwrap_or(existing.url);
+    let events = input.events.unwrap_or(existing.events);
+    let (url, events) =
+        validate_webhook(&url, input.secret.as_deref(), &events)?;
+
+    let webhook = sqlx::query_as::<_, WebhookRow>(&format!(
+        "UPDATE app.webhook_subscriptions
+         SET url = $2,
+             secret = COALESCE($3, secret),
+             events = $4,
+             active = $5
+         WHERE id = $1
+         RETURNING {WEBHOOK_COLUMNS}"
+    ))
+    .bind(id)
+    .bind(url)
+    .bind(input.secret.as_deref())
+    .bind(events)
+    .bind(input.active.unwrap_or(existing.active))
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(response::ok(StatusCode::OK, webhook))
+}
+
+pub(super) async fn delete_webhook(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    let existing = fetch_owned_webhook(&state.db, id, user.id).await?;
+    sqlx::query("DELETE FROM app.webhook_subscriptions WHERE id = $1")
+        .bind(existing.id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub(super) async fn list_deliveries(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListDeliveriesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    fetch_owned_webhook(&state.db, id, user.id).await?;
+
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let status = query.status.as_deref().map(str::trim);
+    if let Some(status) = status
+        && !["pending", "succeeded", "failed"].contains(&status)
+    {
+        return Err(AppError::Validation(vec![ErrorDetail {
+            field: "status".to_string(),
+            message: "status must be pending, succeeded or failed".to_string(),
+        }]));
+    }
+    if let Some(cursor) = query.cursor {
+        fetch_delivery(&state.db, id, cursor).await.map_err(|_| {
+            AppError::Validation(vec![ErrorDetail {
+                field: "cursor".to_string(),
+                message: "cursor does not match a delivery".to_string(),
+            }])
+        })?;
+    }
+
+    let sql = format!(
+        "{}
+         WHERE delivery.subscription_id = $1
+           AND ($2::text IS NULL OR delivery.status = $2)
+           AND (
+             $3::uuid IS NULL
+             OR (delivery.created_at, delivery.id) < (
+               SELECT created_at, id FROM app.webhook_deliveries WHERE id = $3
+             )
+           )
+         ORDER BY delivery.created_at DESC, delivery.id DESC
+         LIMIT $4",
+        delivery_select_sql()
+    );
+    let items = sqlx::query_as::<_, WebhookDeliveryRow>(&sql)
+        .bind(id)
+        .bind(status)
+        .bind(query.cursor)
+        .bind(pagination::page_fetch_limit(limit))
+        .fetch_all(&state.db)
+        .await?;
+    let (items, next_cursor) =
+        pagination::split_page(items, limit, |item| item.id.to_string());
+
+    Ok(response::list(items, None, limit, None, next_cursor))
+}
+
+pub(super) async fn redeliver(
+    State(state): State<AppState>,
+    session: Session,
+    headers: HeaderMap,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = require_authenticated_user(&state, &session, &headers).await?;
+    middleware::require_csrf_token(&state, &session, user.id, &headers).await?;
+
+    fetch_owned_webhook(&state.db, id, user.id).await?;
+    let original = fetch_delivery(&state.db, id, delivery_id).await?;
+    let redelivery_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO app.webhook_deliveries (
+           subscription_id,
+           event_type,
+           request_id,
+           body,
+           redelivery_of
+         )
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING id",
+    )
+    .bind(id)
+    .bind(&original.event_type)
+    .bind(original.request_id)
+    .bind(&original.body)
+    .bind(original.id)
+    .fetch_one(&state.db)
+    .await?;
+    let delivery = fetch_delivery(&state.db, id, redelivery_id).await?;
+
+    Ok(response::ok(StatusCode::CREATED, delivery))
+}
+
+pub(super) async fn enqueue(
+    state: &AppState,
+    recipients: &[Uuid],
+    envelopes: &[EventEnvelope],
+) {
+    if recipients.is_empty() || envelopes.is_empty() {
+        return;
+    }
+
+    let mut event_types = Vec::with_capacity(envelopes.len());
+    let mut request_ids = Vec::with_capacity(envelopes.len());
+    let mut bodies = Vec::with_capacity(envelopes.len());
+    for envelope in envelopes {
+        match serde_json::to_value(envelope) {
+            Ok(body) => {
+                event_types.push(envelope.event_type.clone());
+                request_ids.push(envelope.request_id);
+                bodies.push(body);
+            }
+            Err(err) => {
+                warn!(error = %err, "failed to serialize webhook event");
+            }
+        }
+    }
+
+    let queued = sqlx::query(
+        "INSERT INTO app.webhook_deliveries (
+           subscription_id,
+           event_type,
+           request_id,
+           body
+         )
+         SELECT sub.id, event.event_type, event.request_id, event.body
+         FROM UNNEST($2::text[], $3::uuid[], $4::jsonb[])
+           WITH ORDINALITY AS event(event_type, request_id, body, position)
+         JOIN app.webhook_subscriptions sub
+           ON sub.active
+          AND sub.owner_user_id = ANY($1)
+          AND event.event_type = ANY(sub.events)
+         ORDER BY event.position",
+    )
+    .bind(recipients)
+    .bind(&event_types)
+    .bind(&request_ids)
+    .bind(&bodies)
+    .execute(&state.db)
+    .await;
+    if let Err(err) = queued {
+        warn!(error = %err, "failed to queue webhook deliveries");
+    }
+}
+
+// Deliveries only reach public addresses unless the host is listed in
+// `allowed_private_hosts`. The resolver drops private answers itself, so a
+// DNS change between a check and the connect cannot reach an internal
+// service, and redirects are not followed, so a receiver cannot bounce
+// deliveries to another host.
+#[derive(Clone)]
+pub struct WebhookClient {
+    http: reqwest::Client,
+    allowed_private_hosts: Arc<[String]>,
+}
+
+pub fn webhook_client(
+    allowed_private_hosts: &[String],
+) -> Result<WebhookClient, AppError> {
+    let allowed_private_hosts: Arc<[String]> = allowed_private_hosts
+        .iter()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect();
+    let http = reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicResolver {
+            allowed_private_hosts: allowed_private_hosts.clone(),
+        }))
+        .user_agent(concat!("reqstly-webhooks/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|err| {
+            AppError::Internal(format!("webhook client error: {err}"))
+        })?;
+
+    Ok(WebhookClient {
+        http,
+        allowed_private_hosts,
+    })
+}
+
+struct PublicResolver {
+    allowed_private_hosts: Arc<[String]>,
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        let allow_private = host_allowed(&self.allowed_private_hosts, &host);
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0))
+                    .await?
+                    .filter(|addr| allow_private || is_public_ip(addr.ip()))
+                    .collect();
+            if addrs.is_empty() {
+                return Err(format!(
+                    "{host} does not resolve to a public address"
+                )
+                .into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+pub fn spawn_webhook_worker(
+    state: AppState,
+    client: WebhookClient,
+) -> JoinHandle<()> {
+    workers::spawn_periodic("webhooks", WEBHOOK_WORKER_INTERVAL, move || {
+        let (state, client) = (state.clone(), client.clone());
+        async move { run_due_webhooks(&state, &client, Utc::now()).await }
+    })
+}
+
+pub async fn run_due_webhooks(
+    state: &AppState,
+    client: &WebhookClient,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError> {
+    // Claiming pushes `next_attempt_at` past the lease so other replicas
+    // skip these rows; a worker that dies mid-attempt is retried after it.
+    let claimed = sqlx::query_as::<_, ClaimedDelivery>(
+        "UPDATE app.webhook_deliveries delivery
+         SET attempts = delivery.attempts + 1,
+             last_attempt_at = $1,
+             next_attempt_at = $1 + make_interval(secs => $3)
+         FROM app.webhook_subscriptions sub
+         WHERE sub.id = delivery.subscription_id
+           AND delivery.id IN (
+             SELECT due.id
+             FROM app.webhook_deliveries due
+             JOIN app.webhook_subscriptions due_sub
+               ON due_sub.id = due.subscription_id
+             WHERE due.status = 'pending'
+               AND due.next_attempt_at <= $1
+               AND due_sub.active
+             ORDER BY due.next_attempt_at ASC, due.id ASC
+             LIMIT $2
+             FOR UPDATE OF due SKIP LOCKED
+           )
+         RETURNING
+           delivery.id,
+           delivery.event_type,
+           delivery.body,
+           delivery.attempts,
+           sub.url,
+           sub.secret",
+    )
+    .bind(now)
+    .bind(WEBHOOK_BATCH)
+    .bind(WEBHOOK_LEASE_SECONDS as f64)
+    .fetch_all(&state.db)
+    .await?;
+
+    let attempted = claimed.len();
+    let results = join_all(
+        claimed
+            .iter()
+            .map(|delivery| attempt_delivery(client, delivery)),
+    )
+    .await;
+
+    for (delivery, result) in claimed.iter().zip(results) {
+        let update = match result {
+            Ok(status) => {
+                sqlx::query(
+                    "UPDATE app.webhook_deliveries
+                     SET status = 'succeeded',
+                         response_status = $2,
+                         last_error = NULL
+                     WHERE id = $1",
+                )
+                .bind(delivery.id)
+                .bind(status)
+                .execute(&state.db)
+                .await
+            }
+            Err(failure) => {
+                let exhausted = delivery.attempts >= WEBHOOK_MAX_ATTEMPTS;
+                sqlx::query(
+                    "UPDATE app.webhook_deliveries
+                     SET status = $2,
+                         response_status = $3,
+                         last_error = $4,
+                         next_attempt_at = $5
+                     WHERE id = $1",
+                )
+                .bind(delivery.id)
+                .bind(if exhausted { "failed" } else { "pending" })
+                .bind(failure.response_status)
+                .bind(&failure.message)
+                .bind(now + backoff(delivery.attempts))
+                .execute(&state.db)
+                .await
+            }
+        };
+        if let Err(err) = update {
+            warn!(
+                delivery_id = %delivery.id,
+                error = %err,
+                "failed to record webhook attempt"
+            );
+        }
+    }
+
+    Ok(attempted)
+}
+
+async fn attempt_delivery(
+    client: &WebhookClient,
+    delivery: &ClaimedDelivery,
+) -> Result<i32, AttemptFailure> {
+    // IP literals never reach the resolver, so they are checked here.
+    if let Ok(url) = url::Url::parse(&delivery.url)
+        && let Some(ip) = literal_ip(&url)
+        && !is_public_ip(ip)
+        && !host_allowed(&client.allowed_private_hosts, &ip.to_string())
+    {
+        return Err(AttemptFailure {
+            response_status: None,
+            message: format!("{ip} is not a public address"),
+        });
+    }
+    let body =
+        serde_json::to_vec(&delivery.body).map_err(|err| AttemptFailure {
+            response_status: None,
+            message: format!("failed to encode body: {err}"),
+        })?;
+    let response = client
+        .http
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event_type)
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .header(SIGNATURE_HEADER, sign(&delivery.secret, &body))
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| AttemptFailure {
+            response_status: None,
+            message: truncate_error(&error_chain(&err)),
+        })?;
+
+    let status = i32::from(response.status().as_u16());
+    if response.status().is_success() {
+        Ok(status)
+    } else {
+        Err(AttemptFailure {
+            response_status: Some(status),
+            message: format!("receiver responded with {status}"),
+        })
+    }
+}
+
+fn literal_ip(url: &url::Url) -> Option<IpAddr> {
+    match url.host()? {
+        url::Host::Ipv4(ip) => Some(IpAddr::V4(ip)),
+        url::Host::Ipv6(ip) => Some(IpAddr::V6(ip)),
+        url::Host::Domain(_) => None,
+    }
+}
+
+fn host_allowed(allowed_private_hosts: &[String], host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    allowed_private_hosts
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(host))
+}
+
+// Loopback, private, link-local (including the 169.254.169.254 metadata
+// endpoint), shared, reserved and multicast ranges are all refused.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && c == 0)
+                || (a == 198 && (b == 18 || b == 19))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(mapped));
+            }
+            let segments = ip.segments();
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., high, low] = segments;
+                let embedded = (u32::from(high) << 16) | u32::from(low);
+                return is_public_ip(IpAddr::V4(Ipv4Addr::from(embedded)));
+            }
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                || segments[..2] == [0x2001, 0xdb8])
+        }
+    }
+}
+
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn backoff(attempts: i32) -> TimeDelta {
+    let exponent = u32::try_from(attempts.saturating_sub(1)).unwrap_or(0);
+    let seconds = 2_i64
+        .checked_pow(exponent)
+        .and_then(|factor| factor.checked_mul(WEBHOOK_BACKOFF_BASE_SECONDS))
+        .map_or(WEBHOOK_BACKOFF_MAX_SECONDS, |seconds| {
+            seconds.min(WEBHOOK_BACKOFF_MAX_SECONDS)
+        });
+    TimeDelta::seconds(seconds)
+}
+
+// reqwest keeps the cause (refused address, TLS, timeout) in the source
+// chain rather than in its own message.
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn truncate_error(message: &str) -> String {
+    message.chars().take(500).collect()
+}
+
+fn validate_webhook(
+    url: &str,
+    secret: Option<&str>,
+    events: &[String],
+) -> Result<(String, Vec<String>), AppError> {
+    let mut details = Vec::new();
+
+    let url = url.trim();
+    let url_valid = url.len() <= 2000
+        && url::Url::parse(url).is_ok_and(|parsed| {
+            matches!(parsed.scheme(), "http" | "https")
+                && parsed.host().is_some()
+        });
+    if !url_valid {
+        details.push(ErrorDetail {
+            field: "url".to_string(),
+            message: "url must be an absolute http or https URL".to_string(),
+        });
+    }
+
+    if let Some(secret) = secret
+        && !(16..=200).contains(&secret.chars().count())
+    {
+        details.push(ErrorDetail {
+            field: "secret".to_string(),
+            message: "secret must be between 16 and 200 characters".to_string(),
+        });
+    }
+
+    let mut selected: Vec<String> = Vec::new();
+    for event in events {
+        let event = event.trim();
+        if !WEBHOOK_EVENT_TYPES.contains(&event) {
+            details.push(ErrorDetail {
+                field: "events".to_string(),
+                message: format!(
+                    "unknown event {event:?}; expected one of {}",
+                    WEBHOOK_EVENT_TYPES.join(", ")
+                ),
+            });
+        } else if !selected.iter().any(|existing| existing == event) {
+            selected.push(event.to_string());
+        }
+    }
+    if events.is_empty() {
+        details.push(ErrorDetail {
+            field: "events".to_string(),
+            message: "select at least one event".to_string(),
+        });
+    }
+
+    if details.is_empty() {
+        Ok((url.to_string(), selected))
+    } else {
+        Err(AppError::Validation(details))
+    }
+}
+
+async fn fetch_owned_webhook<'e, E>(
+    executor: E,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<WebhookRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    sqlx::query_as::<_, WebhookRow>(&format!(
+        "SELECT {WEBHOOK_COLUMNS}
+         FROM app.webhook_subscriptions
+         WHERE id = $1 AND owner_user_id = $2"
+    ))
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::NotFound("webhook not found".to_string()))
+}
+
+async fn fetch_delivery<'e, E>(
+    executor: E,
+    subscription_id: Uuid,
+    delivery_id: Uuid,
+) -> Result<WebhookDeliveryRow, AppError>
+where
+    E: PgExecutor<'e>,
+{
+    let sql = format!(
+        "{} WHERE delivery.id = $1 AND delivery.subscription_id = $2",
+        delivery_select_sql()
+    );
+    sqlx::query_as::<_, WebhookDeliveryRow>(&sql)
+        .bind(delivery_id)
+        .bind(subscription_id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| AppError::NotFound("delivery not found".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(1), TimeDelta::seconds(30));
+        assert_eq!(backoff(2), TimeDelta::seconds(60));
+        assert_eq!(backoff(4), TimeDelta::seconds(240));
+        assert_eq!(backoff(12), TimeDelta::hours(6));
+        assert_eq!(backoff(80), TimeDelta::hours(6));
+    }
+
+    #[test]
+    fn sign_matches_known_hmac_sha256() {
+        // RFC 4231 test case 2.
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c7\
+             5a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn validate_webhook_reports_each_invalid_field() {
+        let Err(AppError::Validation(details)) = validate_webhook(
+            "ftp://example.com/hook",
+            Some("short"),
+            &["request.created".to_string(), "audit.append".to_string()],
+        ) else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<&str> =
+            details.iter().map(|detail| detail.field.as_str()).collect();
+        assert_eq!(fields, ["url", "secret", "events"]);
+
+        let (url, events) = validate_webhook(
+            " https://hooks.example.com/reqstly ",
+            Some("0123456789abcdef"),
+            &["request.patch".to_string(), "request.patch".to_string()],
+        )
+        .expect("valid webhook");
+        assert_eq!(url, "https://hooks.example.com/reqstly");
+        assert_eq!(events, ["request.patch"]);
+    }
+
+    #[test]
+    fn is_public_ip_refuses_internal_ranges() {
+        for internal in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fe80::1",
+            "fd00:ec2::254",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(!is_public_ip(internal.parse().unwrap()), "{internal}");
+        }
+        for public in ["93.184.216.34", "2606:2800:220:1::1"] {
+            assert!(is_public_ip(public.parse().unwrap()), "{public}");
+        }
+    }
+
+    #[test]
+    fn host_allowed_matches_bracketed_ipv6_and_case() {
+        let allowed = [
+            "127.0.0.1".to_string(),
+            "::1".to_string(),
+            "hooks.internal".to_string(),
+        ];
+        assert!(host_allowed(&allowed, "127.0.0.1"));
+        assert!(host_allowed(&allowed, "[::1]"));
+        assert!(host_allowed(&allowed, "Hooks.Internal"));
+        assert!(!host_allowed(&allowed, "10.0.0.1"));
+    }
+}
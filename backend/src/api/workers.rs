@@ -0,0 +1,29 @@
+use std::future::Future;
+
+use tokio::task::JoinHandle;
+use tokio::time::{self, Duration};
+use tracing::{info, warn};
+
+use crate::error::AppError;
+
+pub(super) fn spawn_periodic<F, Fut>(
+    name: &'static str,
+    period: Duration,
+    mut pass: F,
+) -> JoinHandle<()>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<usize, AppError>> + Send,
+{
+    tokio::spawn(async move {
+        let mut interval = time::interval(period);
+        loop {
+            interval.tick().await;
+            match pass().await {
+                Ok(0) => {}
+                Ok(processed) => info!(worker = name, processed, "pass done"),
+                Err(err) => warn!(worker = name, error = %err, "pass failed"),
+            }
+        }
+    })
+}
@@ -2,6 +2,7 @@ use axum::http::{HeaderMap, header};
 use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
+use sqlx::PgPool;
 use tower_sessions::Session;
 use uuid::Uuid;
 
@@ -9,6 +10,7 @@ use crate::{
     AppState,
     auth::{repo, session},
     error::AppError,
+    realtime::{ConnectionAuth, RevocationReason},
 };
 
 #[derive(Debug, Clone)]
@@ -121,6 +123,46 @@ pub async fn verify_ws_token_with_state(
     Ok(user_id)
 }
 
+// Token expiry is not re-checked: it only bounds the upgrade handshake.
+pub async fn revalidate_ws_connection(
+    pool: &PgPool,
+    user_id: Uuid,
+    auth: &ConnectionAuth,
+) -> Result<Option<RevocationReason>, AppError> {
+    let user = repo::get_user_by_id(pool, user_id).await?;
+    if !user.is_some_and(|user| user.is_active) {
+        return Ok(Some(RevocationReason::AccountDisabled));
+    }
+
+    let security = repo::get_user_auth_security(pool, user_id).await?;
+    if security.locked_until.is_some_and(|locked_until| {
+        locked_until > time::OffsetDateTime::now_utc()
+    }) {
+        return Ok(Some(RevocationReason::AccountLocked));
+    }
+    if security.compromised_at.is_some() || security.require_reauth {
+        return Ok(Some(RevocationReason::ReauthRequired));
+    }
+
+    match auth {
+        ConnectionAuth::Session {
+            session_version, ..
+        } if *session_version != security.session_version => {
+            Ok(Some(RevocationReason::SessionsRevoked))
+        }
+        ConnectionAuth::Session { .. } => Ok(None),
+        ConnectionAuth::WsToken { token_fingerprint } => {
+            let revoked = repo::is_ws_token_issuance_revoked(
+                pool,
+                user_id,
+                token_fingerprint.as_slice(),
+            )
+            .await?;
+            Ok(revoked.then_some(RevocationReason::TokenRevoked))
+        }
+    }
+}
+
 pub async fn require_csrf_token(
     state: &AppState,
     session_handle: &Session,
@@ -298,12 +298,27 @@ pub async fn ensure_user_auth_security(
     .execute(pool)
     .await?;
 
+    get_user_auth_security(pool, user_id).await
+}
+
+// Read-only: applies the same risk promotions as
+// `ensure_user_auth_security` without writing them back.
+pub async fn get_user_auth_security(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<AuthSecurityState, AppError> {
     let row = sqlx::query_as::<_, AuthSecurityRow>(
         "SELECT
             session_version,
-            require_reauth,
-            password_login_disabled,
-            passkey_login_disabled,
+            require_reauth
+              OR compromised_at IS NOT NULL
+              OR risk_score >= 80 AS require_reauth,
+            password_login_disabled
+              OR compromised_at IS NOT NULL
+              OR risk_score >= 90 AS password_login_disabled,
+            passkey_login_disabled
+              OR compromised_at IS NOT NULL
+              OR risk_score >= 95 AS passkey_login_disabled,
             risk_score,
             compromised_at,
             locked_until
@@ -401,8 +416,8 @@ pub async fn bump_user_session_version(
 pub async fn record_password_login_failure(
     pool: &PgPool,
     user_id: Uuid,
-) -> Result<(), AppError> {
-    sqlx::query(
+) -> Result<bool, AppError> {
+    let locked = sqlx::query_scalar::<_, bool>(
         "UPDATE app.user_password_identities
          SET failed_attempts = failed_attempts + 1,
              last_failed_at = NOW(),
@@ -414,13 +429,14 @@ pub async fn record_password_login_failure(
                  )
                ELSE locked_until
              END
-         WHERE user_id = $1",
+         WHERE user_id = $1
+         RETURNING COALESCE(locked_until > NOW(), FALSE)",
     )
     .bind(user_id)
-    .execute(pool)
+    .fetch_optional(pool)
     .await?;
 
-    Ok(())
+    Ok(locked.unwrap_or(false))
 }
 
 pub async fn clear_password_login_failures(
@@ -515,6 +531,27 @@ pub async fn is_ws_token_issuance_active(
     .map_err(AppError::from)
 }
 
+pub async fn is_ws_token_issuance_revoked(
+    pool: &PgPool,
+    user_id: Uuid,
+    token_fingerprint: &[u8],
+) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(
+           SELECT 1
+           FROM app.ws_token_issuances
+           WHERE user_id = $1
+             AND token_fingerprint = $2
+             AND revoked_at IS NOT NULL
+         )",
+    )
+    .bind(user_id)
+    .bind(token_fingerprint)
+    .fetch_one(pool)
+    .await
+    .map_err(AppError::from)
+}
+
 pub async fn mark_ws_token_used(
     pool: &PgPool,
     user_id: Uuid,
@@ -28,6 +28,7 @@ use crate::{
         },
     },
     error::{AppError, ErrorDetail},
+    realtime::RevocationReason,
 };
 
 #[derive(Debug, Serialize)]
@@ -174,9 +175,18 @@ pub async fn login_password(
     )?;
 
     if !password_valid {
-        let _ =
+        let locked =
             repo::record_password_login_failure(&state.db, identity.user_id)
                 .await;
+        if matches!(locked, Ok(true)) {
+            state
+                .realtime_hub
+                .disconnect_user(
+                    identity.user_id,
+                    RevocationReason::AccountLocked,
+                )
+                .await;
+        }
         record_login_failure(state, headers, &email, "password-mismatch").await;
         return Err(AppError::Unauthorized(
             "invalid email or password".to_string(),
@@ -238,6 +248,17 @@ pub async fn logout(
             json!({}),
         )
         .await?;
+
+        if let Some(session_id) = session::session_id(session_handle) {
+            state
+                .realtime_hub
+                .disconnect_session(
+                    context.user.id,
+                    &session_id,
+                    RevocationReason::LoggedOut,
+                )
+                .await;
+        }
     }
 
     session::clear_session(session_handle).await
@@ -272,6 +293,11 @@ pub async fn revoke_all_sessions(
     )
     .await?;
 
+    state
+        .realtime_hub
+        .disconnect_user(auth.user.id, RevocationReason::SessionsRevoked)
+        .await;
+
     session::clear_session(session_handle).await
 }
 
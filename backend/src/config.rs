@@ -1,13 +1,18 @@
 use config::{Config, ConfigError, Environment};
 use serde::Deserialize;
 
+use crate::realtime::{RealtimeLimits, SlowConsumerPolicy};
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Settings {
     pub server: ServerSettings,
     pub database: DatabaseSettings,
     pub auth: AuthSettings,
     pub cors: CorsSettings,
+    pub realtime: RealtimeSettings,
     pub logging: LoggingSettings,
+    pub mail: MailSettings,
+    pub webhooks: WebhookSettings,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -38,6 +43,70 @@ pub struct CorsSettings {
     pub allowed_origin: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct RealtimeSettings {
+    pub max_connections_per_user: usize,
+    pub max_connections_total: usize,
+    pub slow_consumer_policy: SlowConsumerPolicy,
+}
+
+impl RealtimeSettings {
+    #[must_use]
+    pub fn limits(&self) -> RealtimeLimits {
+        RealtimeLimits {
+            max_connections_per_user: self.max_connections_per_user,
+            max_connections_total: self.max_connections_total,
+            slow_consumer_policy: self.slow_consumer_policy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MailSettings {
+    pub transport: MailTransportKind,
+    pub from: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_tls: SmtpTls,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub digest_interval_hours: u32,
+    pub app_url: String,
+    pub inbound_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MailTransportKind {
+    Smtp,
+    Stub,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    None,
+    Starttls,
+    Tls,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookSettings {
+    pub allowed_private_hosts: String,
+}
+
+impl WebhookSettings {
+    #[must_use]
+    pub fn allowed_private_hosts(&self) -> Vec<String> {
+        self.allowed_private_hosts
+            .split(',')
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct LoggingSettings {
     pub level: String,
@@ -72,6 +141,9 @@ impl Settings {
             .set_default("auth.webauthn_rp_origin", "https://localhost")?
             .set_default("auth.webauthn_rp_name", "Reqstly")?
             .set_default("cors.allowed_origin", "https://localhost")?
+            .set_default("realtime.max_connections_per_user", 8)?
+            .set_default("realtime.max_connections_total", 10_000)?
+            .set_default("realtime.slow_consumer_policy", "disconnect")?
             .set_default(
                 "logging.level",
                 "reqstly_backend=info,tower_http=info,axum=info",
@@ -79,6 +151,14 @@ impl Settings {
             .set_default("logging.format", "json")?
             .set_default("logging.service_name", "reqstly-backend")?
             .set_default("logging.environment", "dev")?
+            .set_default("mail.transport", "stub")?
+            .set_default("mail.from", "Reqstly <no-reply@localhost>")?
+            .set_default("mail.smtp_host", "localhost")?
+            .set_default("mail.smtp_port", 25)?
+            .set_default("mail.smtp_tls", "none")?
+            .set_default("mail.digest_interval_hours", 24)?
+            .set_default("mail.app_url", "https://localhost")?
+            .set_default("webhooks.allowed_private_hosts", "")?
             .add_source(Environment::default().separator("__"))
             .build()?
             .try_deserialize()
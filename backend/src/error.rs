@@ -3,11 +3,12 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use schemars::JsonSchema;
 use serde::Serialize;
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ErrorDetail {
     pub field: String,
     pub message: String,
@@ -19,6 +20,8 @@ pub enum AppError {
     Unauthorized(String),
     #[error("rate limited: {0}")]
     RateLimited(String),
+    #[error("forbidden: {0}")]
+    Forbidden(String),
     #[error("not found: {0}")]
     NotFound(String),
     #[error("validation failed")]
@@ -36,6 +39,7 @@ impl AppError {
         match self {
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             Self::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
             Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::Database(_) | Self::Migration(_) | Self::Internal(_) => {
@@ -44,10 +48,11 @@ impl AppError {
         }
     }
 
-    fn code(&self) -> &'static str {
+    pub fn code(&self) -> &'static str {
         match self {
             Self::Unauthorized(_) => "UNAUTHORIZED",
             Self::RateLimited(_) => "RATE_LIMITED",
+            Self::Forbidden(_) => "FORBIDDEN",
             Self::NotFound(_) => "NOT_FOUND",
             Self::Validation(_) => "VALIDATION_ERROR",
             Self::Database(_) => "DATABASE_ERROR",
@@ -56,7 +61,7 @@ impl AppError {
         }
     }
 
-    fn message(&self) -> String {
+    pub fn message(&self) -> String {
         match self {
             Self::Validation(_) => "Validation failed".to_string(),
             Self::Database(_) => "A database error occurred".to_string(),
@@ -64,6 +69,28 @@ impl AppError {
             _ => self.to_string(),
         }
     }
+
+    pub fn details(&self) -> Option<Vec<ErrorDetail>> {
+        match self {
+            Self::Validation(items) => Some(items.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn log(&self) {
+        match self {
+            Self::Unauthorized(_)
+            | Self::RateLimited(_)
+            | Self::Forbidden(_)
+            | Self::NotFound(_)
+            | Self::Validation(_) => {
+                tracing::warn!("request failed: {}", self);
+            }
+            Self::Database(_) | Self::Migration(_) | Self::Internal(_) => {
+                tracing::error!("request failed: {}", self);
+            }
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -88,24 +115,8 @@ struct ErrorMeta {
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let status = self.status_code();
-        let details = match &self {
-            AppError::Validation(items) => Some(items.clone()),
-            _ => None,
-        };
-
-        match &self {
-            AppError::Unauthorized(_)
-            | AppError::RateLimited(_)
-            | AppError::NotFound(_)
-            | AppError::Validation(_) => {
-                tracing::warn!("request failed: {}", self);
-            }
-            AppError::Database(_)
-            | AppError::Migration(_)
-            | AppError::Internal(_) => {
-                tracing::error!("request failed: {}", self);
-            }
-        }
+        let details = self.details();
+        self.log();
 
         (
             status,
@@ -3,6 +3,7 @@ pub mod auth;
 pub mod config;
 pub mod db;
 pub mod error;
+pub mod mail;
 pub mod realtime;
 pub mod response;
 pub mod telemetry;
@@ -41,6 +42,7 @@ pub struct AppState {
     pub passkey: auth::PasskeyService,
     pub realtime_hub: realtime::RealtimeHub,
     pub ws_allowed_origins: Vec<String>,
+    pub inbound_email_token: Option<String>,
 }
 
 pub fn build_app(
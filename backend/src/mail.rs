@@ -0,0 +1,153 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+};
+
+use crate::{
+    config::{MailSettings, MailTransportKind, SmtpTls},
+    error::AppError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+pub trait MailTransport: Send + Sync {
+    fn send(
+        &self,
+        message: MailMessage,
+    ) -> impl Future<Output = Result<(), AppError>> + Send;
+}
+
+#[derive(Clone)]
+pub enum Mailer {
+    Smtp(Box<SmtpMailer>),
+    Stub(StubMailer),
+}
+
+impl Mailer {
+    pub fn from_settings(settings: &MailSettings) -> Result<Self, AppError> {
+        match settings.transport {
+            MailTransportKind::Smtp => SmtpMailer::new(settings)
+                .map(|mailer| Self::Smtp(Box::new(mailer))),
+            MailTransportKind::Stub => Ok(Self::Stub(StubMailer::default())),
+        }
+    }
+}
+
+impl MailTransport for Mailer {
+    async fn send(&self, message: MailMessage) -> Result<(), AppError> {
+        match self {
+            Self::Smtp(mailer) => mailer.send(message).await,
+            Self::Stub(mailer) => mailer.send(message).await,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(settings: &MailSettings) -> Result<Self, AppError> {
+        let from = parse_mailbox(&settings.from)?;
+        let host = settings.smtp_host.as_str();
+        let builder = match settings.smtp_tls {
+            SmtpTls::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+            }
+            SmtpTls::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                    .map_err(smtp_error)?
+            }
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+                .map_err(smtp_error)?,
+        };
+        let builder = builder.port(settings.smtp_port);
+        let username = settings.smtp_username.as_deref().unwrap_or_default();
+        let builder = if username.is_empty() {
+            builder
+        } else {
+            builder.credentials(Credentials::new(
+                username.to_string(),
+                settings.smtp_password.clone().unwrap_or_default(),
+            ))
+        };
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+impl MailTransport for SmtpMailer {
+    async fn send(&self, message: MailMessage) -> Result<(), AppError> {
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(parse_mailbox(&message.to)?)
+            .subject(message.subject)
+            .multipart(MultiPart::alternative_plain_html(
+                message.text,
+                message.html,
+            ))
+            .map_err(|err| {
+                AppError::Internal(format!("invalid mail message: {err}"))
+            })?;
+
+        self.transport.send(email).await.map_err(smtp_error)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct StubMailer {
+    sent: Arc<Mutex<Vec<MailMessage>>>,
+}
+
+impl StubMailer {
+    #[must_use]
+    pub fn sent(&self) -> Vec<MailMessage> {
+        self.sent
+            .lock()
+            .map(|sent| sent.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl MailTransport for StubMailer {
+    async fn send(&self, message: MailMessage) -> Result<(), AppError> {
+        tracing::info!(
+            to = %message.to,
+            subject = %message.subject,
+            "stub mailer accepted message"
+        );
+        self.sent
+            .lock()
+            .map_err(|_| {
+                AppError::Internal("stub mailer poisoned".to_string())
+            })?
+            .push(message);
+        Ok(())
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, AppError> {
+    address.parse().map_err(|err| {
+        AppError::Internal(format!("invalid mail address {address:?}: {err}"))
+    })
+}
+
+fn smtp_error(err: lettre::transport::smtp::Error) -> AppError {
+    AppError::Internal(format!("smtp error: {err}"))
+}
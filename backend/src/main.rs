@@ -1,18 +1,126 @@
 use dotenvy::dotenv;
 use reqstly_backend::{
-    AppState, auth, build_app, config::Settings, db, error, realtime, telemetry,
+    AppState, api, auth, build_app, config::Settings, db, error, mail,
+    realtime, telemetry,
 };
 use std::net::SocketAddr;
 use tracing::Instrument;
 
+const IMPORT_USAGE: &str = "usage: reqstly_backend import --owner <email> \
+                            [--format csv|jsonl] [--dry-run] <file>";
+const WORKSPACE_ADMIN_USAGE: &str =
+    "usage: reqstly_backend workspace-admin --grant|--revoke <email>";
+
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("import") {
+        if let Err(err) = run_import(&args[1..]).await {
+            eprintln!("import failed: {err}");
+            if let error::AppError::Validation(details) = &err {
+                for detail in details {
+                    eprintln!("  {}: {}", detail.field, detail.message);
+                }
+            }
+            std::process::exit(1);
+        }
+        return;
+    }
+    if args.first().map(String::as_str) == Some("workspace-admin") {
+        if let Err(err) = run_workspace_admin(&args[1..]).await {
+            eprintln!("workspace-admin failed: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if let Err(err) = run().await {
         eprintln!("server failed: {err}");
         std::process::exit(1);
     }
 }
 
+fn import_usage() -> ! {
+    eprintln!("{IMPORT_USAGE}");
+    std::process::exit(2);
+}
+
+async fn run_import(args: &[String]) -> Result<(), error::AppError> {
+    dotenv().ok();
+
+    let mut owner = None;
+    let mut format = None;
+    let mut dry_run = false;
+    let mut path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--owner" => {
+                owner = Some(args.next().unwrap_or_else(|| import_usage()))
+            }
+            "--format" => {
+                format = Some(args.next().unwrap_or_else(|| import_usage()))
+            }
+            "--dry-run" => dry_run = true,
+            value if !value.starts_with("--") && path.is_none() => {
+                path = Some(value);
+            }
+            _ => import_usage(),
+        }
+    }
+    let (Some(owner), Some(path)) = (owner, path) else {
+        import_usage();
+    };
+    let format = match format {
+        Some(format) => api::ImportFormat::parse(format)?,
+        None if path.ends_with(".jsonl") || path.ends_with(".ndjson") => {
+            api::ImportFormat::JsonLines
+        }
+        None => api::ImportFormat::Csv,
+    };
+
+    let data = std::fs::read_to_string(path).map_err(|err| {
+        error::AppError::Internal(format!("cannot read {path}: {err}"))
+    })?;
+    let settings = Settings::from_env().map_err(|err| {
+        error::AppError::Internal(format!("config error: {err}"))
+    })?;
+    let db = db::create_pool(&settings.database.url).await?;
+
+    let owner_id = api::resolve_import_owner(&db, owner).await?;
+    let report =
+        api::import_requests(&db, owner_id, format, &data, dry_run).await?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report)
+            .expect("import report always serializes")
+    );
+
+    if dry_run && !report.errors.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_workspace_admin(args: &[String]) -> Result<(), error::AppError> {
+    dotenv().ok();
+
+    let (admin, email) = match args {
+        [flag, email] if flag == "--grant" => (true, email),
+        [flag, email] if flag == "--revoke" => (false, email),
+        _ => {
+            eprintln!("{WORKSPACE_ADMIN_USAGE}");
+            std::process::exit(2);
+        }
+    };
+    let settings = Settings::from_env().map_err(|err| {
+        error::AppError::Internal(format!("config error: {err}"))
+    })?;
+    let db = db::create_pool(&settings.database.url).await?;
+
+    api::set_workspace_admin(&db, email, admin).await
+}
+
 async fn run() -> Result<(), error::AppError> {
     dotenv().ok();
 
@@ -51,12 +159,37 @@ async fn run() -> Result<(), error::AppError> {
             ws_token_secret: settings.auth.ws_token_secret,
             ws_token_issuer: settings.auth.ws_token_issuer,
             passkey,
-            realtime_hub: realtime::RealtimeHub::new(),
+            realtime_hub: realtime::RealtimeHub::with_limits(
+                settings.realtime.limits(),
+            ),
             ws_allowed_origins: realtime::parse_allowed_origins(
                 &settings.cors.allowed_origin,
             ),
+            inbound_email_token: settings
+                .mail
+                .inbound_token
+                .clone()
+                .filter(|token| !token.is_empty()),
         };
 
+        let _scheduler = api::spawn_scheduler(state.clone());
+        let _sla = api::spawn_sla_worker(state.clone());
+        let _escalations = api::spawn_escalation_worker(state.clone());
+        let _webhooks = api::spawn_webhook_worker(
+            state.clone(),
+            api::webhook_client(&settings.webhooks.allowed_private_hosts())?,
+        );
+        let mailer = mail::Mailer::from_settings(&settings.mail)?;
+        let _digests = api::spawn_digest_worker(
+            state.clone(),
+            mailer,
+            api::DigestConfig {
+                period: chrono::TimeDelta::hours(i64::from(
+                    settings.mail.digest_interval_hours,
+                )),
+                app_url: settings.mail.app_url.clone(),
+            },
+        );
         let app = build_app(state, &settings.cors.allowed_origin)?
             .layer(session_runtime.layer);
 
@@ -0,0 +1,1049 @@
+pub mod wire;
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::{Notify, RwLock, oneshot};
+use tracing::warn;
+use uuid::Uuid;
+
+pub use wire::{
+    WireCompression, WireEncoding, WireError, WireFormat, WireFrame,
+};
+
+pub const WS_QUEUE_DEPTH: usize = 256;
+pub const WS_MAX_PENDING_MUTATIONS: usize = 16;
+pub const WS_MAX_MESSAGE_BYTES: usize = 16 * 1024;
+pub const WS_HEARTBEAT_INTERVAL_SECS: u64 = 25;
+pub const WS_IDLE_TIMEOUT_SECS: u64 = 75;
+pub const WS_AUTH_RECHECK_INTERVAL_SECS: u64 = 30;
+pub const WS_CLOSE_TIMEOUT_SECS: u64 = 5;
+pub const WS_CLOSE_SESSION_REVOKED: u16 = 4401;
+pub const WS_CLOSE_SLOW_CONSUMER: u16 = 4408;
+pub const WS_CLOSE_TRY_AGAIN_LATER: u16 = 1013;
+
+pub const PROTOCOL_VERSION_V1: u8 = 1;
+pub const LATEST_PROTOCOL_VERSION: u8 = 2;
+pub const SUPPORTED_PROTOCOL_VERSIONS: [u8; 2] = [1, 2];
+
+#[must_use]
+pub fn negotiate_protocol_version(requested: u8) -> u8 {
+    requested.clamp(PROTOCOL_VERSION_V1, LATEST_PROTOCOL_VERSION)
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct EventEnvelope {
+    pub v: u8,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub ts: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    pub payload: Value,
+}
+
+impl EventEnvelope {
+    pub fn new(
+        event_type: impl Into<String>,
+        request_id: Option<Uuid>,
+        trace_id: Option<String>,
+        payload: Value,
+    ) -> Self {
+        Self {
+            v: PROTOCOL_VERSION_V1,
+            event_type: event_type.into(),
+            ts: Utc::now(),
+            request_id,
+            trace_id,
+            payload,
+        }
+    }
+
+    pub fn encode(self) -> Result<Arc<str>, serde_json::Error> {
+        serde_json::to_string(&self).map(Into::<Arc<str>>::into)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionFormat {
+    pub wire: WireFormat,
+    pub protocol_version: u8,
+}
+
+#[derive(Debug)]
+struct EventVersion {
+    envelope: EventEnvelope,
+    json: Arc<str>,
+}
+
+#[derive(Debug)]
+pub struct OutboundEvent {
+    versions: Vec<EventVersion>,
+    frames: Mutex<Vec<(ConnectionFormat, WireFrame)>>,
+}
+
+impl OutboundEvent {
+    pub fn new(envelope: EventEnvelope) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            versions: vec![EventVersion::new(envelope)?],
+            frames: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn with_version(
+        mut self,
+        envelope: EventEnvelope,
+    ) -> Result<Self, serde_json::Error> {
+        let version = envelope.v;
+        self.versions.retain(|event| event.envelope.v != version);
+        self.versions.push(EventVersion::new(envelope)?);
+        self.versions.sort_by_key(|event| event.envelope.v);
+        Ok(self)
+    }
+
+    #[must_use]
+    pub fn json(&self, protocol_version: u8) -> &Arc<str> {
+        &self.version(protocol_version).json
+    }
+
+    pub fn frame(
+        &self,
+        format: ConnectionFormat,
+    ) -> Result<WireFrame, WireError> {
+        let event = self.version(format.protocol_version);
+        if !format.wire.is_binary() {
+            return Ok(WireFrame::Text(Arc::clone(&event.json)));
+        }
+
+        let mut frames = self
+            .frames
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some((_, frame)) = frames.iter().find(|(cached, _)| {
+            cached.wire == format.wire
+                && cached.protocol_version == event.envelope.v
+        }) {
+            return Ok(frame.clone());
+        }
+
+        let frame = format.wire.encode_frame(&event.envelope)?;
+        frames.push((
+            ConnectionFormat {
+                wire: format.wire,
+                protocol_version: event.envelope.v,
+            },
+            frame.clone(),
+        ));
+        Ok(frame)
+    }
+
+    fn version(&self, protocol_version: u8) -> &EventVersion {
+        self.versions
+            .iter()
+            .rev()
+            .find(|event| event.envelope.v <= protocol_version)
+            .unwrap_or(&self.versions[0])
+    }
+}
+
+impl EventVersion {
+    fn new(envelope: EventEnvelope) -> Result<Self, serde_json::Error> {
+        let json = serde_json::to_string(&envelope)?.into();
+        Ok(Self { envelope, json })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Hello {
+        #[serde(default)]
+        last_seen_ts: Option<DateTime<Utc>>,
+        #[serde(default)]
+        encoding: Option<WireEncoding>,
+        #[serde(default)]
+        compression: Option<WireCompression>,
+        #[serde(default)]
+        protocol_version: Option<u8>,
+    },
+    #[serde(rename = "request.create")]
+    RequestCreate { op_id: String, data: Value },
+    #[serde(rename = "request.update")]
+    RequestUpdate {
+        op_id: String,
+        id: Uuid,
+        data: Value,
+    },
+}
+
+impl ClientMessage {
+    pub fn last_seen_ts(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Hello { last_seen_ts, .. } => *last_seen_ts,
+            Self::RequestCreate { .. } | Self::RequestUpdate { .. } => None,
+        }
+    }
+
+    pub fn op_id(&self) -> Option<&str> {
+        match self {
+            Self::Hello { .. } => None,
+            Self::RequestCreate { op_id, .. }
+            | Self::RequestUpdate { op_id, .. } => Some(op_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionAuth {
+    Session {
+        session_id: String,
+        session_version: i32,
+    },
+    WsToken {
+        token_fingerprint: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationReason {
+    SessionsRevoked,
+    LoggedOut,
+    TokenRevoked,
+    AccountDisabled,
+    AccountLocked,
+    ReauthRequired,
+}
+
+impl RevocationReason {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SessionsRevoked => "sessions_revoked",
+            Self::LoggedOut => "logged_out",
+            Self::TokenRevoked => "token_revoked",
+            Self::AccountDisabled => "account_disabled",
+            Self::AccountLocked => "account_locked",
+            Self::ReauthRequired => "reauth_required",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowConsumerPolicy {
+    DropOldest,
+    Disconnect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RealtimeLimits {
+    pub max_connections_per_user: usize,
+    pub max_connections_total: usize,
+    pub slow_consumer_policy: SlowConsumerPolicy,
+}
+
+impl Default for RealtimeLimits {
+    fn default() -> Self {
+        Self {
+            max_connections_per_user: 8,
+            max_connections_total: 10_000,
+            slow_consumer_policy: SlowConsumerPolicy::Disconnect,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionLimitExceeded {
+    PerUser,
+    Global,
+}
+
+impl ConnectionLimitExceeded {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PerUser => "per_user",
+            Self::Global => "global",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    Revoked(RevocationReason),
+    SlowConsumer,
+}
+
+#[derive(Debug)]
+pub struct RealtimeConnection {
+    pub id: Uuid,
+    pub outbound: OutboundReceiver,
+    pub disconnect: oneshot::Receiver<DisconnectReason>,
+}
+
+#[derive(Debug)]
+struct OutboundQueue {
+    buffer: Mutex<VecDeque<Arc<OutboundEvent>>>,
+    notify: Notify,
+    closed: AtomicBool,
+}
+
+enum PushOutcome {
+    Queued,
+    DroppedOldest,
+    Full,
+    Closed,
+}
+
+impl OutboundQueue {
+    fn new() -> Self {
+        Self {
+            buffer: Mutex::new(VecDeque::with_capacity(WS_QUEUE_DEPTH)),
+            notify: Notify::new(),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn push(
+        &self,
+        message: Arc<OutboundEvent>,
+        policy: SlowConsumerPolicy,
+    ) -> PushOutcome {
+        if self.closed.load(Ordering::Acquire) {
+            return PushOutcome::Closed;
+        }
+
+        let outcome = {
+            let mut buffer = self.buffer();
+            if buffer.len() < WS_QUEUE_DEPTH {
+                buffer.push_back(message);
+                PushOutcome::Queued
+            } else if policy == SlowConsumerPolicy::DropOldest {
+                buffer.pop_front();
+                buffer.push_back(message);
+                PushOutcome::DroppedOldest
+            } else {
+                return PushOutcome::Full;
+            }
+        };
+
+        self.notify.notify_one();
+        outcome
+    }
+
+    fn len(&self) -> usize {
+        self.buffer().len()
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.notify.notify_one();
+    }
+
+    fn buffer(&self) -> MutexGuard<'_, VecDeque<Arc<OutboundEvent>>> {
+        self.buffer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[derive(Debug)]
+pub struct OutboundReceiver {
+    queue: Arc<OutboundQueue>,
+}
+
+impl OutboundReceiver {
+    pub async fn recv(&mut self) -> Option<Arc<OutboundEvent>> {
+        loop {
+            if let Some(message) = self.queue.buffer().pop_front() {
+                return Some(message);
+            }
+            if self.queue.closed.load(Ordering::Acquire) {
+                return None;
+            }
+            self.queue.notify.notified().await;
+        }
+    }
+}
+
+impl Drop for OutboundReceiver {
+    fn drop(&mut self) {
+        self.queue.closed.store(true, Ordering::Release);
+    }
+}
+
+#[derive(Debug)]
+struct ConnectionEntry {
+    queue: Arc<OutboundQueue>,
+    auth: Option<ConnectionAuth>,
+    format: ConnectionFormat,
+    disconnect: Option<oneshot::Sender<DisconnectReason>>,
+}
+
+impl ConnectionEntry {
+    fn signal(&mut self, reason: DisconnectReason) {
+        if let Some(disconnect) = self.disconnect.take() {
+            let _ = disconnect.send(reason);
+        }
+    }
+}
+
+impl Drop for ConnectionEntry {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+type ConnectionSenders = HashMap<Uuid, ConnectionEntry>;
+type ConnectionMap = HashMap<Uuid, ConnectionSenders>;
+
+#[derive(Clone, Debug)]
+pub struct RealtimeHub {
+    inner: Arc<RwLock<ConnectionMap>>,
+    limits: RealtimeLimits,
+}
+
+impl RealtimeHub {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_limits(RealtimeLimits::default())
+    }
+
+    #[must_use]
+    pub fn with_limits(limits: RealtimeLimits) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(HashMap::new())),
+            limits,
+        }
+    }
+
+    pub async fn register(&self, user_id: Uuid) -> (Uuid, OutboundReceiver) {
+        let mut guard = self.inner.write().await;
+        let connection = insert_connection(&mut guard, user_id, None);
+        (connection.id, connection.outbound)
+    }
+
+    pub async fn register_authenticated(
+        &self,
+        user_id: Uuid,
+        auth: ConnectionAuth,
+    ) -> Result<RealtimeConnection, ConnectionLimitExceeded> {
+        let mut guard = self.inner.write().await;
+        if let Err(exceeded) = self.check_limits(&guard, user_id) {
+            metrics::counter!(
+                "reqstly_ws_rejected_connections_total",
+                "scope" => exceeded.as_str()
+            )
+            .increment(1);
+            return Err(exceeded);
+        }
+
+        Ok(insert_connection(&mut guard, user_id, Some(auth)))
+    }
+
+    fn check_limits(
+        &self,
+        connections: &ConnectionMap,
+        user_id: Uuid,
+    ) -> Result<(), ConnectionLimitExceeded> {
+        let user_connections =
+            connections.get(&user_id).map_or(0, HashMap::len);
+        if user_connections >= self.limits.max_connections_per_user {
+            return Err(ConnectionLimitExceeded::PerUser);
+        }
+        if total_connections(connections) >= self.limits.max_connections_total {
+            return Err(ConnectionLimitExceeded::Global);
+        }
+
+        Ok(())
+    }
+
+    pub async fn set_format(
+        &self,
+        user_id: Uuid,
+        connection_id: Uuid,
+        format: ConnectionFormat,
+    ) {
+        let mut guard = self.inner.write().await;
+        if let Some(entry) = guard
+            .get_mut(&user_id)
+            .and_then(|connections| connections.get_mut(&connection_id))
+        {
+            entry.format = format;
+        }
+    }
+
+    pub async fn unregister(&self, user_id: Uuid, connection_id: Uuid) {
+        let mut guard = self.inner.write().await;
+
+        if let Some(connections) = guard.get_mut(&user_id) {
+            connections.remove(&connection_id);
+            if connections.is_empty() {
+                guard.remove(&user_id);
+            }
+        }
+        record_active_connections(&guard);
+    }
+
+    pub async fn send_to_user(
+        &self,
+        user_id: Uuid,
+        message: Arc<OutboundEvent>,
+    ) -> usize {
+        let queues = {
+            let guard = self.inner.read().await;
+            guard
+                .get(&user_id)
+                .map(|connections| {
+                    connections
+                        .iter()
+                        .map(|(connection_id, entry)| {
+                            (
+                                *connection_id,
+                                Arc::clone(&entry.queue),
+                                entry.format,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        let mut delivered = 0;
+        let mut closed_connection_ids = Vec::new();
+        let mut slow_connection_ids = Vec::new();
+
+        for (connection_id, queue, format) in queues {
+            // Encode on the publishing task, once per format; the sockets
+            // only copy the shared frame out.
+            if let Err(err) = message.frame(format) {
+                warn!(error = %err, "failed to encode websocket frame");
+            }
+            match queue
+                .push(Arc::clone(&message), self.limits.slow_consumer_policy)
+            {
+                PushOutcome::Queued => {
+                    delivered += 1;
+                }
+                PushOutcome::DroppedOldest => {
+                    delivered += 1;
+                    record_dropped_message("drop_oldest");
+                }
+                PushOutcome::Full => {
+                    record_dropped_message("slow_consumer");
+                    slow_connection_ids.push(connection_id);
+                }
+                PushOutcome::Closed => {
+                    record_dropped_message("closed");
+                    closed_connection_ids.push(connection_id);
+                }
+            }
+            metrics::histogram!("reqstly_ws_queue_depth")
+                .record(queue.len() as f64);
+        }
+
+        if !closed_connection_ids.is_empty() || !slow_connection_ids.is_empty()
+        {
+            let mut guard = self.inner.write().await;
+            if let Some(connections) = guard.get_mut(&user_id) {
+                for connection_id in closed_connection_ids {
+                    connections.remove(&connection_id);
+                }
+                for connection_id in slow_connection_ids {
+                    if let Some(mut entry) = connections.remove(&connection_id)
+                    {
+                        metrics::counter!(
+                            "reqstly_ws_slow_consumer_disconnects_total"
+                        )
+                        .increment(1);
+                        entry.signal(DisconnectReason::SlowConsumer);
+                    }
+                }
+
+                if connections.is_empty() {
+                    guard.remove(&user_id);
+                }
+            }
+            record_active_connections(&guard);
+        }
+
+        delivered
+    }
+
+    pub async fn broadcast_to_users<I>(
+        &self,
+        user_ids: I,
+        message: Arc<OutboundEvent>,
+    ) -> usize
+    where
+        I: IntoIterator<Item = Uuid>,
+    {
+        let unique_user_ids = user_ids.into_iter().collect::<HashSet<_>>();
+        let mut delivered = 0;
+
+        for user_id in unique_user_ids {
+            delivered += self.send_to_user(user_id, Arc::clone(&message)).await;
+        }
+
+        delivered
+    }
+
+    pub async fn connection_count(&self, user_id: Uuid) -> usize {
+        let guard = self.inner.read().await;
+        guard.get(&user_id).map_or(0, HashMap::len)
+    }
+
+    pub async fn total_connection_count(&self) -> usize {
+        let guard = self.inner.read().await;
+        total_connections(&guard)
+    }
+
+    pub async fn disconnect_user(
+        &self,
+        user_id: Uuid,
+        reason: RevocationReason,
+    ) -> usize {
+        self.disconnect_matching(user_id, reason, |_| true).await
+    }
+
+    pub async fn disconnect_session(
+        &self,
+        user_id: Uuid,
+        session_id: &str,
+        reason: RevocationReason,
+    ) -> usize {
+        self.disconnect_matching(user_id, reason, |auth| {
+            matches!(
+                auth,
+                Some(ConnectionAuth::Session { session_id: id, .. })
+                    if id == session_id
+            )
+        })
+        .await
+    }
+
+    async fn disconnect_matching<F>(
+        &self,
+        user_id: Uuid,
+        reason: RevocationReason,
+        predicate: F,
+    ) -> usize
+    where
+        F: Fn(Option<&ConnectionAuth>) -> bool,
+    {
+        let mut guard = self.inner.write().await;
+        let Some(connections) = guard.get_mut(&user_id) else {
+            return 0;
+        };
+
+        let matched = connections
+            .iter()
+            .filter(|(_, entry)| predicate(entry.auth.as_ref()))
+            .map(|(connection_id, _)| *connection_id)
+            .collect::<Vec<_>>();
+
+        for connection_id in &matched {
+            if let Some(mut entry) = connections.remove(connection_id) {
+                entry.signal(DisconnectReason::Revoked(reason));
+            }
+        }
+
+        if connections.is_empty() {
+            guard.remove(&user_id);
+        }
+        record_active_connections(&guard);
+
+        matched.len()
+    }
+}
+
+impl Default for RealtimeHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn insert_connection(
+    connections: &mut ConnectionMap,
+    user_id: Uuid,
+    auth: Option<ConnectionAuth>,
+) -> RealtimeConnection {
+    let connection_id = Uuid::new_v4();
+    let queue = Arc::new(OutboundQueue::new());
+    let (disconnect_tx, disconnect_rx) = oneshot::channel();
+
+    connections.entry(user_id).or_default().insert(
+        connection_id,
+        ConnectionEntry {
+            queue: Arc::clone(&queue),
+            auth,
+            format: ConnectionFormat {
+                wire: WireFormat::default(),
+                protocol_version: PROTOCOL_VERSION_V1,
+            },
+            disconnect: Some(disconnect_tx),
+        },
+    );
+    record_active_connections(connections);
+
+    RealtimeConnection {
+        id: connection_id,
+        outbound: OutboundReceiver { queue },
+        disconnect: disconnect_rx,
+    }
+}
+
+fn total_connections(connections: &ConnectionMap) -> usize {
+    connections.values().map(HashMap::len).sum()
+}
+
+fn record_active_connections(connections: &ConnectionMap) {
+    metrics::gauge!("reqstly_ws_active_connections")
+        .set(total_connections(connections) as f64);
+}
+
+fn record_dropped_message(reason: &'static str) {
+    metrics::counter!("reqstly_ws_dropped_messages_total", "reason" => reason)
+        .increment(1);
+}
+
+#[must_use]
+pub fn parse_allowed_origins(raw: &str) -> Vec<String> {
+    if raw.trim() == "*" {
+        return vec!["*".to_string()];
+    }
+
+    raw.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[must_use]
+pub fn is_origin_allowed(
+    origin: Option<&str>,
+    allowed_origins: &[String],
+) -> bool {
+    if allowed_origins.iter().any(|item| item == "*") {
+        return true;
+    }
+
+    let Some(origin) = origin else {
+        return false;
+    };
+
+    allowed_origins.iter().any(|allowed| allowed == origin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_allowed_origins_supports_csv_and_star() {
+        assert_eq!(parse_allowed_origins("*"), vec!["*"]);
+        assert_eq!(
+            parse_allowed_origins(
+                " https://localhost,https://app.example.com "
+            ),
+            vec!["https://localhost", "https://app.example.com"]
+        );
+    }
+
+    #[test]
+    fn client_message_parses_request_mutations() {
+        let request_id = Uuid::new_v4();
+        let message: ClientMessage = serde_json::from_value(json!({
+            "type": "request.update",
+            "op_id": "op-1",
+            "id": request_id,
+            "data": { "status": "resolved" }
+        }))
+        .expect("request.update should parse");
+
+        assert_eq!(message.op_id(), Some("op-1"));
+        assert!(matches!(
+            message,
+            ClientMessage::RequestUpdate { id, .. } if id == request_id
+        ));
+
+        let hello: ClientMessage =
+            serde_json::from_value(json!({ "type": "hello" }))
+                .expect("hello should parse");
+        assert_eq!(hello.op_id(), None);
+        assert!(
+            serde_json::from_value::<ClientMessage>(json!({
+                "type": "request.create",
+                "data": {}
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn outbound_event_picks_latest_version_not_above_negotiated() {
+        let v1 = EventEnvelope::new("request.patch", None, None, json!({}));
+        let mut v2 = v1.clone();
+        v2.v = 2;
+        v2.payload = json!({ "changes": {} });
+        let event = OutboundEvent::new(v1)
+            .and_then(|event| event.with_version(v2))
+            .expect("event should encode");
+
+        assert!(!event.json(1).contains("changes"));
+        assert!(event.json(2).contains("changes"));
+        assert!(event.json(9).contains("changes"));
+    }
+
+    #[tokio::test]
+    async fn hub_encodes_each_binary_format_once() {
+        let hub = RealtimeHub::new();
+        let user_id = Uuid::new_v4();
+        let format = ConnectionFormat {
+            wire: WireFormat {
+                encoding: WireEncoding::Msgpack,
+                compression: WireCompression::Deflate,
+            },
+            protocol_version: PROTOCOL_VERSION_V1,
+        };
+        let (first_id, mut first) = hub.register(user_id).await;
+        let (second_id, mut second) = hub.register(user_id).await;
+        hub.set_format(user_id, first_id, format).await;
+        hub.set_format(user_id, second_id, format).await;
+
+        hub.send_to_user(user_id, outbound_event("sync.required"))
+            .await;
+        let first = first.recv().await.expect("first connection");
+        let second = second.recv().await.expect("second connection");
+
+        let (
+            Ok(WireFrame::Binary(first_bytes)),
+            Ok(WireFrame::Binary(second_bytes)),
+        ) = (first.frame(format), second.frame(format))
+        else {
+            panic!("msgpack frames should be binary");
+        };
+        assert!(Arc::ptr_eq(&first_bytes, &second_bytes));
+        let decoded = format
+            .wire
+            .decode_binary(&first_bytes)
+            .expect("frame should decode");
+        assert_eq!(decoded["type"], "sync.required");
+    }
+
+    #[tokio::test]
+    async fn hub_sends_and_cleans_up_connections() {
+        let hub = RealtimeHub::new();
+        let user_id = Uuid::new_v4();
+
+        let (connection_id, mut receiver) = hub.register(user_id).await;
+        let message = outbound_event("request.created");
+
+        let delivered = hub.send_to_user(user_id, Arc::clone(&message)).await;
+        assert_eq!(delivered, 1);
+
+        let received =
+            receiver.recv().await.expect("receiver should get event");
+        assert!(Arc::ptr_eq(&received, &message));
+
+        drop(receiver);
+
+        let delivered_after_drop =
+            hub.send_to_user(user_id, Arc::clone(&message)).await;
+        assert_eq!(delivered_after_drop, 0);
+
+        hub.unregister(user_id, connection_id).await;
+        assert_eq!(hub.connection_count(user_id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn hub_disconnects_only_connections_bound_to_session() {
+        let hub = RealtimeHub::new();
+        let user_id = Uuid::new_v4();
+
+        let session_a = hub
+            .register_authenticated(
+                user_id,
+                ConnectionAuth::Session {
+                    session_id: "session-a".to_string(),
+                    session_version: 1,
+                },
+            )
+            .await
+            .expect("connection should fit within limits");
+        let mut session_b = hub
+            .register_authenticated(
+                user_id,
+                ConnectionAuth::Session {
+                    session_id: "session-b".to_string(),
+                    session_version: 1,
+                },
+            )
+            .await
+            .expect("connection should fit within limits");
+
+        let closed = hub
+            .disconnect_session(
+                user_id,
+                "session-a",
+                RevocationReason::LoggedOut,
+            )
+            .await;
+        assert_eq!(closed, 1);
+        assert_eq!(
+            session_a
+                .disconnect
+                .await
+                .expect("revocation should be signalled"),
+            DisconnectReason::Revoked(RevocationReason::LoggedOut)
+        );
+        assert!(session_b.disconnect.try_recv().is_err());
+        assert_eq!(hub.connection_count(user_id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn hub_disconnect_user_closes_every_connection() {
+        let hub = RealtimeHub::new();
+        let user_id = Uuid::new_v4();
+
+        let (_legacy_id, mut legacy_receiver) = hub.register(user_id).await;
+        let token_connection = hub
+            .register_authenticated(
+                user_id,
+                ConnectionAuth::WsToken {
+                    token_fingerprint: vec![7; 32],
+                },
+            )
+            .await
+            .expect("connection should fit within limits");
+
+        let closed = hub
+            .disconnect_user(user_id, RevocationReason::SessionsRevoked)
+            .await;
+        assert_eq!(closed, 2);
+        assert_eq!(
+            token_connection
+                .disconnect
+                .await
+                .expect("revocation should be signalled"),
+            DisconnectReason::Revoked(RevocationReason::SessionsRevoked)
+        );
+        assert!(legacy_receiver.recv().await.is_none());
+        assert_eq!(hub.connection_count(user_id).await, 0);
+    }
+
+    fn outbound_event(event_type: &str) -> Arc<OutboundEvent> {
+        let envelope = EventEnvelope::new(event_type, None, None, json!({}));
+        Arc::new(OutboundEvent::new(envelope).expect("event should encode"))
+    }
+
+    fn token_auth(seed: u8) -> ConnectionAuth {
+        ConnectionAuth::WsToken {
+            token_fingerprint: vec![seed; 32],
+        }
+    }
+
+    #[tokio::test]
+    async fn hub_enforces_per_user_and_global_connection_caps() {
+        let hub = RealtimeHub::with_limits(RealtimeLimits {
+            max_connections_per_user: 2,
+            max_connections_total: 3,
+            slow_consumer_policy: SlowConsumerPolicy::Disconnect,
+        });
+        let user_a = Uuid::new_v4();
+        let user_b = Uuid::new_v4();
+
+        for seed in 0..2 {
+            hub.register_authenticated(user_a, token_auth(seed))
+                .await
+                .expect("connection should fit within limits");
+        }
+        assert_eq!(
+            hub.register_authenticated(user_a, token_auth(2))
+                .await
+                .expect_err("per-user cap should apply"),
+            ConnectionLimitExceeded::PerUser
+        );
+
+        hub.register_authenticated(user_b, token_auth(3))
+            .await
+            .expect("connection should fit within limits");
+        assert_eq!(
+            hub.register_authenticated(user_b, token_auth(4))
+                .await
+                .expect_err("global cap should apply"),
+            ConnectionLimitExceeded::Global
+        );
+        assert_eq!(hub.total_connection_count().await, 3);
+    }
+
+    #[tokio::test]
+    async fn hub_drop_oldest_policy_keeps_newest_events() {
+        let hub = RealtimeHub::with_limits(RealtimeLimits {
+            slow_consumer_policy: SlowConsumerPolicy::DropOldest,
+            ..RealtimeLimits::default()
+        });
+        let user_id = Uuid::new_v4();
+        let mut connection = hub
+            .register_authenticated(user_id, token_auth(1))
+            .await
+            .expect("connection should fit within limits");
+
+        for index in 0..=WS_QUEUE_DEPTH {
+            let delivered = hub
+                .send_to_user(user_id, outbound_event(&index.to_string()))
+                .await;
+            assert_eq!(delivered, 1);
+        }
+
+        let first = connection
+            .outbound
+            .recv()
+            .await
+            .expect("queue should hold events");
+        assert!(first.json(PROTOCOL_VERSION_V1).contains(r#""type":"1""#));
+        assert!(connection.disconnect.try_recv().is_err());
+        assert_eq!(hub.connection_count(user_id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn hub_disconnect_policy_signals_slow_consumer() {
+        let hub = RealtimeHub::new();
+        let user_id = Uuid::new_v4();
+        let connection = hub
+            .register_authenticated(user_id, token_auth(1))
+            .await
+            .expect("connection should fit within limits");
+
+        for index in 0..WS_QUEUE_DEPTH {
+            hub.send_to_user(user_id, outbound_event(&index.to_string()))
+                .await;
+        }
+        let delivered =
+            hub.send_to_user(user_id, outbound_event("overflow")).await;
+
+        assert_eq!(delivered, 0);
+        assert_eq!(
+            connection
+                .disconnect
+                .await
+                .expect("slow consumer should be signalled"),
+            DisconnectReason::SlowConsumer
+        );
+        assert_eq!(hub.connection_count(user_id).await, 0);
+    }
+}
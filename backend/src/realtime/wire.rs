@@ -0,0 +1,255 @@
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
+
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::WS_MAX_MESSAGE_BYTES;
+
+pub const WS_SUBPROTOCOL_JSON: &str = "reqstly.json";
+pub const WS_SUBPROTOCOL_MSGPACK: &str = "reqstly.msgpack";
+pub const WS_SUBPROTOCOL_MSGPACK_DEFLATE: &str = "reqstly.msgpack+deflate";
+pub const WS_SUBPROTOCOLS: [&str; 3] = [
+    WS_SUBPROTOCOL_MSGPACK_DEFLATE,
+    WS_SUBPROTOCOL_MSGPACK,
+    WS_SUBPROTOCOL_JSON,
+];
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WireEncoding {
+    #[default]
+    Json,
+    Msgpack,
+}
+
+// The websocket stack has no `permessage-deflate` (RFC 7692), so this is our
+// own scheme: each frame's payload is a standalone raw deflate stream and
+// always travels as a binary frame. See `/api/v1/realtime/schema` docs.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WireCompression {
+    #[default]
+    None,
+    Deflate,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WireFormat {
+    pub encoding: WireEncoding,
+    pub compression: WireCompression,
+}
+
+#[derive(Debug, Clone)]
+pub enum WireFrame {
+    Text(Arc<str>),
+    Binary(Arc<[u8]>),
+}
+
+#[derive(Debug, Error)]
+pub enum WireError {
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("msgpack encode: {0}")]
+    MsgpackEncode(#[from] rmp_serde::encode::Error),
+    #[error("msgpack decode: {0}")]
+    MsgpackDecode(#[from] rmp_serde::decode::Error),
+    #[error("deflate: {0}")]
+    Deflate(#[from] std::io::Error),
+    #[error("binary frames were not negotiated")]
+    BinaryNotNegotiated,
+    #[error("decompressed message exceeds {WS_MAX_MESSAGE_BYTES} bytes")]
+    TooLarge,
+}
+
+impl WireFormat {
+    #[must_use]
+    pub fn from_subprotocol(protocol: &str) -> Option<Self> {
+        let (encoding, compression) = match protocol {
+            WS_SUBPROTOCOL_JSON => (WireEncoding::Json, WireCompression::None),
+            WS_SUBPROTOCOL_MSGPACK => {
+                (WireEncoding::Msgpack, WireCompression::None)
+            }
+            WS_SUBPROTOCOL_MSGPACK_DEFLATE => {
+                (WireEncoding::Msgpack, WireCompression::Deflate)
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            encoding,
+            compression,
+        })
+    }
+
+    #[must_use]
+    pub fn is_binary(self) -> bool {
+        self.encoding == WireEncoding::Msgpack
+            || self.compression == WireCompression::Deflate
+    }
+
+    pub fn encode_frame<T>(self, event: &T) -> Result<WireFrame, WireError>
+    where
+        T: Serialize + ?Sized,
+    {
+        if !self.is_binary() {
+            return Ok(WireFrame::Text(serde_json::to_string(event)?.into()));
+        }
+
+        let bytes = match self.encoding {
+            WireEncoding::Json => serde_json::to_vec(event)?,
+            WireEncoding::Msgpack => {
+                // Human-readable keeps ids and timestamps as the strings
+                // JSON clients see instead of raw bytes.
+                let mut bytes = Vec::new();
+                event.serialize(
+                    &mut rmp_serde::Serializer::new(&mut bytes)
+                        .with_struct_map()
+                        .with_human_readable(),
+                )?;
+                bytes
+            }
+        };
+
+        let bytes = match self.compression {
+            WireCompression::None => bytes,
+            WireCompression::Deflate => {
+                let mut encoder =
+                    DeflateEncoder::new(Vec::new(), Compression::fast());
+                encoder.write_all(&bytes)?;
+                encoder.finish()?
+            }
+        };
+
+        Ok(WireFrame::Binary(bytes.into()))
+    }
+
+    pub fn decode_binary(self, bytes: &[u8]) -> Result<Value, WireError> {
+        if !self.is_binary() {
+            return Err(WireError::BinaryNotNegotiated);
+        }
+
+        let inflated;
+        let bytes = match self.compression {
+            WireCompression::None => bytes,
+            WireCompression::Deflate => {
+                let mut buffer = Vec::new();
+                DeflateDecoder::new(bytes)
+                    .take(WS_MAX_MESSAGE_BYTES as u64 + 1)
+                    .read_to_end(&mut buffer)?;
+                if buffer.len() > WS_MAX_MESSAGE_BYTES {
+                    return Err(WireError::TooLarge);
+                }
+                inflated = buffer;
+                inflated.as_slice()
+            }
+        };
+
+        match self.encoding {
+            WireEncoding::Json => Ok(serde_json::from_slice(bytes)?),
+            WireEncoding::Msgpack => Ok(rmp_serde::from_slice(bytes)?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn binary(frame: WireFrame) -> Arc<[u8]> {
+        match frame {
+            WireFrame::Binary(bytes) => bytes,
+            WireFrame::Text(_) => panic!("expected a binary frame"),
+        }
+    }
+
+    #[test]
+    fn default_format_keeps_json_text() {
+        let frame = WireFormat::default()
+            .encode_frame(&json!({ "type": "sync.required" }))
+            .expect("json should pass through");
+        assert!(
+            matches!(frame, WireFrame::Text(text) if text.contains("sync"))
+        );
+        assert!(matches!(
+            WireFormat::default().decode_binary(&[0x80]),
+            Err(WireError::BinaryNotNegotiated)
+        ));
+    }
+
+    #[test]
+    fn subprotocols_map_to_formats() {
+        let format =
+            WireFormat::from_subprotocol(WS_SUBPROTOCOL_MSGPACK_DEFLATE)
+                .expect("known subprotocol");
+        assert_eq!(format.encoding, WireEncoding::Msgpack);
+        assert_eq!(format.compression, WireCompression::Deflate);
+        assert_eq!(WireFormat::from_subprotocol("graphql-ws"), None);
+    }
+
+    #[test]
+    fn msgpack_deflate_round_trips_events() {
+        let format = WireFormat {
+            encoding: WireEncoding::Msgpack,
+            compression: WireCompression::Deflate,
+        };
+        let event = json!({
+            "v": 1,
+            "type": "request.patch",
+            "payload": { "changed_fields": ["status"] }
+        });
+
+        let bytes =
+            binary(format.encode_frame(&event).expect("event should encode"));
+        assert!(bytes.len() < event.to_string().len());
+        assert_eq!(
+            format.decode_binary(&bytes).expect("frame should decode"),
+            event
+        );
+    }
+
+    #[test]
+    fn deflate_rejects_oversized_payloads() {
+        let format = WireFormat {
+            encoding: WireEncoding::Json,
+            compression: WireCompression::Deflate,
+        };
+        let oversized = json!({ "data": "x".repeat(WS_MAX_MESSAGE_BYTES) });
+        let bytes = binary(
+            format
+                .encode_frame(&oversized)
+                .expect("event should encode"),
+        );
+
+        assert!(matches!(
+            format.decode_binary(&bytes),
+            Err(WireError::TooLarge)
+        ));
+    }
+}
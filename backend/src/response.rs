@@ -28,10 +28,14 @@ pub struct ResponseMeta {
 #[derive(Serialize)]
 pub struct ListMeta {
     pub request_id: String,
-    pub total: u64,
-    pub page: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
     pub limit: u64,
-    pub total_pages: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<u64>,
+    pub next_cursor: Option<String>,
 }
 
 pub fn ok<T>(status: StatusCode, data: T) -> (StatusCode, Json<ApiResponse<T>>)
@@ -51,14 +55,16 @@ where
 
 pub fn list<T>(
     items: Vec<T>,
-    page: u64,
+    page: Option<u64>,
     limit: u64,
-    total: u64,
+    total: Option<u64>,
+    next_cursor: Option<String>,
 ) -> (StatusCode, Json<ApiListResponse<T>>)
 where
     T: Serialize,
 {
-    let total_pages = if total == 0 { 0 } else { total.div_ceil(limit) };
+    let total_pages =
+        total.map(|total| if total == 0 { 0 } else { total.div_ceil(limit) });
 
     (
         StatusCode::OK,
@@ -70,6 +76,7 @@ where
                 page,
                 limit,
                 total_pages,
+                next_cursor,
             },
         }),
     )
@@ -81,13 +88,22 @@ mod tests {
 
     #[test]
     fn list_sets_total_pages_with_div_ceil() {
-        let (_status, body) = list(vec![1, 2], 1, 2, 5);
-        assert_eq!(body.0.meta.total_pages, 3);
+        let (_status, body) = list(vec![1, 2], Some(1), 2, Some(5), None);
+        assert_eq!(body.0.meta.total_pages, Some(3));
     }
 
     #[test]
     fn list_sets_zero_total_pages_when_empty() {
-        let (_status, body) = list(Vec::<i32>::new(), 1, 20, 0);
-        assert_eq!(body.0.meta.total_pages, 0);
+        let (_status, body) =
+            list(Vec::<i32>::new(), Some(1), 20, Some(0), None);
+        assert_eq!(body.0.meta.total_pages, Some(0));
+    }
+
+    #[test]
+    fn list_omits_total_pages_without_total() {
+        let (_status, body) =
+            list(vec![1], None, 20, None, Some("next".to_string()));
+        assert_eq!(body.0.meta.total_pages, None);
+        assert_eq!(body.0.meta.next_cursor.as_deref(), Some("next"));
     }
 }
@@ -105,3 +105,44 @@ async fn unauthorized_and_not_found_errors_match_contract() {
 
     ctx.cleanup().await;
 }
+
+#[tokio::test]
+async fn realtime_schema_contract_covers_every_event_type() {
+    let ctx = TestContext::new().await;
+
+    let (status, payload) =
+        send_json(&ctx.app, Method::GET, "/api/v1/realtime/schema", None, None)
+            .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_success_envelope(&payload);
+    assert_eq!(payload["data"]["protocol_version"], 2);
+    assert_eq!(
+        payload["data"]["supported_versions"],
+        serde_json::json!([1, 2])
+    );
+
+    let schema_text = payload["data"]["events"].to_string();
+    for event_type in [
+        "request.created",
+        "request.patch",
+        "request.deleted",
+        "audit.append",
+        "profile.patch",
+        "sync.required",
+        "session.revoked",
+        "hello.accepted",
+        "ack",
+        "nack",
+        "request.batch",
+    ] {
+        assert!(
+            schema_text.contains(&format!("\"{event_type}\"")),
+            "schema is missing {event_type}"
+        );
+    }
+    assert!(
+        payload["data"]["version_overrides"]["2"]["request.patch"].is_object()
+    );
+
+    ctx.cleanup().await;
+}
@@ -1,18 +1,32 @@
 mod support;
 
+use std::sync::Arc;
+
 use axum::{
     body::Body,
     http::{Method, Request, StatusCode, header},
 };
 use http_body_util::BodyExt;
+use reqstly_backend::{
+    auth::middleware,
+    realtime::{
+        ConnectionAuth, DisconnectReason, EventEnvelope, OutboundEvent,
+        RealtimeLimits, RevocationReason, WS_CLOSE_SLOW_CONSUMER,
+        WS_CLOSE_TRY_AGAIN_LATER, WireFormat,
+        wire::WS_SUBPROTOCOL_MSGPACK_DEFLATE,
+    },
+};
 use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
 use sqlx::Error as SqlxError;
 use tower::util::ServiceExt;
 use uuid::Uuid;
 
 use support::{
-    TestClaims, TestContext, build_token, build_token_with_claims,
-    create_request, insert_auth_user, insert_ws_token_issuance, send_json,
+    TestClaims, TestContext, build_token, build_token_with_claims, connect_ws,
+    create_request, insert_auth_user, insert_ws_token_issuance,
+    recv_ws_binary_event, recv_ws_close, recv_ws_event, send_json,
+    send_ws_json, wait_for_connections,
 };
 
 async fn assert_me_unauthorized_for_token(
@@ -157,6 +171,308 @@ async fn auth_revoke_all_sessions_revokes_active_ws_tokens() {
     ctx.cleanup().await;
 }
 
+#[tokio::test]
+async fn auth_revoke_all_sessions_disconnects_live_sockets() {
+    let ctx = TestContext::new().await;
+
+    let token_auth = ConnectionAuth::WsToken {
+        token_fingerprint: Sha256::digest(ctx.token.as_bytes()).to_vec(),
+    };
+    let connection = ctx
+        .realtime_hub
+        .register_authenticated(ctx.user_id, token_auth.clone())
+        .await
+        .expect("connection should fit within limits");
+
+    let still_valid = middleware::revalidate_ws_connection(
+        &ctx.pool,
+        ctx.user_id,
+        &token_auth,
+    )
+    .await
+    .expect("revalidation should run");
+    assert_eq!(still_valid, None);
+
+    let (revoke_status, _) = send_json(
+        &ctx.app,
+        Method::POST,
+        "/api/v1/auth/sessions/revoke",
+        Some(&ctx.token),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(revoke_status, StatusCode::OK);
+
+    assert_eq!(
+        connection
+            .disconnect
+            .await
+            .expect("connection should be signalled"),
+        DisconnectReason::Revoked(RevocationReason::SessionsRevoked)
+    );
+    assert_eq!(ctx.realtime_hub.connection_count(ctx.user_id).await, 0);
+
+    let revoked = middleware::revalidate_ws_connection(
+        &ctx.pool,
+        ctx.user_id,
+        &token_auth,
+    )
+    .await
+    .expect("revalidation should run");
+    assert_eq!(revoked, Some(RevocationReason::TokenRevoked));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn ws_revalidation_detects_lockout_and_deactivation() {
+    let ctx = TestContext::new().await;
+
+    let session_auth = ConnectionAuth::Session {
+        session_id: "integration-session".to_string(),
+        session_version: 1,
+    };
+    assert_eq!(
+        middleware::revalidate_ws_connection(
+            &ctx.pool,
+            ctx.user_id,
+            &session_auth
+        )
+        .await
+        .expect("revalidation should run"),
+        None
+    );
+
+    sqlx::query(
+        "UPDATE app.user_auth_security
+         SET locked_until = NOW() + INTERVAL '10 minutes'
+         WHERE user_id = $1",
+    )
+    .bind(ctx.user_id)
+    .execute(&ctx.pool)
+    .await
+    .expect("lockout should apply");
+    assert_eq!(
+        middleware::revalidate_ws_connection(
+            &ctx.pool,
+            ctx.user_id,
+            &session_auth
+        )
+        .await
+        .expect("revalidation should run"),
+        Some(RevocationReason::AccountLocked)
+    );
+
+    sqlx::query("UPDATE app.app_users SET is_active = FALSE WHERE id = $1")
+        .bind(ctx.user_id)
+        .execute(&ctx.pool)
+        .await
+        .expect("deactivation should apply");
+    assert_eq!(
+        middleware::revalidate_ws_connection(
+            &ctx.pool,
+            ctx.user_id,
+            &session_auth
+        )
+        .await
+        .expect("revalidation should run"),
+        Some(RevocationReason::AccountDisabled)
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn password_lockout_disconnects_live_sockets() {
+    let ctx = TestContext::new().await;
+    let (signup_status, signup) = send_json(
+        &ctx.app,
+        Method::POST,
+        "/api/v1/auth/signup",
+        None,
+        Some(json!({
+            "email": "locked@example.com",
+            "password": "correct horse battery"
+        })),
+    )
+    .await;
+    assert_eq!(signup_status, StatusCode::CREATED);
+    let user_id = signup["data"]["id"]
+        .as_str()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .expect("signup should return the user id");
+
+    let connection = ctx
+        .realtime_hub
+        .register_authenticated(
+            user_id,
+            ConnectionAuth::Session {
+                session_id: "locked-session".to_string(),
+                session_version: 1,
+            },
+        )
+        .await
+        .expect("connection should fit within limits");
+
+    sqlx::query(
+        "UPDATE app.user_password_identities
+         SET failed_attempts = 9
+         WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .execute(&ctx.pool)
+    .await
+    .expect("failed attempts should update");
+    let (login_status, _) = send_json(
+        &ctx.app,
+        Method::POST,
+        "/api/v1/auth/login/password",
+        None,
+        Some(json!({
+            "email": "locked@example.com",
+            "password": "wrong horse battery"
+        })),
+    )
+    .await;
+    assert_eq!(login_status, StatusCode::UNAUTHORIZED);
+
+    assert_eq!(
+        connection
+            .disconnect
+            .await
+            .expect("connection should be signalled"),
+        DisconnectReason::Revoked(RevocationReason::AccountLocked)
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn ws_request_create_acks_nacks_and_broadcasts() {
+    let ctx = TestContext::new().await;
+    let url = ctx.serve().await;
+    let (mut writer, _) = connect_ws(&url, &ctx.token, None).await;
+    let (mut subscriber, _) = connect_ws(&url, &ctx.token, None).await;
+
+    send_ws_json(
+        &mut writer,
+        json!({
+            "type": "request.create",
+            "op_id": "op-create",
+            "data": {
+                "title": "Socket laptop",
+                "category": "IT",
+                "priority": "high"
+            }
+        }),
+    )
+    .await;
+    let ack = recv_ws_event(&mut writer, "ack").await;
+    assert_eq!(ack["payload"]["op_id"], "op-create");
+    assert_eq!(ack["payload"]["request"]["title"], "Socket laptop");
+
+    let created = recv_ws_event(&mut subscriber, "request.created").await;
+    assert_eq!(
+        created["payload"]["request"]["id"],
+        ack["payload"]["request"]["id"]
+    );
+
+    send_ws_json(
+        &mut writer,
+        json!({
+            "type": "request.create",
+            "op_id": "op-invalid",
+            "data": { "title": "", "category": "IT", "priority": "high" }
+        }),
+    )
+    .await;
+    let nack = recv_ws_event(&mut writer, "nack").await;
+    assert_eq!(nack["payload"]["op_id"], "op-invalid");
+    assert_eq!(nack["payload"]["error"]["code"], "VALIDATION_ERROR");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn ws_connection_over_cap_closes_with_try_again_later() {
+    let ctx = TestContext::with_realtime_limits(RealtimeLimits {
+        max_connections_per_user: 1,
+        ..RealtimeLimits::default()
+    })
+    .await;
+    let url = ctx.serve().await;
+    let (_first, _) = connect_ws(&url, &ctx.token, None).await;
+    wait_for_connections(&ctx, 1).await;
+
+    let (mut second, _) = connect_ws(&url, &ctx.token, None).await;
+    let (code, _) = recv_ws_close(&mut second).await;
+    assert_eq!(code, WS_CLOSE_TRY_AGAIN_LATER);
+    assert_eq!(ctx.realtime_hub.connection_count(ctx.user_id).await, 1);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ws_slow_consumer_is_told_to_resync_and_closed() {
+    let ctx = TestContext::new().await;
+    let url = ctx.serve().await;
+    let (mut socket, _) = connect_ws(&url, &ctx.token, None).await;
+    wait_for_connections(&ctx, 1).await;
+
+    // The client never reads, so once the socket buffers fill up the
+    // outbound queue does too.
+    let padding = "x".repeat(64 * 1024);
+    let event = Arc::new(
+        OutboundEvent::new(EventEnvelope::new(
+            "request.patch",
+            None,
+            None,
+            json!({ "padding": padding }),
+        ))
+        .expect("event should encode"),
+    );
+    let mut pushed = 0;
+    while ctx.realtime_hub.connection_count(ctx.user_id).await > 0 {
+        assert!(pushed < 10_000, "slow consumer was never disconnected");
+        ctx.realtime_hub
+            .send_to_user(ctx.user_id, Arc::clone(&event))
+            .await;
+        pushed += 1;
+    }
+
+    let (code, event_types) = recv_ws_close(&mut socket).await;
+    assert_eq!(code, WS_CLOSE_SLOW_CONSUMER);
+    assert_eq!(
+        event_types.last().map(String::as_str),
+        Some("sync.required")
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn ws_msgpack_deflate_subprotocol_sends_binary_events() {
+    let ctx = TestContext::new().await;
+    let url = ctx.serve().await;
+    let (mut socket, negotiated) =
+        connect_ws(&url, &ctx.token, Some(WS_SUBPROTOCOL_MSGPACK_DEFLATE))
+            .await;
+    assert_eq!(negotiated.as_deref(), Some(WS_SUBPROTOCOL_MSGPACK_DEFLATE));
+    wait_for_connections(&ctx, 1).await;
+
+    let (status, _) = create_request(&ctx, "Binary laptop", "IT", "high").await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let format = WireFormat::from_subprotocol(WS_SUBPROTOCOL_MSGPACK_DEFLATE)
+        .expect("known subprotocol");
+    let event =
+        recv_ws_binary_event(&mut socket, format, "request.created").await;
+    assert_eq!(event["payload"]["request"]["title"], "Binary laptop");
+    assert_eq!(event["request_id"], event["payload"]["request"]["id"]);
+
+    ctx.cleanup().await;
+}
+
 #[tokio::test]
 async fn ownership_isolation_blocks_cross_user_access() {
     let ctx = TestContext::new().await;
@@ -4,14 +4,23 @@ use axum::{
     body::Body,
     http::{Method, Request, StatusCode, header},
 };
+use futures_util::{SinkExt, StreamExt};
 use http_body_util::BodyExt;
 use jsonwebtoken::{EncodingKey, Header, encode};
-use reqstly_backend::{AppState, build_app, db};
+use reqstly_backend::{
+    AppState, build_app, db,
+    realtime::{RealtimeHub, RealtimeLimits, WireFormat},
+};
 use serde::Serialize;
 use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use sqlx::PgPool;
 use time::{Duration, OffsetDateTime};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{
+    MaybeTlsStream, WebSocketStream, connect_async,
+    tungstenite::{Message, client::IntoClientRequest, http::HeaderValue},
+};
 use tower::util::ServiceExt;
 use tower_sessions::{MemoryStore, SessionManagerLayer};
 use url::Url;
@@ -29,8 +38,11 @@ pub struct TestContext {
     pub db_name: String,
     pub user_id: Uuid,
     pub token: String,
+    pub realtime_hub: RealtimeHub,
 }
 
+pub type TestSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
 #[derive(Debug, Serialize)]
 pub struct TestClaims {
     pub sub: String,
@@ -42,6 +54,10 @@ pub struct TestClaims {
 
 impl TestContext {
     pub async fn new() -> Self {
+        Self::with_realtime_limits(RealtimeLimits::default()).await
+    }
+
+    pub async fn with_realtime_limits(limits: RealtimeLimits) -> Self {
         let admin_database_url = std::env::var("TEST_DATABASE_ADMIN_URL")
             .unwrap_or_else(|_| DEFAULT_ADMIN_DATABASE_URL.to_string());
 
@@ -62,6 +78,7 @@ impl TestContext {
 
         let token = build_token(user_id);
         insert_ws_token_issuance(&pool, user_id, &token).await;
+        let realtime_hub = RealtimeHub::with_limits(limits);
         let app = build_app(
             AppState {
                 db: pool.clone(),
@@ -73,8 +90,9 @@ impl TestContext {
                     "Reqstly Integration Tests",
                 )
                 .expect("passkey service should initialize"),
-                realtime_hub: reqstly_backend::realtime::RealtimeHub::new(),
+                realtime_hub: realtime_hub.clone(),
                 ws_allowed_origins: vec!["*".to_string()],
+                inbound_email_token: None,
             },
             "*",
         )
@@ -90,9 +108,24 @@ impl TestContext {
             db_name,
             user_id,
             token,
+            realtime_hub,
         }
     }
 
+    pub async fn serve(&self) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("test listener should bind");
+        let address = listener.local_addr().expect("listener has an address");
+        let app = self.app.clone();
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("test server should run");
+        });
+        format!("ws://{address}/ws")
+    }
+
     pub async fn cleanup(self) {
         self.pool.close().await;
         drop_test_database(&self.admin_database_url, &self.db_name).await;
@@ -271,3 +304,126 @@ pub async fn insert_ws_token_issuance(
     .await
     .expect("test ws token issuance insert should succeed");
 }
+
+pub async fn connect_ws(
+    url: &str,
+    token: &str,
+    subprotocol: Option<&str>,
+) -> (TestSocket, Option<String>) {
+    let mut request = format!("{url}?token={token}")
+        .into_client_request()
+        .expect("websocket request should build");
+    if let Some(subprotocol) = subprotocol {
+        request.headers_mut().insert(
+            "sec-websocket-protocol",
+            HeaderValue::from_str(subprotocol).expect("valid subprotocol"),
+        );
+    }
+
+    let (socket, response) = connect_async(request)
+        .await
+        .expect("websocket should connect");
+    let negotiated = response
+        .headers()
+        .get("sec-websocket-protocol")
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+    (socket, negotiated)
+}
+
+pub async fn send_ws_json(socket: &mut TestSocket, message: Value) {
+    socket
+        .send(Message::Text(message.to_string()))
+        .await
+        .expect("websocket message should send");
+}
+
+pub async fn recv_ws_event(socket: &mut TestSocket, event_type: &str) -> Value {
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let message = socket
+                .next()
+                .await
+                .expect("websocket should stay open")
+                .expect("websocket frame should read");
+            let Message::Text(text) = message else {
+                continue;
+            };
+            let event = serde_json::from_str::<Value>(&text)
+                .expect("websocket event should be json");
+            if event["type"] == event_type {
+                return event;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("{event_type} should arrive within timeout"))
+}
+
+pub async fn recv_ws_binary_event(
+    socket: &mut TestSocket,
+    format: WireFormat,
+    event_type: &str,
+) -> Value {
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let message = socket
+                .next()
+                .await
+                .expect("websocket should stay open")
+                .expect("websocket frame should read");
+            let bytes = match message {
+                Message::Binary(bytes) => bytes,
+                Message::Text(text) => panic!("unexpected text frame: {text}"),
+                _ => continue,
+            };
+            let event = format
+                .decode_binary(&bytes)
+                .expect("binary frame should decode");
+            if event["type"] == event_type {
+                return event;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("{event_type} should arrive within timeout"))
+}
+
+pub async fn recv_ws_close(socket: &mut TestSocket) -> (u16, Vec<String>) {
+    let mut event_types = Vec::new();
+    tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        loop {
+            let message = socket
+                .next()
+                .await
+                .expect("websocket should send a close frame")
+                .expect("websocket frame should read");
+            match message {
+                Message::Text(text) => {
+                    let event = serde_json::from_str::<Value>(&text)
+                        .expect("websocket event should be json");
+                    event_types.push(
+                        event["type"].as_str().unwrap_or_default().to_string(),
+                    );
+                }
+                Message::Close(frame) => {
+                    let code = frame.map_or(0, |frame| frame.code.into());
+                    return (code, event_types);
+                }
+                _ => {}
+            }
+        }
+    })
+    .await
+    .expect("websocket should close within timeout")
+}
+
+pub async fn wait_for_connections(ctx: &TestContext, expected: usize) {
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while ctx.realtime_hub.connection_count(ctx.user_id).await != expected {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("connection count should settle");
+}
@@ -44,7 +44,7 @@ function asRecord(value: unknown): Record<string, unknown> | null {
   return value as Record<string, unknown>;
 }
 
-function parseRealtimeEvent(raw: string): RealtimeServerEvent | null {
+function parseRealtimeEvents(raw: string): RealtimeServerEvent[] | null {
   let parsed: unknown;
 
   try {
@@ -53,8 +53,37 @@ function parseRealtimeEvent(raw: string): RealtimeServerEvent | null {
     return null;
   }
 
-  const envelope = asRecord(parsed) as RealtimeEnvelope | null;
+  const envelope = asRecord(parsed);
   if (!envelope) return null;
+
+  // Bulk operations pack several events for this client into one frame;
+  // unwrap them so listeners only ever see the individual event types.
+  if (envelope.type === 'request.batch') {
+    const events = asRecord(envelope.payload)?.events;
+    if (!Array.isArray(events)) return null;
+
+    const unwrapped: RealtimeServerEvent[] = [];
+    for (const item of events) {
+      const inner = asRecord(item);
+      if (!inner) continue;
+      const parsedInner = validateRealtimeEvent({
+        ...envelope,
+        type: inner.type,
+        payload: inner.payload
+      });
+      if (parsedInner) unwrapped.push(parsedInner);
+    }
+    return unwrapped;
+  }
+
+  const single = validateRealtimeEvent(envelope);
+  return single ? [single] : null;
+}
+
+function validateRealtimeEvent(
+  value: Record<string, unknown>
+): RealtimeServerEvent | null {
+  const envelope = value as unknown as RealtimeEnvelope;
   if (typeof envelope.type !== 'string' || !supportedTypes.has(envelope.type)) {
     return null;
   }
@@ -222,24 +251,26 @@ function connect(initialToken?: string): void {
       return;
     }
 
-    const parsed = parseRealtimeEvent(event.data);
-    if (!parsed) {
+    const events = parseRealtimeEvents(event.data);
+    if (!events) {
       logDebug(SCOPE, 'Ignored malformed realtime event');
       return;
     }
 
-    realtimeLastEventTs.set(parsed.ts);
+    for (const parsed of events) {
+      realtimeLastEventTs.set(parsed.ts);
 
-    if (parsed.type === 'sync.required') {
-      notifyResyncListeners('server-sync-required');
-    }
+      if (parsed.type === 'sync.required') {
+        notifyResyncListeners('server-sync-required');
+      }
 
-    logDebug(SCOPE, 'Realtime event received', {
-      type: parsed.type,
-      requestId: parsed.request_id ?? null
-    });
+      logDebug(SCOPE, 'Realtime event received', {
+        type: parsed.type,
+        requestId: parsed.request_id ?? null
+      });
 
-    notifyListeners(parsed);
+      notifyListeners(parsed);
+    }
   };
 
   socket.onerror = (event) => {
@@ -56,6 +56,14 @@ export interface SupportRequest {
   assignee_user_id: string | null;
   assignee_email: string | null;
   assignee_display_name: string | null;
+  checklist_total: number;
+  checklist_done: number;
+  checklist_required: boolean;
+  sla_first_response_due_at: string | null;
+  sla_resolution_due_at: string | null;
+  sla_remaining_seconds: number | null;
+  sla_first_response_breached: boolean;
+  sla_resolution_breached: boolean;
   created_at: string;
   updated_at: string;
 }
@@ -72,7 +80,7 @@ export interface AuditLog {
   request_id: string;
   actor_user_id: string;
   actor_email: string;
-  action: 'created' | 'updated' | 'deleted' | 'status_changed';
+  action: 'created' | 'updated' | 'deleted' | 'status_changed' | 'linked' | 'unlinked' | 'escalated';
   old_value: Record<string, unknown>;
   new_value: Record<string, unknown>;
   created_at: string;
@@ -28,16 +28,88 @@ EXPECTED_IMPLEMENTATION_ROUTES = {
     ("POST", "/api/v1/auth/passkeys/login/finish"),
     ("GET", "/api/v1/me"),
     ("PATCH", "/api/v1/me"),
+    ("GET", "/api/v1/notifications"),
+    ("GET", "/api/v1/notifications/unread-count"),
+    ("POST", "/api/v1/notifications/read-all"),
+    ("POST", "/api/v1/notifications/{id}/read"),
     ("GET", "/api/v1/preferences"),
     ("PATCH", "/api/v1/preferences"),
     ("GET", "/api/v1/meta/enums"),
+    ("GET", "/api/v1/realtime/schema"),
     ("GET", "/api/v1/assignees/suggestions"),
     ("GET", "/api/v1/requests"),
     ("POST", "/api/v1/requests"),
+    ("POST", "/api/v1/requests/bulk"),
+    ("GET", "/api/v1/requests/export"),
+    ("POST", "/api/v1/requests/import"),
     ("GET", "/api/v1/requests/{id}"),
     ("PATCH", "/api/v1/requests/{id}"),
     ("DELETE", "/api/v1/requests/{id}"),
+    ("GET", "/api/v1/requests/{id}/attachments"),
+    ("GET", "/api/v1/requests/{id}/attachments/{attachment_id}"),
     ("GET", "/api/v1/requests/{id}/audit"),
+    ("GET", "/api/v1/requests/{id}/audit/export"),
+    ("GET", "/api/v1/requests/{id}/checklist"),
+    ("POST", "/api/v1/requests/{id}/checklist"),
+    ("PUT", "/api/v1/requests/{id}/checklist/order"),
+    ("PATCH", "/api/v1/requests/{id}/checklist/{item_id}"),
+    ("DELETE", "/api/v1/requests/{id}/checklist/{item_id}"),
+    ("GET", "/api/v1/requests/{id}/comments"),
+    ("POST", "/api/v1/requests/{id}/comments"),
+    ("DELETE", "/api/v1/requests/{id}/comments/{comment_id}"),
+    ("GET", "/api/v1/requests/{id}/links"),
+    ("POST", "/api/v1/requests/{id}/links"),
+    ("DELETE", "/api/v1/requests/{id}/links/{link_id}"),
+    ("POST", "/api/v1/requests/{id}/watch"),
+    ("DELETE", "/api/v1/requests/{id}/watch"),
+    ("GET", "/api/v1/requests/{id}/watchers"),
+    ("POST", "/api/v1/requests/{id}/watchers"),
+    ("DELETE", "/api/v1/requests/{id}/watchers/{user_id}"),
+    ("GET", "/api/v1/schedules"),
+    ("POST", "/api/v1/schedules"),
+    ("GET", "/api/v1/schedules/{id}"),
+    ("PATCH", "/api/v1/schedules/{id}"),
+    ("DELETE", "/api/v1/schedules/{id}"),
+    ("GET", "/api/v1/templates"),
+    ("POST", "/api/v1/templates"),
+    ("GET", "/api/v1/templates/{id}"),
+    ("PATCH", "/api/v1/templates/{id}"),
+    ("DELETE", "/api/v1/templates/{id}"),
+    ("GET", "/api/v1/views"),
+    ("POST", "/api/v1/views"),
+    ("GET", "/api/v1/views/{id}"),
+    ("PATCH", "/api/v1/views/{id}"),
+    ("DELETE", "/api/v1/views/{id}"),
+    ("GET", "/api/v1/webhooks"),
+    ("POST", "/api/v1/webhooks"),
+    ("GET", "/api/v1/webhooks/{id}"),
+    ("PATCH", "/api/v1/webhooks/{id}"),
+    ("DELETE", "/api/v1/webhooks/{id}"),
+    ("GET", "/api/v1/webhooks/{id}/deliveries"),
+    ("POST", "/api/v1/webhooks/{id}/deliveries/{delivery_id}/redeliver"),
+    ("POST", "/api/v1/inbound/email"),
+    ("GET", "/api/v1/sla/calendars"),
+    ("POST", "/api/v1/sla/calendars"),
+    ("GET", "/api/v1/sla/calendars/{id}"),
+    ("PATCH", "/api/v1/sla/calendars/{id}"),
+    ("DELETE", "/api/v1/sla/calendars/{id}"),
+    ("GET", "/api/v1/sla/policies"),
+    ("POST", "/api/v1/sla/policies"),
+    ("GET", "/api/v1/sla/policies/{id}"),
+    ("PATCH", "/api/v1/sla/policies/{id}"),
+    ("DELETE", "/api/v1/sla/policies/{id}"),
+    ("GET", "/api/v1/escalation-rules"),
+    ("POST", "/api/v1/escalation-rules"),
+    ("GET", "/api/v1/escalation-rules/{id}"),
+    ("PATCH", "/api/v1/escalation-rules/{id}"),
+    ("DELETE", "/api/v1/escalation-rules/{id}"),
+    ("GET", "/api/v1/routing-rules"),
+    ("POST", "/api/v1/routing-rules"),
+    ("GET", "/api/v1/routing-rules/{id}"),
+    ("PATCH", "/api/v1/routing-rules/{id}"),
+    ("DELETE", "/api/v1/routing-rules/{id}"),
+    ("GET", "/api/v1/me/availability"),
+    ("PATCH", "/api/v1/me/availability"),
 }
 
 # Routes intentionally exposed by the service but excluded from OpenAPI docs.
@@ -68,7 +140,7 @@ def parse_openapi_routes(path: Path) -> set[tuple[str, str]]:
             current_path = path_match.group(1)
             continue
 
-        method_match = re.match(r"^\s{4}(get|post|patch|delete):\s*$", raw)
+        method_match = re.match(r"^\s{4}(get|post|put|patch|delete):\s*$", raw)
         if method_match and current_path:
             routes.add((method_match.group(1).upper(), current_path))
 